@@ -0,0 +1,128 @@
+use luma_compiler::{CompilerOptions, LumaCompiler, lints::{LintLevel, LintOptions}};
+use luma_core::{CodeSource, SourceManager};
+use luma_diagnostic::{Diagnostic, JsonPrinter, Printer, SarifPrinter};
+
+const USAGE: &str = "usage: luma [-A <lint>] [-W <lint>] [-D <lint>] [--deny-warnings] [--format pretty|json|sarif]\n       luma explain <code>";
+
+/// How diagnostics are written to stdout
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Pretty,
+    Json,
+    Sarif,
+}
+
+impl Format {
+    fn render(self, sources: &SourceManager, diagnostics: &[Diagnostic]) -> String {
+        match self {
+            Format::Pretty => Printer::print(sources, diagnostics),
+            Format::Json => JsonPrinter::print(sources, diagnostics),
+            Format::Sarif => SarifPrinter::print(sources, diagnostics),
+        }
+    }
+}
+
+struct Args {
+    lints: LintOptions,
+    format: Format,
+}
+
+/// Reads the arguments, `-A`, `-W` and `-D` take a lint or group name
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut lints = LintOptions::new();
+    let mut format = Format::Pretty;
+
+    while let Some(arg) = args.next() {
+        let level = match arg.as_str() {
+            "--deny-warnings" => {
+                lints = lints.deny_warnings(true);
+                continue;
+            }
+            "--format" => {
+                format = match args.next().as_deref() {
+                    Some("pretty") => Format::Pretty,
+                    Some("json") => Format::Json,
+                    Some("sarif") => Format::Sarif,
+                    Some(other) => return Err(format!("unknown format '{other}'")),
+                    None => return Err(String::from("'--format' expects a format")),
+                };
+                continue;
+            }
+            "-A" => LintLevel::Allow,
+            "-W" => LintLevel::Warn,
+            "-D" => LintLevel::Deny,
+            _ => return Err(format!("unknown argument '{arg}'")),
+        };
+
+        let lint = args.next().ok_or_else(|| format!("'{arg}' expects a lint name"))?;
+        lints = lints.set(lint, level);
+    }
+
+    Ok(Args { lints, format })
+}
+
+/// Prints the long-form explanation of a compiler or runtime diagnostic code, `luma explain E0101`
+fn explain(code: Option<String>) -> ! {
+    let Some(code) = code else {
+        eprintln!("'explain' expects a diagnostic code\n{USAGE}");
+        std::process::exit(2);
+    };
+
+    match luma_vm::explain(&code) {
+        Some(explanation) => {
+            print!("{explanation}");
+            std::process::exit(0);
+        }
+        None => {
+            eprintln!("no diagnostic has the code '{code}'");
+            std::process::exit(1);
+        }
+    }
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1).peekable();
+
+    if args.peek().is_some_and(|arg| arg == "explain") {
+        explain(args.nth(1));
+    }
+
+    let args = match parse_args(args) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{message}\n{USAGE}");
+            std::process::exit(2);
+        }
+    };
+
+    let sources = vec![CodeSource::new(
+        include_str!("../../../examples/sample.luma").to_string(),
+        Some("examples/sample.luma".to_string()),
+    )];
+
+    let mut options = CompilerOptions::new();
+    options.lints = args.lints;
+
+    let compiler = LumaCompiler::configure(options);
+    let result = compiler.compile(sources);
+
+    // machine-readable output is a single document, even when there is nothing to report
+    if args.format != Format::Pretty {
+        println!("{}", args.format.render(&result.sources, &result.diagnostics));
+        std::process::exit(if result.result.is_some() { 0 } else { 1 });
+    }
+
+    // warnings are printed even when compilation succeeds
+    if !result.diagnostics.is_empty() {
+        let output = args.format.render(&result.sources, &result.diagnostics);
+        println!("{}", output);
+    }
+
+    let Some(bytecodes) = result.result else {
+        std::process::exit(1);
+    };
+
+    println!("Compilation successful!");
+
+    println!("Bytecode: {:#?}", bytecodes);
+}
@@ -1,10 +1,13 @@
 use luma_core::{CodeSource, CodeSourceId, SourceManager};
-use luma_diagnostic::Diagnostic;
+use luma_diagnostic::{Diagnostic, DiagnosticLevel, warning};
 
-use crate::{AnalyzerStage, AstLoweringStage, CodegenStage, CompilerContext, CompilerOptions, CompilerStage, LexerStage, ParserStage, aast::AnnotatedAst, ast::Ast, bytecode::ModuleBytecode};
+use crate::{AnalyzerStage, AstLoweringStage, CodegenStage, CompilerContext, CompilerOptions, CompilerStage, LexerStage, Library, ModuleResolutionStage, NativeSignature, OptimizerStage, ParserStage, aast::AnnotatedAst, ast::Ast, bytecode::ModuleBytecode, diagnostics::CompilerError, lints::{AnnotAstLint, AstLint, LintRegistry}, stages::analyzer::passes::{_01_ast, _02_aast}};
 
 pub struct LumaCompiler {
     options: CompilerOptions,
+    natives: Vec<NativeSignature>,
+    libraries: Vec<Library>,
+    lints: LintRegistry,
 }
 
 #[derive(Debug)]
@@ -14,21 +17,70 @@ pub struct CompileResult {
     pub result: Option<Vec<ModuleBytecode>>
 }
 
+impl CompileResult {
+    /// Whether compilation failed, the diagnostics may still hold warnings when it didn't
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics.iter().any(|diag| diag.level == DiagnosticLevel::Error)
+    }
+}
+
 impl LumaCompiler {
     pub fn new() -> Self {
         Self {
             options: CompilerOptions::default(),
+            natives: Vec::new(),
+            libraries: Vec::new(),
+            lints: LintRegistry::new(),
         }
     }
 
     pub fn configure(options: CompilerOptions) -> Self {
-        Self { 
-            options
+        Self {
+            options,
+            natives: Vec::new(),
+            libraries: Vec::new(),
+            lints: LintRegistry::new(),
         }
     }
 
+    /// Declares host functions that the compiled sources may call
+    pub fn with_natives(mut self, natives: impl IntoIterator<Item = NativeSignature>) -> Self {
+        self.natives.extend(natives);
+        self
+    }
+
+    /// Registers libraries that the compiled sources may import
+    pub fn with_libraries(mut self, libraries: impl IntoIterator<Item = Library>) -> Self {
+        self.libraries.extend(libraries);
+        self
+    }
+
+    /// Registers a lint run over the analyzed AST of every compiled source
+    pub fn with_lint(mut self, lint: impl AstLint + 'static) -> Self {
+        self.lints.register(lint);
+        self
+    }
+
+    /// Registers a lint run over the lowered AST of every compiled source
+    pub fn with_annot_lint(mut self, lint: impl AnnotAstLint + 'static) -> Self {
+        self.lints.register_annot(lint);
+        self
+    }
+
     pub fn compile(self, sources: impl IntoIterator<Item = CodeSource>) -> CompileResult {
         let mut ctx = CompilerContext::configure(self.options);
+        ctx.natives = self.natives;
+        ctx.lints = self.lints;
+
+        for name in ctx.options.lints.unknown(&ctx.lints) {
+            ctx.add_diag(warning!(CompilerError::UnknownLint { name: name.to_string() }));
+        }
+
+        for library in self.libraries {
+            let source_id = ctx.sources.add_source(library.source);
+            ctx.libraries.insert(library.name, source_id);
+        }
+
         let mut source_ids = Vec::<CodeSourceId>::new();
 
         for source in sources {
@@ -47,10 +99,20 @@ impl LumaCompiler {
     fn run_pipeline(ctx: &CompilerContext, source_ids: Vec<CodeSourceId>) -> Result<Vec<ModuleBytecode>, ()> {
         let tokens = run_stage(ctx, LexerStage, source_ids)?;
         let asts = run_stage(ctx, ParserStage, &tokens)?;
-        let asts = run_stage(ctx, AnalyzerStage::<Ast>::default(), asts)?;
+        let asts = run_stage(ctx, ModuleResolutionStage, asts)?;
+
+        let mut analyzer = AnalyzerStage::<Ast>::default();
+        analyzer.add_pass(_01_ast::RegisteredLints::new(&ctx.lints));
+        let asts = run_stage(ctx, analyzer, asts)?;
+
         let aasts = run_stage(ctx, AstLoweringStage, asts)?;
-        let aasts = run_stage(ctx, AnalyzerStage::<AnnotatedAst>::default(), aasts)?;
+
+        let mut analyzer = AnalyzerStage::<AnnotatedAst>::default();
+        analyzer.add_pass(_02_aast::RegisteredLints::new(&ctx.lints));
+        let aasts = run_stage(ctx, analyzer, aasts)?;
+
         let bytecodes = run_stage(ctx, CodegenStage, aasts)?;
+        let bytecodes = run_stage(ctx, OptimizerStage, bytecodes)?;
 
         Ok(bytecodes)
     }
@@ -69,7 +131,10 @@ where
     ctx.set_stage_name(S::name());
     let output = stage.process(ctx, input);
 
-    if ctx.has_diagnostics() {
+    // a denied lint fails the stage that reported it
+    ctx.options.lints.apply(&ctx.lints, &mut ctx.get_diagnostics_mut());
+
+    if ctx.has_errors() {
         return Err(());
     }
 
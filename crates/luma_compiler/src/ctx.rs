@@ -1,15 +1,22 @@
-use std::cell::{Ref, RefCell, RefMut};
+use std::{
+    cell::{Ref, RefCell, RefMut},
+    collections::HashMap,
+};
 
-use luma_core::SourceManager;
-use luma_diagnostic::Diagnostic;
+use luma_core::{CodeSourceId, SourceManager};
+use luma_diagnostic::{Diagnostic, DiagnosticLevel};
 
-use crate::CompilerOptions;
+use crate::{CompilerOptions, NativeSignature, lints::LintRegistry};
 
 #[derive(Default)]
 pub struct CompilerContext {
     current_stage_name: RefCell<String>,
     
     pub options: CompilerOptions,
+    pub natives: Vec<NativeSignature>,
+    /// module name -> source of the library, see [`crate::Library`]
+    pub libraries: HashMap<String, CodeSourceId>,
+    pub lints: LintRegistry,
     pub sources: SourceManager,
     pub(crate) diagnostics: RefCell<Vec<Diagnostic>>,
 }
@@ -19,7 +26,10 @@ impl CompilerContext {
         Self {
             current_stage_name: RefCell::new(String::new()),
             options,
-            
+            natives: Vec::new(),
+            libraries: HashMap::new(),
+            lints: LintRegistry::new(),
+
             sources: SourceManager::new(),
             diagnostics: RefCell::new(Vec::new()),
         }
@@ -33,6 +43,11 @@ impl CompilerContext {
         !self.diagnostics.borrow().is_empty()
     }
 
+    /// Whether an error was reported, warnings don't stop the pipeline
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics.borrow().iter().any(|diag| diag.level == DiagnosticLevel::Error)
+    }
+
     pub fn get_diagnostics(&self) -> Ref<'_, Vec<Diagnostic>> {
         self.diagnostics.borrow()
     }
@@ -1,13 +1,43 @@
 use luma_core::CodeSourceId;
-use luma_diagnostic::define_diagnostics;
+use luma_diagnostic::{Explanation, define_diagnostics};
+
+use crate::stages::{
+    analyzer::AnalyzerError, codegen::CodegenError, lowering::LoweringError, parser::ParserError,
+    resolver::ResolverError,
+};
 
 define_diagnostics! {
     pub enum CompilerError {
         // general errors
-        #[Error("invalid source id", "the source id '{id:?}' does not exist in the source manager")]
+        #[Error(E0001, "invalid source id", "the source id '{id:?}' does not exist in the source manager")]
+        /// A stage was handed a source that was never added to the compiler, this is a bug in the embedding code.
         InvalidSourceId {
             id: CodeSourceId,
         },
+        #[Warning(W0001, "unknown lint", "no lint or lint group is named '{name}'")]
+        /// A level was configured for a name that isn't a built-in lint, a registered lint or a lint group, so it has no effect.
+        ///
+        /// ```text
+        /// -D unused_varaibles
+        /// ```
+        UnknownLint {
+            name: String,
+        },
     }
 }
 
+/// The explanations of every diagnostic the compiler reports, one catalog per stage
+pub const EXPLANATIONS: &[&[Explanation]] = &[
+    CompilerError::EXPLANATIONS,
+    ParserError::EXPLANATIONS,
+    ResolverError::EXPLANATIONS,
+    AnalyzerError::EXPLANATIONS,
+    LoweringError::EXPLANATIONS,
+    CodegenError::EXPLANATIONS,
+];
+
+/// The explanation of a diagnostic code reported by the compiler, like `E0101`
+#[must_use]
+pub fn explain(code: &str) -> Option<Explanation> {
+    Explanation::find(EXPLANATIONS, code)
+}
@@ -1,21 +1,25 @@
-#![allow(clippy::new_without_default)]
+#![allow(clippy::new_without_default, clippy::result_large_err)]
 #![feature(iterator_try_collect)]
 #![feature(try_blocks)]
 
 mod compiler;
 mod ctx;
 pub mod diagnostics;
+pub mod lints;
 mod options;
 mod representation;
 pub mod stages;
 
+#[cfg(test)]
+mod tests;
+
 pub use compiler::LumaCompiler;
 pub use ctx::CompilerContext;
 pub use options::CompilerOptions;
 pub use representation::*;
 pub use stages::{
     analyzer::AnalyzerStage, codegen::CodegenStage, lexer::LexerStage, lowering::AstLoweringStage,
-    parser::ParserStage,
+    optimizer::OptimizerStage, parser::ParserStage, resolver::ModuleResolutionStage,
 };
 
 pub trait CompilerStage<'stage> {
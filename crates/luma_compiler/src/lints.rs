@@ -0,0 +1,269 @@
+use std::rc::Rc;
+
+use luma_core::Span;
+use luma_diagnostic::{Diagnostic, DiagnosticLevel};
+
+use crate::{
+    aast::{AnnotAstVisitor, AnnotStmt},
+    ast::{AstVisitor, Stmt},
+    options::define_options,
+};
+
+pub const UNUSED_VARIABLES: &str = "unused_variables";
+pub const UNUSED_PARAMETERS: &str = "unused_parameters";
+pub const UNUSED_FUNCTIONS: &str = "unused_functions";
+pub const UNUSED_FIELDS: &str = "unused_fields";
+pub const UNUSED_IMPORTS: &str = "unused_imports";
+
+/// The lints reported by the analyzer itself
+pub const BUILTIN_LINTS: &[LintInfo] = &[
+    LintInfo::new(UNUSED_VARIABLES, LintLevel::Warn, "local variables that are never read"),
+    LintInfo::new(UNUSED_PARAMETERS, LintLevel::Warn, "function parameters that are never read"),
+    LintInfo::new(UNUSED_FUNCTIONS, LintLevel::Warn, "private functions that are never called"),
+    LintInfo::new(UNUSED_FIELDS, LintLevel::Warn, "private struct fields that are never read"),
+    LintInfo::new(UNUSED_IMPORTS, LintLevel::Warn, "imports none of whose items are used"),
+];
+
+/// Lints configured together under a single name
+pub const LINT_GROUPS: &[(&str, &[&str])] = &[(
+    "unused",
+    &[UNUSED_VARIABLES, UNUSED_PARAMETERS, UNUSED_FUNCTIONS, UNUSED_FIELDS, UNUSED_IMPORTS],
+)];
+
+/// Whether `name` is the lint itself or a group containing it
+#[must_use]
+pub fn lint_matches(name: &str, lint: &str) -> bool {
+    name == lint
+        || LINT_GROUPS
+            .iter()
+            .any(|(group, lints)| *group == name && lints.contains(&lint))
+}
+
+/// How the diagnostics of a lint are reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintLevel {
+    /// dropped, `-A`
+    Allow,
+    /// reported as warnings, `-W`
+    Warn,
+    /// reported as errors and fail the compilation, `-D`
+    Deny,
+}
+
+/// Name, default level and description of a lint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LintInfo {
+    pub name: &'static str,
+    /// the level used when the lint isn't configured
+    pub default_level: LintLevel,
+    pub description: &'static str,
+}
+
+impl LintInfo {
+    pub const fn new(name: &'static str, default_level: LintLevel, description: &'static str) -> Self {
+        Self {
+            name,
+            default_level,
+            description,
+        }
+    }
+}
+
+/// A lint checking the resolved and typed AST, run after the built-in analyzer passes.
+///
+/// Diagnostics reported inside a statement allowing the lint, like `@allow(my_lint)`, are dropped
+pub trait AstLint: for<'a> AstVisitor<'a, Ctx = LintContext> {
+    fn info(&self) -> LintInfo;
+}
+
+/// A lint checking the lowered AST, run after the built-in analyzer passes.
+///
+/// Diagnostics reported inside a statement allowing the lint are dropped, an error returned by the walk is reported
+/// as a diagnostic of the lint
+pub trait AnnotAstLint: for<'a> AnnotAstVisitor<'a, Ctx = LintContext> {
+    fn info(&self) -> LintInfo;
+}
+
+/// Collects the diagnostics reported by a lint while it visits a module
+#[derive(Debug)]
+pub struct LintContext {
+    lint: &'static str,
+    pub(crate) diagnostics: Vec<Diagnostic>,
+}
+
+impl LintContext {
+    pub(crate) fn new(lint: &'static str) -> Self {
+        Self {
+            lint,
+            diagnostics: Vec::new(),
+        }
+    }
+
+    /// The name of the running lint
+    #[must_use]
+    pub fn lint(&self) -> &'static str {
+        self.lint
+    }
+
+    /// Reports a diagnostic of the running lint, its level is replaced by the configured or default level of the lint
+    pub fn report(&mut self, diagnostic: Diagnostic) {
+        self.diagnostics.push(diagnostic.lint(self.lint));
+    }
+
+    /// The reported diagnostics, without those inside the spans of the statements allowing the lint
+    fn into_reported(self, allowed: &[Span]) -> impl Iterator<Item = Diagnostic> {
+        self.diagnostics
+            .into_iter()
+            .filter(move |diag| !diag.span.is_some_and(|span| allowed.iter().any(|allowed| contains(allowed, &span))))
+    }
+}
+
+/// A top-level statement of a module, see [`lint_module`]
+pub(crate) trait ModuleStmt {
+    fn span(&self) -> Span;
+}
+
+impl ModuleStmt for Stmt {
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl ModuleStmt for AnnotStmt {
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// Runs a lint over the statements of the module, skipping the statements merged in from imported libraries.
+///
+/// `walk` lints a statement and collects the spans of the statements in it allowing the lint, the diagnostics
+/// reported inside of those are dropped
+pub(crate) fn lint_module<S: ModuleStmt>(
+    lint: &'static str,
+    module: Span,
+    statements: &mut [S],
+    mut walk: impl FnMut(&mut LintContext, &mut Vec<Span>, &mut S),
+) -> Vec<Diagnostic> {
+    let mut lint_ctx = LintContext::new(lint);
+    let mut allowed = Vec::new();
+
+    for stmt in statements.iter_mut().filter(|stmt| stmt.span().source_id == module.source_id) {
+        walk(&mut lint_ctx, &mut allowed, stmt);
+    }
+
+    lint_ctx.into_reported(&allowed).collect()
+}
+
+fn contains(outer: &Span, inner: &Span) -> bool {
+    outer.source_id == inner.source_id && outer.start <= inner.start && inner.end <= outer.end
+}
+
+/// The lints registered with [`crate::LumaCompiler::with_lint`] and [`crate::LumaCompiler::with_annot_lint`]
+#[derive(Default, Clone)]
+pub struct LintRegistry {
+    pub(crate) ast: Vec<Rc<dyn AstLint>>,
+    pub(crate) aast: Vec<Rc<dyn AnnotAstLint>>,
+}
+
+impl LintRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&mut self, lint: impl AstLint + 'static) {
+        self.ast.push(Rc::new(lint));
+    }
+
+    pub fn register_annot(&mut self, lint: impl AnnotAstLint + 'static) {
+        self.aast.push(Rc::new(lint));
+    }
+
+    /// Every known lint, the built-in ones first
+    pub fn lints(&self) -> impl Iterator<Item = LintInfo> + '_ {
+        BUILTIN_LINTS
+            .iter()
+            .copied()
+            .chain(self.ast.iter().map(|lint| lint.info()))
+            .chain(self.aast.iter().map(|lint| lint.info()))
+    }
+
+    #[must_use]
+    pub fn find(&self, name: &str) -> Option<LintInfo> {
+        self.lints().find(|info| info.name == name)
+    }
+}
+
+// `levels` holds the levels of lints and lint groups, a later entry overrides the earlier ones.
+// `deny_warnings` reports every warning as an error, `--deny-warnings`
+define_options! {
+    pub struct LintOptions {
+        levels: Vec<(String, LintLevel)>,
+        deny_warnings: bool,
+    }
+}
+
+impl LintOptions {
+    pub fn allow(self, lint: impl Into<String>) -> Self {
+        self.set(lint, LintLevel::Allow)
+    }
+
+    pub fn warn(self, lint: impl Into<String>) -> Self {
+        self.set(lint, LintLevel::Warn)
+    }
+
+    pub fn deny(self, lint: impl Into<String>) -> Self {
+        self.set(lint, LintLevel::Deny)
+    }
+
+    pub fn set(mut self, lint: impl Into<String>, level: LintLevel) -> Self {
+        self.levels.push((lint.into(), level));
+        self
+    }
+
+    pub fn deny_warnings(mut self, deny_warnings: bool) -> Self {
+        self.deny_warnings = deny_warnings;
+        self
+    }
+
+    /// The configured level of the lint, `None` keeps the level it was reported with
+    #[must_use]
+    pub fn level(&self, lint: &str) -> Option<LintLevel> {
+        self.levels
+            .iter()
+            .rev()
+            .find(|(name, _)| lint_matches(name, lint))
+            .map(|(_, level)| *level)
+    }
+
+    /// The configured names that are neither a known lint nor a lint group, in the order they were configured
+    pub(crate) fn unknown<'a>(&'a self, registry: &'a LintRegistry) -> impl Iterator<Item = &'a str> {
+        self.levels
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .filter(|name| registry.find(name).is_none() && !LINT_GROUPS.iter().any(|(group, _)| group == name))
+    }
+
+    /// Drops the allowed diagnostics and raises the denied ones to errors, unconfigured lints use their default level
+    pub(crate) fn apply(&self, registry: &LintRegistry, diagnostics: &mut Vec<Diagnostic>) {
+        diagnostics.retain_mut(|diag| {
+            let level = diag
+                .lint
+                .as_deref()
+                .and_then(|lint| self.level(lint).or_else(|| registry.find(lint).map(|info| info.default_level)));
+
+            match level {
+                Some(LintLevel::Allow) => return false,
+                Some(LintLevel::Warn) => diag.level = DiagnosticLevel::Warning,
+                Some(LintLevel::Deny) => diag.level = DiagnosticLevel::Error,
+                None => {}
+            }
+
+            if self.deny_warnings && diag.level == DiagnosticLevel::Warning {
+                diag.level = DiagnosticLevel::Error;
+            }
+
+            true
+        });
+    }
+}
@@ -34,11 +34,16 @@ mod macros {
 
 pub(crate) use macros::define_options;
 
-use crate::stages::lexer::LexerOptions;
+use crate::{
+    lints::LintOptions,
+    stages::{lexer::LexerOptions, optimizer::OptimizerOptions},
+};
 
 define_options! {
     pub struct CompilerOptions {
         lexer: LexerOptions,
+        optimizer: OptimizerOptions,
+        lints: LintOptions,
     }
 }
 
@@ -25,16 +25,31 @@ impl AnnotExpr {
 #[derive(Display, Debug, Clone, PartialEq)]
 #[strum(serialize_all = "lowercase")]
 pub enum AnnotExprKind {
+    /// `&variable`, a reference to a variable
+    AddressOf(Box<AnnotExpr>),
     Assign(AssignAnnotExpr),
     Binary(BinaryAnnotExpr),
     Block(BlockAnnotExpr),
     Call(CallAnnotExpr),
+    /// `value catch e { ... }`, unwraps a result or evaluates the handler with its error
+    Catch(CatchAnnotExpr),
+    /// `*reference`, the variable a reference refers to, which can be assigned to
+    Deref(Box<AnnotExpr>),
+    /// `err(value)`, the error of a result
+    Err(Box<AnnotExpr>),
     Get(GetAnnotExpr),
     Group(Box<AnnotExpr>),
     Ident(IdentAnnotExpr),
     If(IfAnnotExpr),
+    Interpolation(InterpolationAnnotExpr),
     Literal(LiteralAnnotExpr),
+    /// `ok(value)`, the value of a result
+    Ok(Box<AnnotExpr>),
+    /// `value?`, unwraps an optional or a result, returning `none` or the error from the enclosing function
+    Propagate(Box<AnnotExpr>),
     Struct(StructAnnotExpr),
+    /// `try value`, turns runtime errors raised while evaluating the value into an error result
+    Try(Box<AnnotExpr>),
     TupleLiteral(TupleAnnotExpr),
     Unary(UnaryAnnotExpr),
 }
@@ -79,6 +94,14 @@ pub struct CallAnnotExpr {
     pub arguments: Vec<AnnotExpr>,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatchAnnotExpr {
+    pub value: Box<AnnotExpr>,
+    /// the local the error is bound to for the handler
+    pub binding: AnnotSymbol,
+    pub handler: Box<AnnotExpr>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct GetAnnotExpr {
     pub object: Box<AnnotExpr>,
@@ -92,11 +115,24 @@ pub struct IdentAnnotExpr {
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct IfAnnotExpr {
+    /// the local the unwrapped condition is bound to, see [`IfExpr`](crate::ast::IfExpr)
+    pub binding: Option<AnnotSymbol>,
     pub condition: Box<AnnotExpr>,
     pub then_branch: Box<AnnotExpr>,
     pub else_branch: Option<Box<AnnotExpr>>,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct InterpolationAnnotExpr {
+    pub parts: Vec<InterpolationAnnotPart>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InterpolationAnnotPart {
+    Literal(String),
+    Expr(AnnotExpr),
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum LiteralAnnotExpr {
     Int(IntLiteralAnnotExpr),
@@ -105,6 +141,7 @@ pub enum LiteralAnnotExpr {
     Char(char),
     String(String),
     Unit,
+    None,
 }
 
 #[derive(Debug, Clone, PartialEq)]
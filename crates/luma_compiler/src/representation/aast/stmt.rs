@@ -2,13 +2,15 @@ use strum::Display;
 
 use luma_core::Span;
 
-use crate::{Type, Visibility, aast::*, ScopeId};
+use crate::{Type, Visibility, aast::*, ast::Attribute, ScopeId};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct AnnotStmt {
     pub item: AnnotStmtKind,
     pub scope_id: ScopeId,
     pub span: Span,
+    /// the attributes of the statement it was lowered from
+    pub attributes: Vec<Attribute>,
 }
 
 impl AnnotStmt {
@@ -16,9 +18,16 @@ impl AnnotStmt {
         AnnotStmt { 
             item,
             scope_id,
-            span
+            span,
+            attributes: Vec::new(),
         }
     }
+
+    /// Whether an `@allow(..)` attribute of the statement names the lint or its group
+    #[must_use]
+    pub fn allows(&self, lint: &str) -> bool {
+        self.attributes.iter().any(|attribute| attribute.allows(lint))
+    }
 }
 
 #[derive(Display, Debug, Clone, PartialEq)]
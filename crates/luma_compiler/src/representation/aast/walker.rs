@@ -73,11 +73,21 @@ pub trait AnnotAstVisitor<'a> {
                     self.walk_expr(ctx, arg)?;
                 }
             },
+            AnnotExprKind::Catch(catch_expr) => {
+                self.walk_expr(ctx, &mut catch_expr.value)?;
+                self.walk_expr(ctx, &mut catch_expr.handler)?;
+            },
             AnnotExprKind::Get(get_expr) => {
                 self.walk_expr(ctx, &mut get_expr.object)?;
             },
-            AnnotExprKind::Group(group) => {
-                self.walk_expr(ctx, group)?;
+            AnnotExprKind::AddressOf(inner)
+            | AnnotExprKind::Deref(inner)
+            | AnnotExprKind::Group(inner)
+            | AnnotExprKind::Propagate(inner)
+            | AnnotExprKind::Ok(inner)
+            | AnnotExprKind::Err(inner)
+            | AnnotExprKind::Try(inner) => {
+                self.walk_expr(ctx, inner)?;
             },
             AnnotExprKind::If(if_expr) => {
                 self.walk_expr(ctx, &mut if_expr.condition)?;
@@ -87,6 +97,13 @@ pub trait AnnotAstVisitor<'a> {
                     self.walk_expr(ctx, else_branch)?;
                 }
             },
+            AnnotExprKind::Interpolation(interpolation_expr) => {
+                for part in &mut interpolation_expr.parts {
+                    if let InterpolationAnnotPart::Expr(expr) = part {
+                        self.walk_expr(ctx, expr)?;
+                    }
+                }
+            },
             AnnotExprKind::Struct(struct_expr) => {
                 for field in &mut struct_expr.fields {
                     self.walk_expr(ctx, &mut field.value)?;
@@ -155,9 +172,13 @@ pub trait AnnotAstVisitor<'a> {
         self.try_visit_type(ctx, ty)?;
 
         match &mut ty.kind {
-            TypeKind::Ptr(ty) => {
+            TypeKind::Ptr(ty) | TypeKind::Optional(ty) => {
                 self.walk_type(ctx, ty)?;
             },
+            TypeKind::Result(value, error) => {
+                self.walk_type(ctx, value)?;
+                self.walk_type(ctx, error)?;
+            },
             TypeKind::Tuple(types) => {
                 for elem_type in types {
                     self.walk_type(ctx, elem_type)?;
@@ -1,7 +1,7 @@
 use luma_core::Span;
 use strum::Display;
 
-use crate::{TypeKind, ast::*};
+use crate::{ScopeId, TypeKind, ast::*};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Expr {
@@ -33,16 +33,31 @@ impl Expr {
 #[derive(Display, Debug, Clone, PartialEq)]
 #[strum(serialize_all = "lowercase")]
 pub enum ExprKind {
+    /// `&variable`, a reference to a variable
+    AddressOf(Box<Expr>),
     Assign(AssignExpr),
     Binary(BinaryExpr),
     Block(BlockExpr),
     Call(CallExpr),
+    /// `value catch e { ... }`, unwraps a result or evaluates the handler with its error
+    Catch(CatchExpr),
+    /// `*reference`, the variable a reference refers to, which can be assigned to
+    Deref(Box<Expr>),
+    /// `err(value)`, the error of a result
+    Err(Box<Expr>),
     Get(GetExpr),
     Group(Box<Expr>),
     Ident(IdentExpr),
     If(IfExpr),
+    Interpolation(InterpolationExpr),
     Literal(LiteralExpr),
+    /// `ok(value)`, the value of a result
+    Ok(Box<Expr>),
+    /// `value?`, unwraps an optional or a result, returning `none` or the error from the enclosing function
+    Propagate(Box<Expr>),
     Struct(StructExpr),
+    /// `try value`, turns runtime errors raised while evaluating the value into an error result
+    Try(Box<Expr>),
     TupleLiteral(TupleExpr),
     Unary(UnaryExpr),
 }
@@ -73,6 +88,14 @@ pub struct CallExpr {
     pub arguments: Vec<Expr>,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatchExpr {
+    pub value: Box<Expr>,
+    /// the error of the value, declared for the handler
+    pub binding: Binding,
+    pub handler: Box<Expr>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct GetExpr {
     pub object: Box<Expr>,
@@ -86,11 +109,33 @@ pub struct IdentExpr {
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct IfExpr {
+    /// `if var x = maybe { ... }` binds the unwrapped condition for the then branch
+    pub binding: Option<Binding>,
     pub condition: Box<Expr>,
     pub then_branch: Box<Expr>,
     pub else_branch: Option<Box<Expr>>,
 }
 
+/// A variable declared by an expression for one of its branches
+#[derive(Debug, Clone, PartialEq)]
+pub struct Binding {
+    pub symbol: Symbol,
+    /// the scope around the branch the symbol is declared in
+    pub scope_id: Option<ScopeId>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InterpolationExpr {
+    pub parts: Vec<InterpolationPart>,
+}
+
+/// A piece of an interpolated string, empty literal segments are left out
+#[derive(Debug, Clone, PartialEq)]
+pub enum InterpolationPart {
+    Literal(String),
+    Expr(Expr),
+}
+
 #[derive(Display, Debug, Clone, PartialEq)]
 pub enum LiteralExpr {
     Int(u64),
@@ -99,6 +144,7 @@ pub enum LiteralExpr {
     Char(char),
     String(String),
     Unit,
+    None,
 }
 
 #[derive(Debug, Clone, PartialEq)]
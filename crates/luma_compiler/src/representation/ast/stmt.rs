@@ -2,13 +2,14 @@ use strum::Display;
 
 use luma_core::Span;
 
-use crate::{Type, Visibility, ast::*};
+use crate::{Type, Visibility, ast::*, lints::lint_matches};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Stmt {
     pub item: StmtKind,
     pub scope_id: Option<usize>,
     pub span: Span,
+    pub attributes: Vec<Attribute>,
 }
 
 impl Stmt {
@@ -16,20 +17,44 @@ impl Stmt {
         Stmt { 
             item,
             scope_id: None,
-            span
+            span,
+            attributes: Vec::new(),
         }
     }
 
+    /// Whether an `@allow(..)` attribute of the statement names the lint or its group
+    #[must_use]
+    pub fn allows(&self, lint: &str) -> bool {
+        self.attributes.iter().any(|attribute| attribute.allows(lint))
+    }
+
     pub fn set_scope_id(&mut self, scope_id: usize) {
         self.scope_id = Some(scope_id);
     }
 }
 
+/// `@allow(unused)`, placed before a statement to configure its analysis
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attribute {
+    pub name: Symbol,
+    pub arguments: Vec<Symbol>,
+    pub span: Span,
+}
+
+impl Attribute {
+    /// Whether this is an `@allow(..)` naming the lint or its group
+    #[must_use]
+    pub fn allows(&self, lint: &str) -> bool {
+        self.name.name() == "allow" && self.arguments.iter().any(|argument| lint_matches(argument.name(), lint))
+    }
+}
+
 #[derive(Display, Debug, Clone, PartialEq)]
 #[strum(serialize_all = "lowercase")]
 pub enum StmtKind {
     Expr(Expr),
     Func(FuncDeclStmt),
+    Import(ImportStmt),
     Return(ReturnStmt),
     Struct(StructDeclStmt),
     Var(VarDeclStmt),
@@ -53,6 +78,20 @@ pub struct FuncParam {
     pub scope_id: Option<usize>,
 }
 
+/// `import std.math;`, brings the declarations of a library into the module
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportStmt {
+    pub path: Vec<Symbol>,
+}
+
+impl ImportStmt {
+    /// The dotted name of the imported module
+    #[must_use]
+    pub fn module_name(&self) -> String {
+        self.path.iter().map(|segment| segment.name()).collect::<Vec<_>>().join(".")
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ReturnStmt {
     pub value: Option<Expr>,
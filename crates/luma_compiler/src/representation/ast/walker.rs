@@ -22,6 +22,8 @@ pub trait AstVisitor<'a> {
     fn visit_struct_expr_field<'node>(&self, ctx: &mut Self::Ctx, struct_expr: &'node StructExpr, field: &'node mut StructExprField) {}
     fn leave_struct_expr_field<'node>(&self, ctx: &mut Self::Ctx, struct_expr: &'node StructExpr, field: &'node mut StructExprField) {}
     
+    fn visit_binding(&self, ctx: &mut Self::Ctx, binding: &mut Binding) {}
+
     fn enter_scope(&self, ctx: &mut Self::Ctx, entering_scope_id: Option<ScopeId>) {}
     fn exit_scope(&self, ctx: &mut Self::Ctx, leaving_scope_id: Option<ScopeId>) {} 
 
@@ -63,6 +65,9 @@ pub trait AstVisitor<'a> {
 
                 self.exit_scope(ctx, stmt.scope_id);
             },
+            StmtKind::Import(_) => {
+                // the imported declarations are walked where the resolver placed them
+            },
             StmtKind::Return(return_stmt) => {
                 if let Some(value) = &mut return_stmt.value {
                     self.walk_expr(ctx, value);
@@ -113,20 +118,51 @@ pub trait AstVisitor<'a> {
                     self.walk_expr(ctx, arg);
                 }
             },
+            ExprKind::Catch(catch_expr) => {
+                self.walk_expr(ctx, &mut catch_expr.value);
+
+                // the error is only visible in the handler
+                self.enter_scope(ctx, catch_expr.binding.scope_id);
+                self.visit_binding(ctx, &mut catch_expr.binding);
+                self.walk_expr(ctx, &mut catch_expr.handler);
+                self.exit_scope(ctx, catch_expr.binding.scope_id);
+            },
             ExprKind::Get(get_expr) => {
                 self.walk_expr(ctx, &mut get_expr.object);
             },
-            ExprKind::Group(group) => {
-                self.walk_expr(ctx, group);
+            ExprKind::AddressOf(inner)
+            | ExprKind::Deref(inner)
+            | ExprKind::Group(inner)
+            | ExprKind::Propagate(inner)
+            | ExprKind::Ok(inner)
+            | ExprKind::Err(inner)
+            | ExprKind::Try(inner) => {
+                self.walk_expr(ctx, inner);
             },
             ExprKind::If(if_expr) => {
                 self.walk_expr(ctx, &mut if_expr.condition);
-                self.walk_expr(ctx, &mut if_expr.then_branch);
+
+                // the binding is only visible in the then branch
+                if let Some(binding) = &mut if_expr.binding {
+                    self.enter_scope(ctx, binding.scope_id);
+                    self.visit_binding(ctx, binding);
+                    self.walk_expr(ctx, &mut if_expr.then_branch);
+                    self.exit_scope(ctx, binding.scope_id);
+                } else {
+                    self.walk_expr(ctx, &mut if_expr.then_branch);
+                }
 
                 if let Some(else_branch) = &mut if_expr.else_branch {
                     self.walk_expr(ctx, else_branch);
                 }
             },
+            ExprKind::Interpolation(interpolation_expr) => {
+                for part in &mut interpolation_expr.parts {
+                    if let InterpolationPart::Expr(expr) = part {
+                        self.walk_expr(ctx, expr);
+                    }
+                }
+            },
             ExprKind::Struct(struct_expr) => {
                 let ptr = struct_expr as *const StructExpr;
 
@@ -159,9 +195,13 @@ pub trait AstVisitor<'a> {
         self.visit_type(ctx, ty);
 
         match &mut ty.kind {
-            TypeKind::Ptr(ty) => {
+            TypeKind::Ptr(ty) | TypeKind::Optional(ty) => {
                 self.walk_type(ctx, ty);
             },
+            TypeKind::Result(value, error) => {
+                self.walk_type(ctx, value);
+                self.walk_type(ctx, error);
+            },
             TypeKind::Tuple(types) => {
                 for elem_type in types {
                     self.walk_type(ctx, elem_type);
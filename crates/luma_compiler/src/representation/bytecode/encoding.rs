@@ -0,0 +1,223 @@
+use crate::bytecode::{Opcode, op};
+
+impl Opcode {
+    /// The byte identifying the instruction in an encoded instruction stream
+    #[must_use]
+    pub const fn byte(&self) -> u8 {
+        match self {
+            Opcode::GetLocal(_) => op::GET_LOCAL,
+            Opcode::SetLocal(_) => op::SET_LOCAL,
+            Opcode::Pop => op::POP,
+            Opcode::Dup => op::DUP,
+            Opcode::Return => op::RETURN,
+            Opcode::LoadConst(_) => op::LOAD_CONST,
+            Opcode::PushUnit => op::PUSH_UNIT,
+            Opcode::PushNone => op::PUSH_NONE,
+            Opcode::Jump(_) => op::JUMP,
+            Opcode::JumpIfTrue(_) => op::JUMP_IF_TRUE,
+            Opcode::JumpIfFalse(_) => op::JUMP_IF_FALSE,
+            Opcode::Call(_) => op::CALL,
+            Opcode::CallNative(_) => op::CALL_NATIVE,
+            Opcode::CallIntrinsic(_) => op::CALL_INTRINSIC,
+            Opcode::Concat(_) => op::CONCAT,
+            Opcode::Add => op::ADD,
+            Opcode::Sub => op::SUB,
+            Opcode::Mul => op::MUL,
+            Opcode::Div => op::DIV,
+            Opcode::Mod => op::MOD,
+            Opcode::BitAnd => op::BIT_AND,
+            Opcode::BitOr => op::BIT_OR,
+            Opcode::BitXor => op::BIT_XOR,
+            Opcode::ShiftLeft => op::SHIFT_LEFT,
+            Opcode::ShiftRight => op::SHIFT_RIGHT,
+            Opcode::Equal => op::EQUAL,
+            Opcode::GreaterThan => op::GREATER_THAN,
+            Opcode::LesserThan => op::LESSER_THAN,
+            Opcode::GreaterThanEqual => op::GREATER_THAN_EQUAL,
+            Opcode::LesserThanEqual => op::LESSER_THAN_EQUAL,
+            Opcode::NotEqual => op::NOT_EQUAL,
+            Opcode::And => op::AND,
+            Opcode::Or => op::OR,
+            Opcode::Negate => op::NEGATE,
+            Opcode::Not => op::NOT,
+            Opcode::Try(_) => op::TRY,
+            Opcode::EndTry => op::END_TRY,
+            Opcode::WrapErr => op::WRAP_ERR,
+            Opcode::IsErr => op::IS_ERR,
+            Opcode::UnwrapErr => op::UNWRAP_ERR,
+            Opcode::RefLocal(_) => op::REF_LOCAL,
+            Opcode::LoadRef => op::LOAD_REF,
+            Opcode::StoreRef => op::STORE_REF,
+            Opcode::GetGlobal(_) => op::GET_GLOBAL,
+            Opcode::SetGlobal(_) => op::SET_GLOBAL,
+            Opcode::RefGlobal(_) => op::REF_GLOBAL,
+            Opcode::GetLocalWide(_) => op::GET_LOCAL_WIDE,
+            Opcode::SetLocalWide(_) => op::SET_LOCAL_WIDE,
+            Opcode::LoadConstWide(_) => op::LOAD_CONST_WIDE,
+            Opcode::JumpWide(_) => op::JUMP_WIDE,
+            Opcode::JumpIfTrueWide(_) => op::JUMP_IF_TRUE_WIDE,
+            Opcode::JumpIfFalseWide(_) => op::JUMP_IF_FALSE_WIDE,
+            Opcode::CallWide(_) => op::CALL_WIDE,
+            Opcode::CallNativeWide(_) => op::CALL_NATIVE_WIDE,
+            Opcode::TryWide(_) => op::TRY_WIDE,
+            Opcode::RefLocalWide(_) => op::REF_LOCAL_WIDE,
+            Opcode::GetGlobalWide(_) => op::GET_GLOBAL_WIDE,
+            Opcode::SetGlobalWide(_) => op::SET_GLOBAL_WIDE,
+            Opcode::RefGlobalWide(_) => op::REF_GLOBAL_WIDE,
+        }
+    }
+
+    /// Amount of bytes the instruction takes up once encoded, including its operand
+    #[must_use]
+    pub const fn encoded_len(&self) -> usize {
+        match self {
+            Opcode::GetLocal(_)
+            | Opcode::SetLocal(_)
+            | Opcode::LoadConst(_)
+            | Opcode::Jump(_)
+            | Opcode::JumpIfTrue(_)
+            | Opcode::JumpIfFalse(_)
+            | Opcode::Call(_)
+            | Opcode::CallNative(_)
+            | Opcode::CallIntrinsic(_)
+            | Opcode::Concat(_)
+            | Opcode::Try(_)
+            | Opcode::RefLocal(_)
+            | Opcode::GetGlobal(_)
+            | Opcode::SetGlobal(_)
+            | Opcode::RefGlobal(_) => 3,
+            Opcode::GetLocalWide(_)
+            | Opcode::SetLocalWide(_)
+            | Opcode::LoadConstWide(_)
+            | Opcode::JumpWide(_)
+            | Opcode::JumpIfTrueWide(_)
+            | Opcode::JumpIfFalseWide(_)
+            | Opcode::CallWide(_)
+            | Opcode::CallNativeWide(_)
+            | Opcode::TryWide(_)
+            | Opcode::RefLocalWide(_)
+            | Opcode::GetGlobalWide(_)
+            | Opcode::SetGlobalWide(_)
+            | Opcode::RefGlobalWide(_) => 5,
+            _ => 1,
+        }
+    }
+
+    /// Appends the encoded instruction to `out`
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        out.push(self.byte());
+
+        match self {
+            Opcode::GetLocal(operand) => out.extend_from_slice(&operand.to_le_bytes()),
+            Opcode::SetLocal(operand) => out.extend_from_slice(&operand.to_le_bytes()),
+            Opcode::LoadConst(operand) => out.extend_from_slice(&operand.to_le_bytes()),
+            Opcode::Jump(operand) => out.extend_from_slice(&operand.to_le_bytes()),
+            Opcode::JumpIfTrue(operand) => out.extend_from_slice(&operand.to_le_bytes()),
+            Opcode::JumpIfFalse(operand) => out.extend_from_slice(&operand.to_le_bytes()),
+            Opcode::Call(operand) => out.extend_from_slice(&operand.to_le_bytes()),
+            Opcode::CallNative(operand) => out.extend_from_slice(&operand.to_le_bytes()),
+            Opcode::CallIntrinsic(operand) => out.extend_from_slice(&operand.to_le_bytes()),
+            Opcode::Concat(operand) => out.extend_from_slice(&operand.to_le_bytes()),
+            Opcode::Try(operand) => out.extend_from_slice(&operand.to_le_bytes()),
+            Opcode::RefLocal(operand) => out.extend_from_slice(&operand.to_le_bytes()),
+            Opcode::GetGlobal(operand) => out.extend_from_slice(&operand.to_le_bytes()),
+            Opcode::SetGlobal(operand) => out.extend_from_slice(&operand.to_le_bytes()),
+            Opcode::RefGlobal(operand) => out.extend_from_slice(&operand.to_le_bytes()),
+            Opcode::GetLocalWide(operand) => out.extend_from_slice(&operand.to_le_bytes()),
+            Opcode::SetLocalWide(operand) => out.extend_from_slice(&operand.to_le_bytes()),
+            Opcode::LoadConstWide(operand) => out.extend_from_slice(&operand.to_le_bytes()),
+            Opcode::JumpWide(operand) => out.extend_from_slice(&operand.to_le_bytes()),
+            Opcode::JumpIfTrueWide(operand) => out.extend_from_slice(&operand.to_le_bytes()),
+            Opcode::JumpIfFalseWide(operand) => out.extend_from_slice(&operand.to_le_bytes()),
+            Opcode::CallWide(operand) => out.extend_from_slice(&operand.to_le_bytes()),
+            Opcode::CallNativeWide(operand) => out.extend_from_slice(&operand.to_le_bytes()),
+            Opcode::TryWide(operand) => out.extend_from_slice(&operand.to_le_bytes()),
+            Opcode::RefLocalWide(operand) => out.extend_from_slice(&operand.to_le_bytes()),
+            Opcode::GetGlobalWide(operand) => out.extend_from_slice(&operand.to_le_bytes()),
+            Opcode::SetGlobalWide(operand) => out.extend_from_slice(&operand.to_le_bytes()),
+            Opcode::RefGlobalWide(operand) => out.extend_from_slice(&operand.to_le_bytes()),
+            _ => {}
+        }
+    }
+
+    /// Decodes the instruction at the start of `bytes`, returning it along with its encoded length.
+    ///
+    /// Returns `None` for unknown opcode bytes or if the operand is cut off
+    #[must_use]
+    pub fn decode(bytes: &[u8]) -> Option<(Opcode, usize)> {
+        let opcode = match *bytes.first()? {
+            op::GET_LOCAL => Opcode::GetLocal(read_u16(bytes)?),
+            op::SET_LOCAL => Opcode::SetLocal(read_u16(bytes)?),
+            op::POP => Opcode::Pop,
+            op::DUP => Opcode::Dup,
+            op::RETURN => Opcode::Return,
+            op::LOAD_CONST => Opcode::LoadConst(read_u16(bytes)?),
+            op::PUSH_UNIT => Opcode::PushUnit,
+            op::PUSH_NONE => Opcode::PushNone,
+            op::JUMP => Opcode::Jump(read_u16(bytes)?),
+            op::JUMP_IF_TRUE => Opcode::JumpIfTrue(read_u16(bytes)?),
+            op::JUMP_IF_FALSE => Opcode::JumpIfFalse(read_u16(bytes)?),
+            op::CALL => Opcode::Call(read_u16(bytes)?),
+            op::CALL_NATIVE => Opcode::CallNative(read_u16(bytes)?),
+            op::CALL_INTRINSIC => Opcode::CallIntrinsic(read_u16(bytes)?),
+            op::CONCAT => Opcode::Concat(read_u16(bytes)?),
+            op::ADD => Opcode::Add,
+            op::SUB => Opcode::Sub,
+            op::MUL => Opcode::Mul,
+            op::DIV => Opcode::Div,
+            op::MOD => Opcode::Mod,
+            op::BIT_AND => Opcode::BitAnd,
+            op::BIT_OR => Opcode::BitOr,
+            op::BIT_XOR => Opcode::BitXor,
+            op::SHIFT_LEFT => Opcode::ShiftLeft,
+            op::SHIFT_RIGHT => Opcode::ShiftRight,
+            op::EQUAL => Opcode::Equal,
+            op::GREATER_THAN => Opcode::GreaterThan,
+            op::LESSER_THAN => Opcode::LesserThan,
+            op::GREATER_THAN_EQUAL => Opcode::GreaterThanEqual,
+            op::LESSER_THAN_EQUAL => Opcode::LesserThanEqual,
+            op::NOT_EQUAL => Opcode::NotEqual,
+            op::AND => Opcode::And,
+            op::OR => Opcode::Or,
+            op::NEGATE => Opcode::Negate,
+            op::NOT => Opcode::Not,
+            op::TRY => Opcode::Try(read_u16(bytes)?),
+            op::END_TRY => Opcode::EndTry,
+            op::WRAP_ERR => Opcode::WrapErr,
+            op::IS_ERR => Opcode::IsErr,
+            op::UNWRAP_ERR => Opcode::UnwrapErr,
+            op::REF_LOCAL => Opcode::RefLocal(read_u16(bytes)?),
+            op::LOAD_REF => Opcode::LoadRef,
+            op::STORE_REF => Opcode::StoreRef,
+            op::GET_GLOBAL => Opcode::GetGlobal(read_u16(bytes)?),
+            op::SET_GLOBAL => Opcode::SetGlobal(read_u16(bytes)?),
+            op::REF_GLOBAL => Opcode::RefGlobal(read_u16(bytes)?),
+            op::GET_LOCAL_WIDE => Opcode::GetLocalWide(read_u32(bytes)?),
+            op::SET_LOCAL_WIDE => Opcode::SetLocalWide(read_u32(bytes)?),
+            op::LOAD_CONST_WIDE => Opcode::LoadConstWide(read_u32(bytes)?),
+            op::JUMP_WIDE => Opcode::JumpWide(read_u32(bytes)?),
+            op::JUMP_IF_TRUE_WIDE => Opcode::JumpIfTrueWide(read_u32(bytes)?),
+            op::JUMP_IF_FALSE_WIDE => Opcode::JumpIfFalseWide(read_u32(bytes)?),
+            op::CALL_WIDE => Opcode::CallWide(read_u32(bytes)?),
+            op::CALL_NATIVE_WIDE => Opcode::CallNativeWide(read_u32(bytes)?),
+            op::TRY_WIDE => Opcode::TryWide(read_u32(bytes)?),
+            op::REF_LOCAL_WIDE => Opcode::RefLocalWide(read_u32(bytes)?),
+            op::GET_GLOBAL_WIDE => Opcode::GetGlobalWide(read_u32(bytes)?),
+            op::SET_GLOBAL_WIDE => Opcode::SetGlobalWide(read_u32(bytes)?),
+            op::REF_GLOBAL_WIDE => Opcode::RefGlobalWide(read_u32(bytes)?),
+            _ => return None,
+        };
+
+        Some((opcode, opcode.encoded_len()))
+    }
+}
+
+#[inline]
+fn read_u16(bytes: &[u8]) -> Option<u16> {
+    Some(u16::from_le_bytes(bytes.get(1..3)?.try_into().ok()?))
+}
+
+#[inline]
+fn read_u32(bytes: &[u8]) -> Option<u32> {
+    Some(u32::from_le_bytes(bytes.get(1..5)?.try_into().ok()?))
+}
@@ -0,0 +1,27 @@
+use crate::TypeKind;
+
+/// Fields of a struct declared in the module, host types are checked against it
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructLayout {
+    pub name: String,
+    pub fields: Vec<FieldLayout>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldLayout {
+    pub name: String,
+    pub ty: TypeKind,
+}
+
+impl StructLayout {
+    pub fn get_field(&self, name: &str) -> Option<&FieldLayout> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+}
+
+/// A module-level variable, host values written to it are checked against its type
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlobalLayout {
+    pub name: String,
+    pub ty: TypeKind,
+}
@@ -1,21 +1,61 @@
-use crate::stages::codegen::chunk::FunctionChunk;
+use crate::stages::codegen::{chunk::FunctionChunk, stores::ExportTable};
 
+mod encoding;
+mod layout;
+pub mod op;
 mod opcode;
+mod serialize;
 use luma_core::CodeSourceId;
+pub use layout::{FieldLayout, GlobalLayout, StructLayout};
 pub use opcode::Opcode;
+pub use serialize::{FORMAT_VERSION, MAGIC};
 
 mod value;
 pub use value::BytecodeValue;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ModuleBytecode {
     pub source_id: CodeSourceId,
     pub constants: Vec<BytecodeValue>,
     pub functions: Vec<FunctionChunk>,
+
+    /// names of the host functions called by the module, indexed by `CallNative`
+    pub natives: Vec<String>,
+    pub exports: ExportTable,
+    pub structs: Vec<StructLayout>,
+
+    /// module-level variables, indexed by `GetGlobal` and `SetGlobal`
+    pub globals: Vec<GlobalLayout>,
 }
 
 impl ModuleBytecode {
     pub fn get_init_chunk(&self) -> Option<&FunctionChunk> {
         self.functions.first()
     }
+
+    /// Returns the index and chunk of an exported function
+    pub fn get_export(&self, name: &str) -> Option<(usize, &FunctionChunk)> {
+        let index = self.exports.get_function(name)? as usize;
+        Some((index, self.functions.get(index)?))
+    }
+
+    /// Returns the index and layout of an exported global
+    pub fn get_global_export(&self, name: &str) -> Option<(usize, &GlobalLayout)> {
+        let index = self.exports.get_variable(name)? as usize;
+        Some((index, self.globals.get(index)?))
+    }
+
+    pub fn get_struct(&self, name: &str) -> Option<&StructLayout> {
+        self.structs.iter().find(|layout| layout.name == name)
+    }
+
+    /// Renders every function chunk of the module, the init chunk being `fn#0`
+    pub fn disassemble(&self) -> String {
+        self.functions
+            .iter()
+            .enumerate()
+            .map(|(index, func)| format!("fn#{index} {}", func.disassemble(&self.constants)))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
 }
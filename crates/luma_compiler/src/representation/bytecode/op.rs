@@ -0,0 +1,61 @@
+//! The byte every instruction is encoded as, operands follow as little endian integers
+
+pub const GET_LOCAL: u8 = 0x01;
+pub const SET_LOCAL: u8 = 0x02;
+pub const POP: u8 = 0x03;
+pub const DUP: u8 = 0x04;
+pub const RETURN: u8 = 0x05;
+pub const LOAD_CONST: u8 = 0x06;
+pub const PUSH_UNIT: u8 = 0x07;
+pub const JUMP: u8 = 0x08;
+pub const JUMP_IF_TRUE: u8 = 0x09;
+pub const JUMP_IF_FALSE: u8 = 0x0A;
+pub const CALL: u8 = 0x0B;
+pub const CALL_NATIVE: u8 = 0x0C;
+pub const CALL_INTRINSIC: u8 = 0x0D;
+pub const CONCAT: u8 = 0x0E;
+pub const PUSH_NONE: u8 = 0x0F;
+pub const ADD: u8 = 0x10;
+pub const SUB: u8 = 0x11;
+pub const MUL: u8 = 0x12;
+pub const DIV: u8 = 0x13;
+pub const MOD: u8 = 0x14;
+pub const BIT_AND: u8 = 0x15;
+pub const BIT_OR: u8 = 0x16;
+pub const BIT_XOR: u8 = 0x17;
+pub const SHIFT_LEFT: u8 = 0x18;
+pub const SHIFT_RIGHT: u8 = 0x19;
+pub const EQUAL: u8 = 0x20;
+pub const GREATER_THAN: u8 = 0x21;
+pub const LESSER_THAN: u8 = 0x22;
+pub const GREATER_THAN_EQUAL: u8 = 0x23;
+pub const LESSER_THAN_EQUAL: u8 = 0x24;
+pub const NOT_EQUAL: u8 = 0x25;
+pub const AND: u8 = 0x28;
+pub const OR: u8 = 0x29;
+pub const NEGATE: u8 = 0x30;
+pub const NOT: u8 = 0x31;
+pub const TRY: u8 = 0x40;
+pub const END_TRY: u8 = 0x41;
+pub const WRAP_ERR: u8 = 0x42;
+pub const IS_ERR: u8 = 0x43;
+pub const UNWRAP_ERR: u8 = 0x44;
+pub const REF_LOCAL: u8 = 0x50;
+pub const LOAD_REF: u8 = 0x51;
+pub const STORE_REF: u8 = 0x52;
+pub const GET_GLOBAL: u8 = 0x60;
+pub const SET_GLOBAL: u8 = 0x61;
+pub const REF_GLOBAL: u8 = 0x62;
+pub const GET_LOCAL_WIDE: u8 = 0x81;
+pub const SET_LOCAL_WIDE: u8 = 0x82;
+pub const LOAD_CONST_WIDE: u8 = 0x86;
+pub const JUMP_WIDE: u8 = 0x88;
+pub const JUMP_IF_TRUE_WIDE: u8 = 0x89;
+pub const JUMP_IF_FALSE_WIDE: u8 = 0x8A;
+pub const CALL_WIDE: u8 = 0x8B;
+pub const CALL_NATIVE_WIDE: u8 = 0x8C;
+pub const TRY_WIDE: u8 = 0xC0;
+pub const REF_LOCAL_WIDE: u8 = 0xD0;
+pub const GET_GLOBAL_WIDE: u8 = 0xE0;
+pub const SET_GLOBAL_WIDE: u8 = 0xE1;
+pub const REF_GLOBAL_WIDE: u8 = 0xE2;
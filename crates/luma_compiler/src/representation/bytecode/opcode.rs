@@ -1,3 +1,6 @@
+use crate::{Intrinsic, bytecode::op};
+
+/// A single instruction, see [`op`] for the byte each one is encoded as
 #[derive(strum::Display, Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
 pub enum Opcode {
@@ -6,80 +9,445 @@ pub enum Opcode {
     // ##########################
 
     /// get local variable
-    GetLocal(u16) = 0x01,
+    GetLocal(u16) = op::GET_LOCAL,
 
-    /// set local variable
-    SetLocal(u16) = 0x02,
+    /// set local variable (pops the value off the stack)
+    SetLocal(u16) = op::SET_LOCAL,
 
     /// remove top of stack
-    Pop = 0x03,
+    Pop = op::POP,
 
     /// duplicate top of stack
-    Dup = 0x04,
+    Dup = op::DUP,
 
-    /// return from function
-    Return = 0x05,
+    /// return from function (pops the return value off the stack)
+    Return = op::RETURN,
 
     // ###########################
     // ###  values / literals  ###
     // ###########################
 
     /// load constant from constant pool
-    LoadConst(u16),
+    LoadConst(u16) = op::LOAD_CONST,
 
     /// pushes unit value onto stack
-    PushUnit,
+    PushUnit = op::PUSH_UNIT,
+
+    /// pushes the absent value of an optional onto stack
+    PushNone = op::PUSH_NONE,
 
     // ###########################
     // ###   control flow      ###
     // ###########################
 
-    /// unconditional jump
-    Jump(u16),
+    /// unconditional jump to an instruction index
+    Jump(u16) = op::JUMP,
+
+    /// conditional jump if top of stack is true (pops the condition)
+    JumpIfTrue(u16) = op::JUMP_IF_TRUE,
+
+    /// conditional jump if top of stack is false (pops the condition)
+    JumpIfFalse(u16) = op::JUMP_IF_FALSE,
+
+    /// call the function at an index of the module's function table (pops the arguments, pushes the result)
+    Call(u16) = op::CALL,
 
-    /// conditional jump if top of stack is true
-    JumpIfTrue(u16),
+    /// call the host function at an index of the module's native imports (pops the arguments, pushes the result)
+    CallNative(u16) = op::CALL_NATIVE,
 
-    /// conditional jump if top of stack is false
-    JumpIfFalse(u16),
+    /// call the intrinsic with an id, see [`Intrinsic`](crate::Intrinsic) (pops the arguments, pushes the result)
+    CallIntrinsic(u16) = op::CALL_INTRINSIC,
+
+    /// joins the displayed forms of a number of values into a new string (pops the values, pushes the string)
+    Concat(u16) = op::CONCAT,
 
     // ##########################
     // ###  binary operators  ###
     // ##########################
     
-    Add,
-    Sub,
-    Mul,
-    Div,
-    Mod,
-    BitAnd,
-    BitOr,
-    BitXor,
-    ShiftLeft,
-    ShiftRight,
+    Add = op::ADD,
+    Sub = op::SUB,
+    Mul = op::MUL,
+    Div = op::DIV,
+    Mod = op::MOD,
+    BitAnd = op::BIT_AND,
+    BitOr = op::BIT_OR,
+    BitXor = op::BIT_XOR,
+    ShiftLeft = op::SHIFT_LEFT,
+    ShiftRight = op::SHIFT_RIGHT,
 
     // ##############################
     // ###  comparison operators  ###
     // ##############################
 
-    Equal,
-    GreaterThan,
-    LesserThan,
-    GreaterThanEqual,
-    LesserThanEqual,
-    NotEqual,
+    Equal = op::EQUAL,
+    GreaterThan = op::GREATER_THAN,
+    LesserThan = op::LESSER_THAN,
+    GreaterThanEqual = op::GREATER_THAN_EQUAL,
+    LesserThanEqual = op::LESSER_THAN_EQUAL,
+    NotEqual = op::NOT_EQUAL,
     
     // ###########################
     // ###  logical operators  ###
     // ###########################
 
-    And,
-    Or,
+    And = op::AND,
+    Or = op::OR,
 
     // ###########################
     // ###  unary operators  ###
     // ###########################
-    Negate,
-    Not,
+    Negate = op::NEGATE,
+    Not = op::NOT,
+
+    // ###########################
+    // ###       errors        ###
+    // ###########################
+
+    /// catches recoverable runtime errors until `EndTry`, the error's message is pushed as an error result
+    /// at the target instruction index instead, with the stack cut back to its height at `Try`
+    Try(u16) = op::TRY,
+
+    /// stops catching the runtime errors of the innermost `Try`
+    EndTry = op::END_TRY,
+
+    /// wraps the top of stack into the error of a result (pops the error, pushes the result)
+    WrapErr = op::WRAP_ERR,
+
+    /// whether the top of stack is the error of a result (pops the value, pushes the condition)
+    IsErr = op::IS_ERR,
+
+    /// unwraps the error of a result (pops the result, pushes its error)
+    UnwrapErr = op::UNWRAP_ERR,
+
+    // ###########################
+    // ###     references      ###
+    // ###########################
+
+    /// push a reference to a local variable of the running function
+    RefLocal(u16) = op::REF_LOCAL,
+
+    /// read the variable a reference refers to (pops the reference, pushes the value)
+    LoadRef = op::LOAD_REF,
+
+    /// write the variable a reference refers to (pops the reference, then the value)
+    StoreRef = op::STORE_REF,
+
+    // ###########################
+    // ###       globals       ###
+    // ###########################
+
+    /// get module-level variable
+    GetGlobal(u16) = op::GET_GLOBAL,
+
+    /// set module-level variable (pops the value off the stack)
+    SetGlobal(u16) = op::SET_GLOBAL,
+
+    /// push a reference to a module-level variable
+    RefGlobal(u16) = op::REF_GLOBAL,
+
+    // ###########################
+    // ###   wide operands     ###
+    // ###########################
+    // only used when the operand doesn't fit in 16 bits, see the constructors on `Opcode`.
+    // the wide form of an instruction is always encoded as its narrow byte with the high bit set
+
+    /// get local variable
+    GetLocalWide(u32) = op::GET_LOCAL_WIDE,
+
+    /// set local variable (pops the value off the stack)
+    SetLocalWide(u32) = op::SET_LOCAL_WIDE,
+
+    /// load constant from constant pool
+    LoadConstWide(u32) = op::LOAD_CONST_WIDE,
+
+    /// unconditional jump to an instruction index
+    JumpWide(u32) = op::JUMP_WIDE,
+
+    /// conditional jump if top of stack is true (pops the condition)
+    JumpIfTrueWide(u32) = op::JUMP_IF_TRUE_WIDE,
+
+    /// conditional jump if top of stack is false (pops the condition)
+    JumpIfFalseWide(u32) = op::JUMP_IF_FALSE_WIDE,
+
+    /// call the function at an index of the module's function table (pops the arguments, pushes the result)
+    CallWide(u32) = op::CALL_WIDE,
+
+    /// call the host function at an index of the module's native imports (pops the arguments, pushes the result)
+    CallNativeWide(u32) = op::CALL_NATIVE_WIDE,
+
+    /// catches recoverable runtime errors until `EndTry`
+    TryWide(u32) = op::TRY_WIDE,
+
+    /// push a reference to a local variable of the running function
+    RefLocalWide(u32) = op::REF_LOCAL_WIDE,
+
+    /// get module-level variable
+    GetGlobalWide(u32) = op::GET_GLOBAL_WIDE,
+
+    /// set module-level variable (pops the value off the stack)
+    SetGlobalWide(u32) = op::SET_GLOBAL_WIDE,
+
+    /// push a reference to a module-level variable
+    RefGlobalWide(u32) = op::REF_GLOBAL_WIDE,
+}
+
+/// Picks the narrow variant if the operand fits in 16 bits, otherwise the wide one
+macro_rules! narrow_or_wide {
+    ($operand:expr, $narrow:ident, $wide:ident) => {
+        match u16::try_from($operand) {
+            Ok(operand) => Opcode::$narrow(operand),
+            Err(_) => Opcode::$wide($operand),
+        }
+    };
+}
+
+impl Opcode {
+    #[must_use]
+    pub fn get_local(slot: u32) -> Self {
+        narrow_or_wide!(slot, GetLocal, GetLocalWide)
+    }
+
+    #[must_use]
+    pub fn set_local(slot: u32) -> Self {
+        narrow_or_wide!(slot, SetLocal, SetLocalWide)
+    }
+
+    #[must_use]
+    pub fn load_const(slot: u32) -> Self {
+        narrow_or_wide!(slot, LoadConst, LoadConstWide)
+    }
+
+    #[must_use]
+    pub fn jump(target: u32) -> Self {
+        narrow_or_wide!(target, Jump, JumpWide)
+    }
+
+    #[must_use]
+    pub fn jump_if_true(target: u32) -> Self {
+        narrow_or_wide!(target, JumpIfTrue, JumpIfTrueWide)
+    }
+
+    #[must_use]
+    pub fn jump_if_false(target: u32) -> Self {
+        narrow_or_wide!(target, JumpIfFalse, JumpIfFalseWide)
+    }
+
+    #[must_use]
+    pub fn try_handler(target: u32) -> Self {
+        narrow_or_wide!(target, Try, TryWide)
+    }
+
+    #[must_use]
+    pub fn ref_local(slot: u32) -> Self {
+        narrow_or_wide!(slot, RefLocal, RefLocalWide)
+    }
+
+    #[must_use]
+    pub fn get_global(slot: u32) -> Self {
+        narrow_or_wide!(slot, GetGlobal, GetGlobalWide)
+    }
+
+    #[must_use]
+    pub fn set_global(slot: u32) -> Self {
+        narrow_or_wide!(slot, SetGlobal, SetGlobalWide)
+    }
+
+    #[must_use]
+    pub fn ref_global(slot: u32) -> Self {
+        narrow_or_wide!(slot, RefGlobal, RefGlobalWide)
+    }
+
+    #[must_use]
+    pub fn call(function: u32) -> Self {
+        narrow_or_wide!(function, Call, CallWide)
+    }
+
+    #[must_use]
+    pub fn call_native(native: u32) -> Self {
+        narrow_or_wide!(native, CallNative, CallNativeWide)
+    }
+
+    /// Returns the index of the function called by `Call`
+    #[must_use]
+    pub const fn called_function(&self) -> Option<u32> {
+        match self {
+            Opcode::Call(index) => Some(*index as u32),
+            Opcode::CallWide(index) => Some(*index),
+            _ => None,
+        }
+    }
+
+    /// Returns the index of the native import called by `CallNative`
+    #[must_use]
+    pub const fn called_native(&self) -> Option<u32> {
+        match self {
+            Opcode::CallNative(index) => Some(*index as u32),
+            Opcode::CallNativeWide(index) => Some(*index),
+            _ => None,
+        }
+    }
+
+    /// Returns the intrinsic called by `CallIntrinsic`
+    #[must_use]
+    pub fn called_intrinsic(&self) -> Option<Intrinsic> {
+        match self {
+            Opcode::CallIntrinsic(id) => Intrinsic::from_id(*id),
+            _ => None,
+        }
+    }
+
+    /// Returns the amount of values joined by `Concat`
+    #[must_use]
+    pub const fn concatenated(&self) -> Option<u32> {
+        match self {
+            Opcode::Concat(count) => Some(*count as u32),
+            _ => None,
+        }
+    }
+
+    /// Returns the slot of the local read by `GetLocal`
+    #[must_use]
+    pub const fn local_read(&self) -> Option<u32> {
+        match self {
+            Opcode::GetLocal(slot) => Some(*slot as u32),
+            Opcode::GetLocalWide(slot) => Some(*slot),
+            _ => None,
+        }
+    }
+
+    /// Returns the slot of the local written by `SetLocal`
+    #[must_use]
+    pub const fn local_write(&self) -> Option<u32> {
+        match self {
+            Opcode::SetLocal(slot) => Some(*slot as u32),
+            Opcode::SetLocalWide(slot) => Some(*slot),
+            _ => None,
+        }
+    }
+
+    /// Returns the slot of the local referenced by `RefLocal`, which may be read and written through the reference
+    #[must_use]
+    pub const fn local_ref(&self) -> Option<u32> {
+        match self {
+            Opcode::RefLocal(slot) => Some(*slot as u32),
+            Opcode::RefLocalWide(slot) => Some(*slot),
+            _ => None,
+        }
+    }
+
+    /// Returns the slot of any local access
+    #[must_use]
+    pub const fn local_slot(&self) -> Option<u32> {
+        match (self.local_read(), self.local_write(), self.local_ref()) {
+            (Some(slot), _, _) | (_, Some(slot), _) | (_, _, Some(slot)) => Some(slot),
+            _ => None,
+        }
+    }
+
+    /// Replaces the slot of a local access, does nothing for other instructions
+    pub fn set_local_slot(&mut self, new_slot: u32) {
+        if self.local_read().is_some() {
+            *self = Opcode::get_local(new_slot);
+        } else if self.local_write().is_some() {
+            *self = Opcode::set_local(new_slot);
+        } else if self.local_ref().is_some() {
+            *self = Opcode::ref_local(new_slot);
+        }
+    }
+
+    /// Returns the slot of the global accessed by `GetGlobal`, `SetGlobal` or `RefGlobal`
+    #[must_use]
+    pub const fn global_slot(&self) -> Option<u32> {
+        match self {
+            Opcode::GetGlobal(slot) | Opcode::SetGlobal(slot) | Opcode::RefGlobal(slot) => Some(*slot as u32),
+            Opcode::GetGlobalWide(slot) | Opcode::SetGlobalWide(slot) | Opcode::RefGlobalWide(slot) => Some(*slot),
+            _ => None,
+        }
+    }
+
+    /// Returns the constant slot loaded by `LoadConst`
+    #[must_use]
+    pub const fn const_slot(&self) -> Option<u32> {
+        match self {
+            Opcode::LoadConst(slot) => Some(*slot as u32),
+            Opcode::LoadConstWide(slot) => Some(*slot),
+            _ => None,
+        }
+    }
+
+    /// Returns the target instruction index if this is a jump instruction.
+    ///
+    /// `Try` counts as a conditional jump, as execution continues at its target once an error is caught
+    #[must_use]
+    pub const fn jump_target(&self) -> Option<u32> {
+        match self {
+            Opcode::Jump(target)
+            | Opcode::JumpIfTrue(target)
+            | Opcode::JumpIfFalse(target)
+            | Opcode::Try(target) => Some(*target as u32),
+            Opcode::JumpWide(target)
+            | Opcode::JumpIfTrueWide(target)
+            | Opcode::JumpIfFalseWide(target)
+            | Opcode::TryWide(target) => Some(*target),
+            _ => None,
+        }
+    }
+
+    /// Replaces the target of a jump instruction, does nothing for other instructions.
+    ///
+    /// The jump switches between its narrow and wide form depending on whether the new target fits in 16 bits.
+    pub fn set_jump_target(&mut self, new_target: u32) {
+        *self = match self {
+            Opcode::Jump(_) | Opcode::JumpWide(_) => Opcode::jump(new_target),
+            Opcode::JumpIfTrue(_) | Opcode::JumpIfTrueWide(_) => Opcode::jump_if_true(new_target),
+            Opcode::JumpIfFalse(_) | Opcode::JumpIfFalseWide(_) => {
+                Opcode::jump_if_false(new_target)
+            }
+            Opcode::Try(_) | Opcode::TryWide(_) => Opcode::try_handler(new_target),
+            _ => return,
+        };
+    }
+
+    #[must_use]
+    pub const fn is_jump(&self) -> bool {
+        self.jump_target().is_some()
+    }
+
+    #[must_use]
+    pub const fn is_unconditional_jump(&self) -> bool {
+        matches!(self, Opcode::Jump(_) | Opcode::JumpWide(_))
+    }
+
+    #[must_use]
+    pub const fn is_conditional_jump(&self) -> bool {
+        self.is_jump() && !self.is_unconditional_jump()
+    }
+
+    /// Whether the operand is stored in its wide form
+    #[must_use]
+    pub const fn is_wide(&self) -> bool {
+        matches!(
+            self,
+            Opcode::GetLocalWide(_)
+                | Opcode::SetLocalWide(_)
+                | Opcode::LoadConstWide(_)
+                | Opcode::JumpWide(_)
+                | Opcode::JumpIfTrueWide(_)
+                | Opcode::JumpIfFalseWide(_)
+                | Opcode::CallWide(_)
+                | Opcode::CallNativeWide(_)
+                | Opcode::TryWide(_)
+                | Opcode::RefLocalWide(_)
+                | Opcode::GetGlobalWide(_)
+                | Opcode::SetGlobalWide(_)
+                | Opcode::RefGlobalWide(_)
+        )
+    }
 
-}
\ No newline at end of file
+    /// Whether execution never falls through to the next instruction
+    #[must_use]
+    pub const fn is_terminator(&self) -> bool {
+        self.is_unconditional_jump() || matches!(self, Opcode::Return)
+    }
+}
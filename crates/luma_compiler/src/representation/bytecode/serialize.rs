@@ -0,0 +1,549 @@
+//! Binary format of a compiled module, all integers are little endian.
+//!
+//! ```text
+//! module    := MAGIC version:u16 source_id:u32 count:u32 constant* count:u32 function* count:u32 native:str*
+//!              count:u32 export* count:u32 export* count:u32 struct* count:u32 global*
+//! constant  := tag:u8 payload
+//! function  := name:str arity:u32 max_locals:u32 len:u32 code:u8* count:u32 line* count:u32 local*
+//! line      := offset:u32 span
+//! local     := name:str slot:u32 start:u32 end:u32
+//! export    := name:str index:u32
+//! struct    := name:str count:u32 (name:str type)*
+//! global    := name:str type
+//! type      := tag:u8 payload
+//! span      := source_id:u32 start:u32 end:u32
+//! str       := len:u32 utf8:u8*
+//! ```
+
+use std::collections::HashMap;
+
+use luma_core::{CodeSourceId, Span};
+
+use crate::{
+    Type, TypeKind,
+    bytecode::{BytecodeValue, FieldLayout, GlobalLayout, ModuleBytecode, StructLayout},
+    stages::codegen::{
+        chunk::{CodeChunk, DebugInfo, FunctionChunk, LineEntry, LineTable, LocalInfo},
+        stores::ExportTable,
+    },
+};
+
+/// Identifies serialized modules
+pub const MAGIC: &[u8; 4] = b"LUMA";
+
+/// Bumped whenever the format changes in an incompatible way
+pub const FORMAT_VERSION: u16 = 6;
+
+/// Deepest nesting of constants and types a module may contain, deeper input is rejected instead of overflowing the stack
+const MAX_NESTING: usize = 128;
+
+impl ModuleBytecode {
+    /// Serializes the module including the debug info of its functions
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+
+        out.extend_from_slice(MAGIC);
+        out.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        write_u32(&mut out, self.source_id.value());
+
+        write_len(&mut out, self.constants.len());
+        for constant in &self.constants {
+            write_constant(&mut out, constant);
+        }
+
+        write_len(&mut out, self.functions.len());
+        for func in &self.functions {
+            write_function(&mut out, func);
+        }
+
+        write_len(&mut out, self.natives.len());
+        for native in &self.natives {
+            write_str(&mut out, native);
+        }
+
+        write_exports(&mut out, &self.exports.functions);
+        write_exports(&mut out, &self.exports.variables);
+
+        write_len(&mut out, self.structs.len());
+        for layout in &self.structs {
+            write_str(&mut out, &layout.name);
+            write_len(&mut out, layout.fields.len());
+
+            for field in &layout.fields {
+                write_str(&mut out, &field.name);
+                write_type(&mut out, &field.ty);
+            }
+        }
+
+        write_len(&mut out, self.globals.len());
+        for global in &self.globals {
+            write_str(&mut out, &global.name);
+            write_type(&mut out, &global.ty);
+        }
+
+        out
+    }
+
+    /// Deserializes a module written by [`Self::to_bytes`], `None` if the bytes aren't a valid module
+    pub fn from_bytes(bytes: &[u8]) -> Option<ModuleBytecode> {
+        let mut reader = Reader { bytes, depth: 0 };
+
+        if reader.take(MAGIC.len())? != MAGIC || reader.u16()? != FORMAT_VERSION {
+            return None;
+        }
+
+        let source_id = CodeSourceId::new(reader.u32()?);
+
+        let constants = (0..reader.u32()?)
+            .map(|_| reader.constant())
+            .collect::<Option<Vec<_>>>()?;
+
+        let functions = (0..reader.u32()?)
+            .map(|_| reader.function())
+            .collect::<Option<Vec<_>>>()?;
+
+        let natives = (0..reader.u32()?)
+            .map(|_| reader.string())
+            .collect::<Option<Vec<_>>>()?;
+
+        let exports = ExportTable {
+            functions: reader.exports()?,
+            variables: reader.exports()?,
+        };
+
+        let structs = (0..reader.u32()?)
+            .map(|_| {
+                let name = reader.string()?;
+                let fields = (0..reader.u32()?)
+                    .map(|_| {
+                        Some(FieldLayout {
+                            name: reader.string()?,
+                            ty: reader.type_kind()?,
+                        })
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+
+                Some(StructLayout { name, fields })
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        let globals = (0..reader.u32()?)
+            .map(|_| {
+                Some(GlobalLayout {
+                    name: reader.string()?,
+                    ty: reader.type_kind()?,
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        // trailing bytes mean the input is something else
+        if !reader.bytes.is_empty() {
+            return None;
+        }
+
+        Some(ModuleBytecode {
+            source_id,
+            constants,
+            functions,
+            natives,
+            exports,
+            structs,
+            globals,
+        })
+    }
+}
+
+mod tag {
+    pub const UINT8: u8 = 0;
+    pub const UINT16: u8 = 1;
+    pub const UINT32: u8 = 2;
+    pub const UINT64: u8 = 3;
+    pub const INT8: u8 = 4;
+    pub const INT16: u8 = 5;
+    pub const INT32: u8 = 6;
+    pub const INT64: u8 = 7;
+    pub const FLOAT32: u8 = 8;
+    pub const FLOAT64: u8 = 9;
+    pub const BOOL: u8 = 10;
+    pub const CHAR: u8 = 11;
+    pub const STRING: u8 = 12;
+    pub const UNIT: u8 = 13;
+    pub const TUPLE: u8 = 14;
+    pub const ARRAY: u8 = 15;
+    pub const STRUCT: u8 = 16;
+    pub const NONE: u8 = 17;
+    pub const ERR: u8 = 18;
+}
+
+/// Tags of types, the primitive ones share the tag of their constants
+mod type_tag {
+    pub const TUPLE: u8 = 14;
+    pub const ARRAY: u8 = 15;
+    pub const NAMED: u8 = 16;
+    pub const PTR: u8 = 17;
+    pub const ERROR: u8 = 18;
+    pub const OPTIONAL: u8 = 19;
+    pub const RESULT: u8 = 20;
+}
+
+fn write_constant(out: &mut Vec<u8>, constant: &BytecodeValue) {
+    match constant {
+        BytecodeValue::UInt8(v) => out.extend([tag::UINT8, *v]),
+        BytecodeValue::UInt16(v) => {
+            out.push(tag::UINT16);
+            out.extend_from_slice(&v.to_le_bytes());
+        }
+        BytecodeValue::UInt32(v) => {
+            out.push(tag::UINT32);
+            out.extend_from_slice(&v.to_le_bytes());
+        }
+        BytecodeValue::UInt64(v) => {
+            out.push(tag::UINT64);
+            out.extend_from_slice(&v.to_le_bytes());
+        }
+        BytecodeValue::Int8(v) => out.extend([tag::INT8, *v as u8]),
+        BytecodeValue::Int16(v) => {
+            out.push(tag::INT16);
+            out.extend_from_slice(&v.to_le_bytes());
+        }
+        BytecodeValue::Int32(v) => {
+            out.push(tag::INT32);
+            out.extend_from_slice(&v.to_le_bytes());
+        }
+        BytecodeValue::Int64(v) => {
+            out.push(tag::INT64);
+            out.extend_from_slice(&v.to_le_bytes());
+        }
+        BytecodeValue::Float32(v) => {
+            out.push(tag::FLOAT32);
+            out.extend_from_slice(&v.to_le_bytes());
+        }
+        BytecodeValue::Float64(v) => {
+            out.push(tag::FLOAT64);
+            out.extend_from_slice(&v.to_le_bytes());
+        }
+        BytecodeValue::Bool(v) => out.extend([tag::BOOL, *v as u8]),
+        BytecodeValue::Char(v) => {
+            out.push(tag::CHAR);
+            write_u32(out, *v as u32);
+        }
+        BytecodeValue::String(v) => {
+            out.push(tag::STRING);
+            write_str(out, v);
+        }
+        BytecodeValue::Unit => out.push(tag::UNIT),
+        BytecodeValue::None => out.push(tag::NONE),
+        BytecodeValue::Err(error) => {
+            out.push(tag::ERR);
+            write_constant(out, error);
+        }
+        BytecodeValue::Tuple(elements) | BytecodeValue::Array(elements) => {
+            out.push(if matches!(constant, BytecodeValue::Tuple(_)) { tag::TUPLE } else { tag::ARRAY });
+            write_len(out, elements.len());
+
+            for element in elements {
+                write_constant(out, element);
+            }
+        }
+        BytecodeValue::Struct { name, fields } => {
+            out.push(tag::STRUCT);
+            write_str(out, name);
+            write_len(out, fields.len());
+
+            for (name, value) in fields {
+                write_str(out, name);
+                write_constant(out, value);
+            }
+        }
+    }
+}
+
+fn write_type(out: &mut Vec<u8>, ty: &TypeKind) {
+    match ty {
+        TypeKind::UInt8 => out.push(tag::UINT8),
+        TypeKind::UInt16 => out.push(tag::UINT16),
+        TypeKind::UInt32 => out.push(tag::UINT32),
+        TypeKind::UInt64 => out.push(tag::UINT64),
+        TypeKind::Int8 => out.push(tag::INT8),
+        TypeKind::Int16 => out.push(tag::INT16),
+        TypeKind::Int32 => out.push(tag::INT32),
+        TypeKind::Int64 => out.push(tag::INT64),
+        TypeKind::Float32 => out.push(tag::FLOAT32),
+        TypeKind::Float64 => out.push(tag::FLOAT64),
+        TypeKind::Bool => out.push(tag::BOOL),
+        TypeKind::Char => out.push(tag::CHAR),
+        TypeKind::String => out.push(tag::STRING),
+        TypeKind::Unit => out.push(tag::UNIT),
+        TypeKind::Error => out.push(type_tag::ERROR),
+        TypeKind::Tuple(elements) => {
+            out.push(type_tag::TUPLE);
+            write_len(out, elements.len());
+
+            for element in elements {
+                write_type(out, element);
+            }
+        }
+        TypeKind::Array(element) => {
+            out.push(type_tag::ARRAY);
+            write_type(out, element);
+        }
+        TypeKind::Ptr(inner) => {
+            out.push(type_tag::PTR);
+            write_type(out, inner);
+        }
+        TypeKind::Optional(inner) => {
+            out.push(type_tag::OPTIONAL);
+            write_type(out, inner);
+        }
+        TypeKind::Result(value, error) => {
+            out.push(type_tag::RESULT);
+            write_type(out, value);
+            write_type(out, error);
+        }
+        TypeKind::Named { name, .. } => {
+            out.push(type_tag::NAMED);
+            write_str(out, name);
+        }
+    }
+}
+
+fn write_function(out: &mut Vec<u8>, func: &FunctionChunk) {
+    write_str(out, &func.name);
+    write_len(out, func.arity);
+    write_len(out, func.code.max_locals);
+
+    write_len(out, func.code.bytes().len());
+    out.extend_from_slice(func.code.bytes());
+
+    let debug = &func.code.debug;
+
+    write_len(out, debug.lines.entries().len());
+    for entry in debug.lines.entries() {
+        write_u32(out, entry.offset);
+        write_span(out, entry.span);
+    }
+
+    write_len(out, debug.locals.len());
+    for local in &debug.locals {
+        write_str(out, &local.name);
+        write_u32(out, local.slot);
+        write_u32(out, local.start);
+        write_u32(out, local.end);
+    }
+}
+
+fn write_exports(out: &mut Vec<u8>, exports: &HashMap<String, u32>) {
+    // sorted so that the same module always serializes to the same bytes
+    let mut exports = exports.iter().collect::<Vec<_>>();
+    exports.sort();
+
+    write_len(out, exports.len());
+    for (name, index) in exports {
+        write_str(out, name);
+        write_u32(out, *index);
+    }
+}
+
+fn write_span(out: &mut Vec<u8>, span: Span) {
+    write_u32(out, span.source_id.value());
+    write_u32(out, span.start);
+    write_u32(out, span.end);
+}
+
+fn write_str(out: &mut Vec<u8>, value: &str) {
+    write_len(out, value.len());
+    out.extend_from_slice(value.as_bytes());
+}
+
+#[inline]
+fn write_len(out: &mut Vec<u8>, len: usize) {
+    write_u32(out, u32::try_from(len).expect("lengths are limited to u32 by the compiler"));
+}
+
+#[inline]
+fn write_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+/// Reads values from the front of the remaining bytes
+struct Reader<'bytes> {
+    bytes: &'bytes [u8],
+
+    /// constants or types being read around the current one
+    depth: usize,
+}
+
+impl<'bytes> Reader<'bytes> {
+    fn take(&mut self, len: usize) -> Option<&'bytes [u8]> {
+        let (taken, rest) = self.bytes.split_at_checked(len)?;
+        self.bytes = rest;
+
+        Some(taken)
+    }
+
+    fn array<const N: usize>(&mut self) -> Option<[u8; N]> {
+        self.take(N)?.try_into().ok()
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        Some(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        self.array().map(u16::from_le_bytes)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.array().map(u32::from_le_bytes)
+    }
+
+    fn len(&mut self) -> Option<usize> {
+        self.u32().map(|len| len as usize)
+    }
+
+    fn string(&mut self) -> Option<String> {
+        let len = self.len()?;
+        String::from_utf8(self.take(len)?.to_vec()).ok()
+    }
+
+    fn span(&mut self) -> Option<Span> {
+        Some(Span::new(CodeSourceId::new(self.u32()?), self.u32()?, self.u32()?))
+    }
+
+    fn exports(&mut self) -> Option<HashMap<String, u32>> {
+        (0..self.u32()?)
+            .map(|_| Some((self.string()?, self.u32()?)))
+            .collect()
+    }
+
+    /// Reads a value nested in the one being read, `None` past [`MAX_NESTING`]
+    fn nested<T>(&mut self, read: impl FnOnce(&mut Self) -> Option<T>) -> Option<T> {
+        if self.depth == MAX_NESTING {
+            return None;
+        }
+
+        self.depth += 1;
+        let value = read(self);
+        self.depth -= 1;
+
+        value
+    }
+
+    fn constant(&mut self) -> Option<BytecodeValue> {
+        self.nested(Self::read_constant)
+    }
+
+    fn read_constant(&mut self) -> Option<BytecodeValue> {
+        let value = match self.u8()? {
+            tag::UINT8 => BytecodeValue::UInt8(self.u8()?),
+            tag::UINT16 => BytecodeValue::UInt16(u16::from_le_bytes(self.array()?)),
+            tag::UINT32 => BytecodeValue::UInt32(u32::from_le_bytes(self.array()?)),
+            tag::UINT64 => BytecodeValue::UInt64(u64::from_le_bytes(self.array()?)),
+            tag::INT8 => BytecodeValue::Int8(i8::from_le_bytes(self.array()?)),
+            tag::INT16 => BytecodeValue::Int16(i16::from_le_bytes(self.array()?)),
+            tag::INT32 => BytecodeValue::Int32(i32::from_le_bytes(self.array()?)),
+            tag::INT64 => BytecodeValue::Int64(i64::from_le_bytes(self.array()?)),
+            tag::FLOAT32 => BytecodeValue::Float32(f32::from_le_bytes(self.array()?)),
+            tag::FLOAT64 => BytecodeValue::Float64(f64::from_le_bytes(self.array()?)),
+            tag::BOOL => BytecodeValue::Bool(self.u8()? != 0),
+            tag::CHAR => BytecodeValue::Char(char::from_u32(self.u32()?)?),
+            tag::STRING => BytecodeValue::String(self.string()?.into()),
+            tag::UNIT => BytecodeValue::Unit,
+            tag::NONE => BytecodeValue::None,
+            tag::ERR => BytecodeValue::Err(Box::new(self.constant()?)),
+            tag::TUPLE => BytecodeValue::Tuple(self.constants()?),
+            tag::ARRAY => BytecodeValue::Array(self.constants()?),
+            tag::STRUCT => BytecodeValue::Struct {
+                name: self.string()?,
+                fields: (0..self.u32()?)
+                    .map(|_| Some((self.string()?, self.constant()?)))
+                    .collect::<Option<Vec<_>>>()?,
+            },
+            _ => return None,
+        };
+
+        Some(value)
+    }
+
+    fn constants(&mut self) -> Option<Vec<BytecodeValue>> {
+        (0..self.u32()?).map(|_| self.constant()).collect()
+    }
+
+    fn type_kind(&mut self) -> Option<TypeKind> {
+        self.nested(Self::read_type_kind)
+    }
+
+    fn read_type_kind(&mut self) -> Option<TypeKind> {
+        let ty = match self.u8()? {
+            tag::UINT8 => TypeKind::UInt8,
+            tag::UINT16 => TypeKind::UInt16,
+            tag::UINT32 => TypeKind::UInt32,
+            tag::UINT64 => TypeKind::UInt64,
+            tag::INT8 => TypeKind::Int8,
+            tag::INT16 => TypeKind::Int16,
+            tag::INT32 => TypeKind::Int32,
+            tag::INT64 => TypeKind::Int64,
+            tag::FLOAT32 => TypeKind::Float32,
+            tag::FLOAT64 => TypeKind::Float64,
+            tag::BOOL => TypeKind::Bool,
+            tag::CHAR => TypeKind::Char,
+            tag::STRING => TypeKind::String,
+            tag::UNIT => TypeKind::Unit,
+            type_tag::ERROR => TypeKind::Error,
+            type_tag::TUPLE => TypeKind::Tuple(
+                (0..self.u32()?)
+                    .map(|_| self.type_kind().map(Type::unspanned))
+                    .collect::<Option<Vec<_>>>()?,
+            ),
+            type_tag::ARRAY => TypeKind::Array(Box::new(Type::unspanned(self.type_kind()?))),
+            type_tag::PTR => TypeKind::Ptr(Box::new(Type::unspanned(self.type_kind()?))),
+            type_tag::OPTIONAL => TypeKind::Optional(Box::new(Type::unspanned(self.type_kind()?))),
+            type_tag::RESULT => TypeKind::Result(
+                Box::new(Type::unspanned(self.type_kind()?)),
+                Box::new(Type::unspanned(self.type_kind()?)),
+            ),
+            type_tag::NAMED => TypeKind::Named {
+                name: self.string()?,
+                def_id: None,
+            },
+            _ => return None,
+        };
+
+        Some(ty)
+    }
+
+    fn function(&mut self) -> Option<FunctionChunk> {
+        let name = self.string()?;
+        let arity = self.len()?;
+        let max_locals = self.len()?;
+
+        let code_len = self.len()?;
+        let code = self.take(code_len)?.to_vec();
+
+        let lines = (0..self.u32()?)
+            .map(|_| {
+                Some(LineEntry {
+                    offset: self.u32()?,
+                    span: self.span()?,
+                })
+            })
+            .collect::<Option<LineTable>>()?;
+
+        let locals = (0..self.u32()?)
+            .map(|_| {
+                Some(LocalInfo {
+                    name: self.string()?,
+                    slot: self.u32()?,
+                    start: self.u32()?,
+                    end: self.u32()?,
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(FunctionChunk {
+            name,
+            code: CodeChunk::from_encoded(code, max_locals, DebugInfo { lines, locals }),
+            arity,
+        })
+    }
+}
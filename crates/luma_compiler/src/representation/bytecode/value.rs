@@ -1,4 +1,6 @@
-use std::hash::Hash;
+use std::{hash::Hash, rc::Rc};
+
+use crate::{Type, TypeKind};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum BytecodeValue {
@@ -14,8 +16,82 @@ pub enum BytecodeValue {
     Float64(f64),
     Bool(bool),
     Char(char),
-    String(String),
+    /// shared with the VM's interned strings rather than copied
+    String(Rc<str>),
     Unit,
+    /// the absent value of an optional, present values are stored as themselves
+    None,
+
+    // composite values only cross the boundary to the host, they are never constants
+    Tuple(Vec<BytecodeValue>),
+    Array(Vec<BytecodeValue>),
+    Struct {
+        name: String,
+        fields: Vec<(String, BytecodeValue)>,
+    },
+    /// the error of a result, values are stored as themselves like present optionals
+    Err(Box<BytecodeValue>),
+}
+
+impl BytecodeValue {
+    /// The Luma type of the value, arrays are typed by their first element, `none` as `()?` and errors as `()!E`
+    #[must_use]
+    pub fn type_kind(&self) -> TypeKind {
+        match self {
+            BytecodeValue::UInt8(_) => TypeKind::UInt8,
+            BytecodeValue::UInt16(_) => TypeKind::UInt16,
+            BytecodeValue::UInt32(_) => TypeKind::UInt32,
+            BytecodeValue::UInt64(_) => TypeKind::UInt64,
+            BytecodeValue::Int8(_) => TypeKind::Int8,
+            BytecodeValue::Int16(_) => TypeKind::Int16,
+            BytecodeValue::Int32(_) => TypeKind::Int32,
+            BytecodeValue::Int64(_) => TypeKind::Int64,
+            BytecodeValue::Float32(_) => TypeKind::Float32,
+            BytecodeValue::Float64(_) => TypeKind::Float64,
+            BytecodeValue::Bool(_) => TypeKind::Bool,
+            BytecodeValue::Char(_) => TypeKind::Char,
+            BytecodeValue::String(_) => TypeKind::String,
+            BytecodeValue::Unit => TypeKind::Unit,
+            BytecodeValue::None => TypeKind::Optional(Box::new(Type::unspanned(TypeKind::Unit))),
+            BytecodeValue::Tuple(elements) => TypeKind::Tuple(
+                elements
+                    .iter()
+                    .map(|element| Type::unspanned(element.type_kind()))
+                    .collect(),
+            ),
+            BytecodeValue::Array(elements) => TypeKind::Array(Box::new(Type::unspanned(
+                elements.first().map_or(TypeKind::Unit, BytecodeValue::type_kind),
+            ))),
+            BytecodeValue::Struct { name, .. } => TypeKind::Named {
+                name: name.clone(),
+                def_id: None,
+            },
+            BytecodeValue::Err(error) => TypeKind::Result(
+                Box::new(Type::unspanned(TypeKind::Unit)),
+                Box::new(Type::unspanned(error.type_kind())),
+            ),
+        }
+    }
+
+    /// Whether the value is of type `ty`, every element of an array has to match its element type
+    #[must_use]
+    pub fn matches_type(&self, ty: &TypeKind) -> bool {
+        match (self, ty) {
+            (BytecodeValue::Tuple(elements), TypeKind::Tuple(types)) => {
+                elements.len() == types.len()
+                    && elements.iter().zip(types).all(|(element, ty)| element.matches_type(ty))
+            }
+            (BytecodeValue::Array(elements), TypeKind::Array(element_type)) => {
+                elements.iter().all(|element| element.matches_type(element_type))
+            }
+            (BytecodeValue::Struct { name, .. }, TypeKind::Named { name: type_name, .. }) => name == type_name,
+            (BytecodeValue::None, TypeKind::Optional(_)) => true,
+            (value, TypeKind::Optional(inner)) => value.matches_type(inner),
+            (BytecodeValue::Err(error), TypeKind::Result(_, error_type)) => error.matches_type(error_type),
+            (value, TypeKind::Result(value_type, _)) => value.matches_type(value_type),
+            _ => self.type_kind() == *ty,
+        }
+    }
 }
 
 impl Eq for BytecodeValue {}
@@ -41,6 +117,17 @@ impl Hash for BytecodeValue {
             BytecodeValue::String(v) => v.hash(state),
 
             BytecodeValue::Unit => state.write_u8(0),
+            BytecodeValue::None => state.write_u8(1),
+
+            BytecodeValue::Tuple(elements) | BytecodeValue::Array(elements) => elements.hash(state),
+            BytecodeValue::Struct { name, fields } => {
+                name.hash(state);
+                fields.hash(state);
+            }
+            BytecodeValue::Err(error) => {
+                state.write_u8(2);
+                error.hash(state);
+            }
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,84 @@
+use crate::{Type, TypeKind};
+
+/// A function built into the VM, called with `CallIntrinsic` and the intrinsic's id.
+///
+/// Intrinsics are declared in the root scope of every module, before the natives and the module's own functions
+#[derive(strum::Display, strum::EnumIter, strum::IntoStaticStr, Debug, Clone, Copy, PartialEq, Eq)]
+#[strum(serialize_all = "snake_case")]
+#[repr(u16)]
+pub enum Intrinsic {
+    /// `len(text: str): i32`, the amount of chars
+    Len,
+    /// `slice(text: str, start: i32, end: i32): str`, the chars from `start` up to `end`
+    Slice,
+    /// `char_at(text: str, index: i32): char`
+    CharAt,
+    /// `chars(text: str): [char]`
+    Chars,
+    /// `to_string(value): str`, accepts any displayable value
+    ToString,
+    /// `parse_int(text: str): i64`
+    ParseInt,
+    /// `parse_float(text: str): f64`
+    ParseFloat,
+}
+
+impl Intrinsic {
+    pub fn all() -> impl Iterator<Item = Intrinsic> {
+        <Self as strum::IntoEnumIterator>::iter()
+    }
+
+    #[must_use]
+    pub fn from_id(id: u16) -> Option<Self> {
+        Self::all().find(|intrinsic| intrinsic.id() == id)
+    }
+
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::all().find(|intrinsic| intrinsic.name() == name)
+    }
+
+    #[must_use]
+    pub const fn id(self) -> u16 {
+        self as u16
+    }
+
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        self.into()
+    }
+
+    #[must_use]
+    pub const fn arity(self) -> usize {
+        match self {
+            Intrinsic::Slice => 3,
+            Intrinsic::CharAt => 2,
+            _ => 1,
+        }
+    }
+
+    /// Parameter types, `None` accepts any displayable value
+    #[must_use]
+    pub fn params(self) -> Vec<Option<TypeKind>> {
+        match self {
+            Intrinsic::Len | Intrinsic::Chars | Intrinsic::ParseInt | Intrinsic::ParseFloat => {
+                vec![Some(TypeKind::String)]
+            }
+            Intrinsic::Slice => vec![Some(TypeKind::String), Some(TypeKind::Int32), Some(TypeKind::Int32)],
+            Intrinsic::CharAt => vec![Some(TypeKind::String), Some(TypeKind::Int32)],
+            Intrinsic::ToString => vec![None],
+        }
+    }
+
+    #[must_use]
+    pub fn return_type(self) -> TypeKind {
+        match self {
+            Intrinsic::Len => TypeKind::Int32,
+            Intrinsic::Slice | Intrinsic::ToString => TypeKind::String,
+            Intrinsic::CharAt => TypeKind::Char,
+            Intrinsic::Chars => TypeKind::Array(Box::new(Type::unspanned(TypeKind::Char))),
+            Intrinsic::ParseInt => TypeKind::Int64,
+            Intrinsic::ParseFloat => TypeKind::Float64,
+        }
+    }
+}
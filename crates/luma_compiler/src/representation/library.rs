@@ -0,0 +1,20 @@
+use luma_core::CodeSource;
+
+/// Luma source that modules bring in with `import`, e.g. `import std.math;`.
+///
+/// Libraries are only compiled into the modules importing them, their own imports are resolved the same way
+#[derive(Debug, Clone)]
+pub struct Library {
+    /// the dotted module name, e.g. `std.math`
+    pub name: String,
+    pub source: CodeSource,
+}
+
+impl Library {
+    pub fn new(name: impl Into<String>, source: impl Into<CodeSource>) -> Self {
+        Self {
+            name: name.into(),
+            source: source.into(),
+        }
+    }
+}
@@ -2,9 +2,15 @@ pub mod ast;
 pub mod aast;
 pub mod bytecode;
 
+mod intrinsic;
+mod library;
+mod native;
 mod visibility;
 mod types;
 
+pub use intrinsic::Intrinsic;
+pub use library::Library;
+pub use native::NativeSignature;
 pub use visibility::{Visibility, VisibilityKind};
 pub use types::{Type, TypeKind};
 
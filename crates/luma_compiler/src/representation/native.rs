@@ -0,0 +1,21 @@
+use crate::TypeKind;
+
+/// Signature of a function implemented by the host.
+///
+/// Natives are declared in the root scope of every module, calls to them type-check like calls to Luma functions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NativeSignature {
+    pub name: String,
+    pub params: Vec<TypeKind>,
+    pub return_type: TypeKind,
+}
+
+impl NativeSignature {
+    pub fn new(name: impl Into<String>, params: impl IntoIterator<Item = TypeKind>, return_type: TypeKind) -> Self {
+        Self {
+            name: name.into(),
+            params: params.into_iter().collect(),
+            return_type,
+        }
+    }
+}
@@ -2,12 +2,19 @@ use std::{fmt::Display, ops::Deref};
 
 use luma_core::Span;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Eq)]
 pub struct Type {
     pub kind: TypeKind,
     pub span: Option<Span>,
 }
 
+/// Types are equal regardless of where they were written, so composite types compare structurally
+impl PartialEq for Type {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind
+    }
+}
+
 impl Type {
     #[must_use]
     pub const fn new(span: Option<Span>, kind: TypeKind) -> Self {
@@ -57,8 +64,14 @@ pub enum TypeKind {
     Error, // used to represent a type error, prevents cascading errors
     Tuple(Vec<Type>),
     Unit,
+    /// a reference to a local variable or parameter, written `*T`, taken with `&variable` and followed with `*reference`
     Ptr(Box<Type>),
-    // Array(Box<Type>, Option<Spanned<usize>>),
+    /// either a value of the inner type or `none`, written `T?`
+    Optional(Box<Type>),
+    /// either a value of the first type or an error of the second, written `T!E`
+    Result(Box<Type>, Box<Type>),
+    /// dynamically sized, only produced by host values for now
+    Array(Box<Type>),
     // Func(Vec<Type>, Box<Type>),
     Named {
         name: String,
@@ -74,6 +87,9 @@ impl Display for TypeKind {
             Self::String => write!(f, "string"),
             Self::Unit => write!(f, "()"),
             Self::Ptr(inner) => write!(f, "*{}", inner.kind),
+            Self::Optional(inner) => write!(f, "{}?", inner.kind),
+            Self::Result(value, error) => write!(f, "{}!{}", value.kind, error.kind),
+            Self::Array(element) => write!(f, "[{}]", element.kind),
             Self::Tuple(elements) => {
                 let elements = elements.iter().map(|ty| ty.to_string()).collect::<Vec<_>>().join(", ");
                 write!(f, "({})", elements)
@@ -142,6 +158,16 @@ impl TypeKind {
         matches!(self, TypeKind::Ptr(_))
     }
 
+    #[must_use]
+    pub const fn is_optional(&self) -> bool {
+        matches!(self, TypeKind::Optional(_))
+    }
+
+    #[must_use]
+    pub const fn is_result(&self) -> bool {
+        matches!(self, TypeKind::Result(..))
+    }
+
     #[must_use]
     pub const fn is_named(&self) -> bool {
         matches!(self, TypeKind::Named { .. })
@@ -162,6 +188,18 @@ impl TypeKind {
         matches!(self, TypeKind::Unit)
     }
 
+    /// Whether values of the type can be converted to a string, errors are to avoid cascading diagnostics
+    #[must_use]
+    pub fn is_displayable(&self) -> bool {
+        match self {
+            TypeKind::Tuple(elements) => elements.iter().all(|ty| ty.kind.is_displayable()),
+            TypeKind::Array(element) => element.kind.is_displayable(),
+            // optionals and results have to be unwrapped first
+            TypeKind::Unit | TypeKind::Ptr(_) | TypeKind::Optional(_) | TypeKind::Result(..) | TypeKind::Named { .. } => false,
+            _ => true,
+        }
+    }
+
     pub fn bits(&self) -> Option<usize> {
         Some(match self {
             TypeKind::UInt8 | TypeKind::Int8 => 8,
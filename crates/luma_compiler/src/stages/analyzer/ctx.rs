@@ -1,14 +1,23 @@
 use std::cell::RefCell;
 
-use luma_diagnostic::Diagnostic;
+use luma_diagnostic::{Diagnostic, DiagnosticLevel};
 
-use crate::stages::analyzer::{scopes::ScopeManager, symbols::SymbolTable, type_cache::TypeCache};
+use crate::{
+    Intrinsic, NativeSignature, Type, TypeKind,
+    stages::analyzer::{
+        scopes::ScopeManager,
+        symbols::{FunctionSignature, SymbolNamespace, SymbolTable},
+        type_cache::{TypeCache, TypeCacheEntry},
+    },
+};
 
 pub struct AnalyzerContext {
     pub diagnostics: RefCell<Vec<Diagnostic>>,
     pub scopes: RefCell<ScopeManager>,
     pub symbols: RefCell<SymbolTable>,
     pub type_cache: RefCell<TypeCache>,
+    /// return types of the functions being inferred, innermost last
+    pub return_types: RefCell<Vec<TypeCacheEntry>>,
 }
 
 impl AnalyzerContext {
@@ -18,11 +27,51 @@ impl AnalyzerContext {
             scopes: RefCell::new(ScopeManager::new()),
             symbols: RefCell::new(SymbolTable::new()),
             type_cache: RefCell::new(TypeCache::new()),
+            return_types: RefCell::new(Vec::new()),
         }
     }
 
+    /// Declares the VM's intrinsics in the root scope, natives and declarations of the sources shadow them
+    pub fn declare_intrinsics(&self) {
+        for intrinsic in Intrinsic::all() {
+            self.declare_builtin(intrinsic.name(), intrinsic.params(), intrinsic.return_type());
+        }
+    }
+
+    /// Declares host functions in the root scope, declarations of the sources shadow them
+    pub fn declare_natives(&self, natives: &[NativeSignature]) {
+        for native in natives {
+            let params = native.params.iter().cloned().map(Some).collect();
+            self.declare_builtin(&native.name, params, native.return_type.clone());
+        }
+    }
+
+    fn declare_builtin(&self, name: &str, params: Vec<Option<TypeKind>>, return_type: TypeKind) {
+        let root_scope = self.scopes.borrow().current_scope();
+        let mut symbols = self.symbols.borrow_mut();
+
+        let symbol_id = symbols.declare(
+            root_scope,
+            SymbolNamespace::Value,
+            name.to_string(),
+            Some(Type::unspanned(return_type.clone())),
+        );
+
+        symbols.set_signature(symbol_id, FunctionSignature {
+            params,
+            return_type: Some(return_type.clone()),
+        });
+
+        self.type_cache.borrow_mut().insert_concrete(symbol_id, return_type);
+    }
+
     #[inline]
     pub fn diagnostic(&self, diag: Diagnostic) {
         self.diagnostics.borrow_mut().push(diag);
     }
+
+    /// Whether a pass reported an error, warnings don't halt the analysis
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics.borrow().iter().any(|diag| diag.level == DiagnosticLevel::Error)
+    }
 }
\ No newline at end of file
@@ -3,31 +3,300 @@ use luma_diagnostic::{define_contexts, define_diagnostics};
 
 define_diagnostics! {
     pub enum AnalyzerError {
-        #[Error("unresolved identifier", "the identifier '{identifier}' could not be resolved in the current scope")]
+        #[Error(E0301, "unresolved identifier", "the identifier '{identifier}' could not be resolved in the current scope")]
+        /// The name isn't declared in the current scope or any enclosing one.
+        ///
+        /// Variables bound by `if var` are only visible inside the branch that binds them.
+        ///
+        /// ```luma
+        /// var y = if var x = maybe(1) { x } else { 0 };
+        /// var z = x;
+        /// ```
         UnresolvedIdentifier {
             identifier: String,
         },
-        #[Error("unidentified symbol", "the symbol '{name}' could not be identified")]
+        #[Error(E0302, "unidentified symbol", "the symbol '{name}' could not be identified")]
+        /// A name was used before name resolution assigned it a symbol, this is a bug in the compiler.
         UnidentifiedSymbol {
             name: String,
         },
-        #[Error("unresolved named type", "the type '{name}' could not be resolved in the current scope")]
+        #[Error(E0303, "unresolved named type", "the type '{name}' could not be resolved in the current scope")]
+        /// The type annotation names a struct that isn't declared.
+        ///
+        /// ```luma
+        /// var p: Point = none;
+        /// ```
         UnresolvedType {
             name: String,
         },
-        #[Error("unresolved struct field", "struct '{struct_name}' has no field named '{field_name}'")]
+        #[Error(E0304, "unresolved struct field", "struct '{struct_name}' has no field named '{field_name}'")]
+        /// The struct doesn't declare a field of that name.
+        ///
+        /// ```luma
+        /// struct Point { x: i32, y: i32 };
+        /// var p = Point { x: 1, z: 2 };
+        /// ```
         UnresolvedStructField {
             struct_name: String,
             field_name: String,
         },
-        #[Error("type inference could not infer the type")]
+        #[Error(E0305, "type inference could not infer the type")]
+        /// Nothing in the program determines the type of the expression, annotate the variable it's assigned to.
         TypeInferenceFailure,
-        #[Error("type mismatch", "expected type '{expected}' but found '{found}'")]
+        #[Error(E0306, "type mismatch", "expected type '{expected}' but found '{found}'")]
+        /// The value has a different type than the annotation, parameter or operator requires.
+        ///
+        /// ```luma
+        /// var x = 1;
+        /// var p: *bool = &x;
+        /// ```
         TypeMismatch {
             expected: TypeKind,
             found: TypeKind,
         },
-        #[Error("literal type mismatch", "expected type '{expected}' but found '{literal}'")]
+        #[Error(E0307, "not callable", "only functions can be called")]
+        /// Only functions can be called, the expression evaluates to a value.
+        ///
+        /// ```luma
+        /// var x = 1;
+        /// var y = x();
+        /// ```
+        NotCallable,
+        #[Error(E0308, "argument count mismatch", "expected {expected} arguments but found {found}")]
+        /// The call passes more or fewer arguments than the function declares parameters.
+        ///
+        /// ```luma
+        /// func add(a: i32, b: i32): i32 { a + b };
+        /// var x = add(1);
+        /// ```
+        ArgumentCountMismatch {
+            expected: usize,
+            found: usize,
+        },
+        #[Error(E0309, "not displayable", "values of type '{ty}' can't be converted to a string")]
+        /// Only numbers, chars, strings and booleans can be interpolated into a string.
+        ///
+        /// ```luma
+        /// func f() { };
+        /// var s = "{f}";
+        /// ```
+        NotDisplayable {
+            ty: TypeKind,
+        },
+        #[Error(E0310, "optional used without unwrapping", "values of type '{ty}' may be none, unwrap them with 'if var' or '?' first")]
+        /// An optional may hold no value, so it can't be used where a plain value is expected.
+        ///
+        /// ```luma
+        /// func maybe(x: i32): i32? { if x > 0 { x } else { none } };
+        /// var y = maybe(1) + 1;
+        /// ```
+        ///
+        /// Unwrap it first:
+        ///
+        /// ```luma
+        /// var y = if var x = maybe(1) { x + 1 } else { 0 };
+        /// ```
+        UnwrappedOptional {
+            ty: TypeKind,
+        },
+        #[Error(E0311, "not an optional", "expected an optional value but found '{ty}'")]
+        /// `if var` unwraps optionals, the value always exists.
+        ///
+        /// ```luma
+        /// var y = if var x = 1 { x } else { 0 };
+        /// ```
+        NotOptional {
+            ty: TypeKind,
+        },
+        #[Error(E0312, "'?' outside of an optional function", "'?' can only return none from functions returning an optional, found '{ty}'")]
+        /// `?` returns `none` from the enclosing function when the optional is empty, so that function has to return an optional.
+        ///
+        /// ```luma
+        /// func f(a: i32): i32 { maybe(a)? + 1 };
+        /// ```
+        PropagationOutsideOptional {
+            ty: TypeKind,
+        },
+        #[Error(E0313, "untyped none", "the optional type of 'none' can't be inferred, annotate it")]
+        /// `none` fits every optional type, annotate the variable to choose one.
+        ///
+        /// ```luma
+        /// var x = none;      // error
+        /// var y: i32? = none;
+        /// ```
+        UntypedNone,
+        #[Error(E0314, "nothing to propagate", "'?' needs an optional or a result but found '{ty}'")]
+        /// `?` only applies to optionals and results.
+        ///
+        /// ```luma
+        /// func f(a: i32): i32? { a? };
+        /// ```
+        NothingToPropagate {
+            ty: TypeKind,
+        },
+        #[Error(E0315, "not a result", "expected a result but found '{ty}'")]
+        /// `catch` handles the error of a result, the value can't fail.
+        ///
+        /// ```luma
+        /// var y = 1 catch e { 0 };
+        /// ```
+        NotResult {
+            ty: TypeKind,
+        },
+        #[Error(E0316, "'?' outside of a fallible function", "'?' can only return errors from functions returning a result, found '{ty}'")]
+        /// `?` returns the error from the enclosing function, so that function has to return a result.
+        ///
+        /// ```luma
+        /// func checked(x: i32): i32!str { if x > 0 { ok(x) } else { err("negative") } };
+        /// func f(a: i32): i32 { checked(a)? };
+        /// ```
+        PropagationOutsideResult {
+            ty: TypeKind,
+        },
+        #[Error(E0317, "error type mismatch", "expected errors of type '{expected}' but found '{found}'")]
+        /// `?` passes the error on unchanged, so both functions need the same error type.
+        ///
+        /// ```luma
+        /// func f(a: i32): i32!bool { ok(checked(a)?) };
+        /// ```
+        ErrorTypeMismatch {
+            expected: TypeKind,
+            found: TypeKind,
+        },
+        #[Error(E0318, "untyped result", "the result type of '{constructor}(..)' can't be inferred, annotate it")]
+        /// `ok(..)` only determines the value type and `err(..)` only the error type, annotate the variable.
+        ///
+        /// ```luma
+        /// var x = ok(1);         // error
+        /// var y: i32!str = ok(1);
+        /// ```
+        UntypedResult {
+            constructor: &'static str,
+        },
+        #[Error(E0319, "not addressable", "only variables can be referenced")]
+        /// `&` takes a reference to a variable, temporaries have no place to point at.
+        ///
+        /// ```luma
+        /// var p = &1;
+        /// ```
+        NotAddressable,
+        #[Error(E0320, "not a reference", "expected a reference but found '{ty}'")]
+        /// `*` reads through a reference, the value isn't one.
+        ///
+        /// ```luma
+        /// var x = 1;
+        /// var y = *x;
+        /// ```
+        NotPointer {
+            ty: TypeKind,
+        },
+        #[Error(E0321, "reference to a reference", "'{ty}' is already a reference, pass it on directly")]
+        /// References can't be nested, pass the existing reference on instead.
+        ///
+        /// ```luma
+        /// var x = 1;
+        /// var p = &x;
+        /// var q = &p;
+        /// ```
+        ReferenceToReference {
+            ty: TypeKind,
+        },
+        #[Error(E0322, "escaping reference", "the reference to '{name}' outlives the variable")]
+        /// A function can't return a reference to its own locals or parameters, they're gone once it returns.
+        ///
+        /// ```luma
+        /// func f(): *i32 { var x = 1; &x };
+        /// ```
+        EscapingReference {
+            name: String,
+        },
+        #[Error(E0323, "global used before initialization", "'{name}' is used before its declaration runs")]
+        /// Globals are initialized in declaration order, a global can't be read before its declaration.
+        ///
+        /// ```luma
+        /// var a = b + 1;
+        /// var b = 1;
+        /// ```
+        UninitializedGlobal {
+            name: String,
+        },
+        #[Error(E0324, "global used before initialization", "'{function}' uses '{name}' before its declaration runs")]
+        /// The called function reads a global that isn't initialized yet at the time of the call.
+        ///
+        /// ```luma
+        /// func get(): i32 { b };
+        /// var a = get();
+        /// var b = 1;
+        /// ```
+        UninitializedGlobalCall {
+            name: String,
+            function: String,
+        },
+        #[Error(E0325, "recursive struct", "'{name}' contains itself by value through {cycle}, its layout would be infinite")]
+        /// A struct can't contain itself, directly or through other structs, optionals, results or tuples.
+        ///
+        /// Store a reference or an array instead.
+        ///
+        /// ```luma
+        /// struct Node { value: i32, next: Node? };
+        /// ```
+        RecursiveStruct {
+            name: String,
+            cycle: String,
+        },
+        #[Warning(W0301, "unused variable", "'{name}' is never read, prefix it with '_' to silence this")]
+        /// The local variable is written but never read, reported by the `unused_variables` lint.
+        ///
+        /// ```luma
+        /// pub func f() { var x = 1; };
+        /// ```
+        UnusedVariable {
+            name: String,
+        },
+        #[Warning(W0302, "unused parameter", "'{name}' is never read, prefix it with '_' to silence this")]
+        /// The parameter is never read, reported by the `unused_parameters` lint.
+        ///
+        /// ```luma
+        /// pub func f(a: i32) { };
+        /// ```
+        UnusedParameter {
+            name: String,
+        },
+        #[Warning(W0303, "unused function", "'{name}' is never called, prefix it with '_' or make it 'pub' to silence this")]
+        /// The private function is never called by the module, reported by the `unused_functions` lint.
+        ///
+        /// ```luma
+        /// func helper() { };
+        /// ```
+        UnusedFunction {
+            name: String,
+        },
+        #[Warning(W0304, "unused field", "field '{name}' of '{struct_name}' is never read")]
+        /// The private field is never read, reported by the `unused_fields` lint.
+        ///
+        /// ```luma
+        /// struct Point { x: i32, pub y: i32 };
+        /// ```
+        UnusedField {
+            name: String,
+            struct_name: String,
+        },
+        #[Warning(W0305, "unused import", "nothing imported from '{module}' is used")]
+        /// None of the items of the imported library are used, reported by the `unused_imports` lint.
+        ///
+        /// ```luma
+        /// import util;
+        /// var x = 1;
+        /// ```
+        UnusedImport {
+            module: String,
+        },
+        #[Error(E0326, "literal type mismatch", "expected type '{expected}' but found '{literal}'")]
+        /// The literal can't have the type the context requires.
+        ///
+        /// ```luma
+        /// var x: i32!str = err(1);
+        /// ```
         LiteralTypeMismatch {
             literal: LiteralExpr,
             expected: TypeKind,
@@ -44,4 +313,4 @@ define_contexts! {
         #[Context("in block expression")]
         BlockContext,
     }
-}
\ No newline at end of file
+}
@@ -66,6 +66,9 @@ impl<Input> CompilerStage<'_> for AnalyzerStage<Input> {
     fn process(mut self, ctx: &CompilerContext, input: Self::Input) -> Self::Output {
         let mut asts = input;
 
+        self.ctx.declare_intrinsics();
+        self.ctx.declare_natives(&ctx.natives);
+
         // todo: somehow make this faster (parallelize?)
         'ast_loop: for ast in &mut asts {
             for analyzer in self.passes.iter() {
@@ -73,9 +76,8 @@ impl<Input> CompilerStage<'_> for AnalyzerStage<Input> {
 
                 analyzer.analyze(&mut self.ctx, ast);
 
-                if !analyzer.continue_after_error()
-                    && !self.ctx.diagnostics.borrow().is_empty() {
-                        tracing::debug!("analyzer stage '{}' produced diagnostics, halting further analysis", analyzer.name());
+                if !analyzer.continue_after_error() && self.ctx.has_errors() {
+                        tracing::debug!("analyzer stage '{}' produced errors, halting further analysis", analyzer.name());
                         continue 'ast_loop;
                     }
             }
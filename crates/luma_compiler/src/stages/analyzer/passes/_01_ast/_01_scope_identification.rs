@@ -37,6 +37,10 @@ impl AstVisitor<'_> for ScopeIdentification {
         expr.scope_id = Some(ctx.scopes.borrow().current_scope());
     }
 
+    fn visit_binding(&self, ctx: &mut Self::Ctx, binding: &mut Binding) {
+        binding.scope_id = Some(ctx.scopes.borrow().current_scope());
+    }
+
     fn visit_func_param<'node>(&self, ctx: &mut Self::Ctx, _func: &'node FuncDeclStmt, param: &'node mut FuncParam) {
         param.scope_id = Some(ctx.scopes.borrow().current_scope());
     }
@@ -1,6 +1,15 @@
-use crate::stages::analyzer::{AnalyzerContext, AnalyzerPass, symbols::SymbolNamespace};
+use std::collections::{HashMap, HashSet};
+
+use luma_diagnostic::error;
+
+use crate::stages::analyzer::{
+    AnalyzerContext, AnalyzerError, AnalyzerPass,
+    symbols::{FunctionSignature, SymbolNamespace},
+};
 use crate::{ScopeId, SymbolId, Type, TypeKind, ast::*};
 
+/// Declares every item with its signature before any name is resolved,
+/// so functions and structs can be used before their declaration
 pub struct NameDeclaration;
 
 impl AnalyzerPass<Ast> for NameDeclaration {
@@ -10,6 +19,7 @@ impl AnalyzerPass<Ast> for NameDeclaration {
 
     fn analyze(&self, ctx: &mut AnalyzerContext, input: &mut Ast) {
         self.traverse(ctx, input);
+        self.check_struct_layouts(ctx, input);
     }
 }
 
@@ -28,34 +38,20 @@ impl AstVisitor<'_> for NameDeclaration {
                 );
             }
             StmtKind::Func(func_decl) => {
-                self.declare_symbol(
+                let symbol_id = self.declare_symbol(
                     ctx,
                     stmt.scope_id.unwrap(),
                     &mut func_decl.symbol,
                     SymbolNamespace::Value,
                     func_decl.return_type.clone(),
                 );
+
+                ctx.symbols.borrow_mut().set_signature(symbol_id, FunctionSignature {
+                    params: func_decl.parameters.iter().map(|param| Some(param.ty.kind.clone())).collect(),
+                    return_type: func_decl.return_type.as_ref().map(|ty| ty.kind.clone()),
+                });
             }
             StmtKind::Struct(struct_decl) => {
-                // walk fields first
-                // todo: struct fields decl
-                // for (index, field) in struct_decl.fields.iter_mut().enumerate() {
-                //     let symbol = &mut field.symbol.kind;
-
-                //     // this should never occur, as we should only visit fields of declared structs
-                //     let struct_id = struct_symbol.kind.id().unwrap();
-
-                //     let current_scope = ctx.scopes.borrow().current_scope();
-                //     let symbol_id = ctx.symbols.borrow_mut().declare(
-                //         current_scope,
-                //         SymbolNamespace::StructField(struct_id),
-                //         symbol.name().to_string(),
-                //         Some(field.ty.clone())
-                //     );
-
-                //     symbol.set_id(symbol_id);
-                // }
-
                 let ty = Some(Type::spanned(
                     struct_decl.symbol.span,
                     TypeKind::Named {
@@ -64,12 +60,34 @@ impl AstVisitor<'_> for NameDeclaration {
                     },
                 ));
 
-                self.declare_symbol(ctx, stmt.scope_id.unwrap(), &mut struct_decl.symbol, SymbolNamespace::Type, ty);
+                let scope_id = stmt.scope_id.unwrap();
+                let struct_id = self.declare_symbol(ctx, scope_id, &mut struct_decl.symbol, SymbolNamespace::Type, ty);
+
+                for field in &mut struct_decl.fields {
+                    self.declare_symbol(
+                        ctx,
+                        scope_id,
+                        &mut field.symbol,
+                        SymbolNamespace::StructField(struct_id),
+                        Some(field.ty.clone()),
+                    );
+                }
             }
             _ => {},
         }
     }
 
+    fn visit_binding(&self, ctx: &mut Self::Ctx, binding: &mut Binding) {
+        // typed by the unwrapped condition during inference
+        self.declare_symbol(
+            ctx,
+            binding.scope_id.unwrap(),
+            &mut binding.symbol,
+            SymbolNamespace::Value,
+            None,
+        );
+    }
+
     fn leave_func_param<'node>(&self, ctx: &mut Self::Ctx, _func: &'node FuncDeclStmt, param: &'node mut FuncParam) {
         self.declare_symbol(
             ctx,
@@ -100,4 +118,94 @@ impl NameDeclaration {
         symbol.set_id(symbol_id);
         symbol_id
     }
+
+    /// Reports structs containing themselves by value, references and arrays break the cycle
+    fn check_struct_layouts(&self, ctx: &mut AnalyzerContext, input: &Ast) {
+        let structs = input
+            .statements
+            .iter()
+            .filter_map(|stmt| match &stmt.item {
+                StmtKind::Struct(struct_decl) => Some((struct_decl.symbol.name(), struct_decl)),
+                _ => None,
+            })
+            .collect::<HashMap<_, _>>();
+
+        // every struct of a reported cycle is skipped, so a cycle is reported once
+        let mut reported = HashSet::new();
+
+        for stmt in &input.statements {
+            let StmtKind::Struct(struct_decl) = &stmt.item else {
+                continue;
+            };
+
+            let name = struct_decl.symbol.name();
+            let mut path = vec![name];
+
+            if reported.contains(name) || !Self::find_cycle(&structs, struct_decl, name, &mut path) {
+                continue;
+            }
+
+            reported.extend(path.iter().copied());
+
+            ctx.diagnostic(
+                error!(AnalyzerError::RecursiveStruct {
+                    name: name.to_string(),
+                    cycle: path.join(" -> "),
+                })
+                .span(struct_decl.symbol.span),
+            );
+        }
+    }
+
+    /// Whether the struct leads back to `root` through its by-value fields, leaving the cycle in `path`
+    fn find_cycle<'a>(
+        structs: &HashMap<&'a str, &'a StructDeclStmt>,
+        struct_decl: &'a StructDeclStmt,
+        root: &str,
+        path: &mut Vec<&'a str>,
+    ) -> bool {
+        let mut contained = Vec::new();
+        for field in &struct_decl.fields {
+            contained_structs(&field.ty.kind, &mut contained);
+        }
+
+        for name in contained {
+            if name == root {
+                path.push(name);
+                return true;
+            }
+
+            let Some(inner) = structs.get(name).filter(|_| !path.contains(&name)) else {
+                continue;
+            };
+
+            path.push(name);
+
+            if Self::find_cycle(structs, inner, root, path) {
+                return true;
+            }
+
+            path.pop();
+        }
+
+        false
+    }
+}
+
+/// Collects the names of the structs stored inline in a value of the type
+fn contained_structs<'a>(ty: &'a TypeKind, names: &mut Vec<&'a str>) {
+    match ty {
+        TypeKind::Named { name, .. } => names.push(name),
+        TypeKind::Optional(inner) => contained_structs(&inner.kind, names),
+        TypeKind::Result(value, error) => {
+            contained_structs(&value.kind, names);
+            contained_structs(&error.kind, names);
+        }
+        TypeKind::Tuple(elements) => {
+            for element in elements {
+                contained_structs(&element.kind, names);
+            }
+        }
+        _ => {}
+    }
 }
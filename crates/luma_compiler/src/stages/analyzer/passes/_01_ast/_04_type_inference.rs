@@ -1,8 +1,8 @@
 use luma_core::Span;
 use luma_diagnostic::{context, error};
 
-use crate::stages::analyzer::type_cache::TypeCacheEntry;
-use crate::{TypeKind, ast::*};
+use crate::stages::analyzer::{symbols::FunctionSignature, type_cache::TypeCacheEntry};
+use crate::{SymbolId, Type, TypeKind, ast::*};
 
 use crate::stages::analyzer::{AnalyzerContext, AnalyzerError, AnalyzerErrorContext, AnalyzerPass};
 
@@ -14,6 +14,13 @@ impl AnalyzerPass<Ast> for TypeInference {
     }
 
     fn analyze(&self, ctx: &mut AnalyzerContext, input: &mut Ast) {
+        // top-level functions may be called before their declaration
+        for stmt in &input.statements {
+            if let StmtKind::Func(func_decl) = &stmt.item {
+                Self::declare_function(ctx, func_decl);
+            }
+        }
+
         for stmt in &mut input.statements {
             self.infer_stmt(ctx, &TypeCacheEntry::Concrete(TypeKind::Unit), stmt);
         }
@@ -32,20 +39,11 @@ impl TypeInference {
                 self.infer_expr(ctx, contextual, expr);
             }
             StmtKind::Func(func_decl) => {
-                let symbol_id = func_decl.symbol.unwrap_id();
-
-                let type_entry = {
-                    let mut ty_cache = ctx.type_cache.borrow_mut();
-                    if let Some(ret_type) = &func_decl.return_type {
-                        ty_cache.insert_concrete(symbol_id, ret_type.kind.clone());
-                        TypeCacheEntry::Concrete(ret_type.kind.clone())
-                    } else {
-                        let id = ty_cache.insert_relative(symbol_id);
-                        TypeCacheEntry::Relative(id)
-                    }
-                };
+                let type_entry = Self::declare_function(ctx, func_decl);
 
+                ctx.return_types.borrow_mut().push(type_entry.clone());
                 let body_type = self.infer_expr(ctx, &type_entry, &mut func_decl.body);
+                ctx.return_types.borrow_mut().pop();
 
                 if let Err(err) = ctx.type_cache.borrow_mut().unify(&type_entry, &body_type) {
                     ctx.diagnostic(
@@ -69,12 +67,16 @@ impl TypeInference {
                 }
             }
             StmtKind::Return(_) => todo!(),
-            StmtKind::Struct(_) => todo!(),
+            // field types are declared, there is nothing to infer
+            StmtKind::Struct(_) => {}
+            // the imported declarations are separate statements
+            StmtKind::Import(_) => {}
             StmtKind::Var(var_decl) => {
                 let symbol_id = var_decl.symbol.unwrap_id();
 
                 let type_entry = {
                     let mut ty_cache = ctx.type_cache.borrow_mut();
+
                     if let Some(ty) = &var_decl.ty {
                         ty_cache.insert_concrete(symbol_id, ty.kind.clone());
                         TypeCacheEntry::Concrete(ty.kind.clone())
@@ -100,9 +102,29 @@ impl TypeInference {
         expr: &mut Expr,
     ) -> TypeCacheEntry {
         match &mut expr.item {
+            ExprKind::AddressOf(value) => {
+                if !matches!(value.item, ExprKind::Ident(_)) {
+                    ctx.diagnostic(error!(AnalyzerError::NotAddressable).span(value.span));
+                    self.infer_expr(ctx, &TypeCacheEntry::Concrete(TypeKind::Unit), value);
+                    return TypeCacheEntry::Concrete(TypeKind::Error);
+                }
+
+                let value_type = self.infer_expr(ctx, &TypeCacheEntry::Concrete(TypeKind::Unit), value);
+                let resolved = ctx.type_cache.borrow_mut().resolve(&value_type).unwrap_or(TypeKind::Error);
+
+                match resolved {
+                    TypeKind::Error => TypeCacheEntry::Concrete(TypeKind::Error),
+                    ty @ TypeKind::Ptr(_) => {
+                        ctx.diagnostic(error!(AnalyzerError::ReferenceToReference { ty: ty.clone() }).span(expr.span));
+                        TypeCacheEntry::Concrete(TypeKind::Error)
+                    }
+                    ty => TypeCacheEntry::Concrete(pointer_type(ty)),
+                }
+            }
             ExprKind::Assign(assign_expr) => {
                 let left_type = self.infer_expr(ctx, contextual_type, &mut assign_expr.target);
-                let right_type = self.infer_expr(ctx, contextual_type, &mut assign_expr.value);
+                // the value is typed by the variable it is assigned to
+                let right_type = self.infer_expr(ctx, &left_type, &mut assign_expr.value);
 
                 if let Err(err) = ctx.type_cache.borrow_mut().unify(&left_type, &right_type) {
                     ctx.diagnostic(err.span(expr.span));
@@ -111,14 +133,57 @@ impl TypeInference {
                 left_type
             },
             ExprKind::Binary(binary_expr) => {
-                let left_type = self.infer_expr(ctx, contextual_type, &mut binary_expr.left);
-                let right_type = self.infer_expr(ctx, contextual_type, &mut binary_expr.right);
+                let operator = binary_expr.operator.kind.clone();
 
-                if let Err(err) = ctx.type_cache.borrow_mut().unify(&left_type, &right_type) {
+                let (left_type, right_type) = if operator.is_comparison() {
+                    let no_context = TypeCacheEntry::Concrete(TypeKind::Unit);
+                    let (first, second) = comparison_operands(binary_expr);
+
+                    let first_type = self.infer_expr(ctx, &no_context, first);
+                    let second_type = self.infer_expr(ctx, &first_type, second);
+
+                    if types_right_first(binary_expr) {
+                        (second_type, first_type)
+                    } else {
+                        (first_type, second_type)
+                    }
+                } else {
+                    // logic operands are always booleans
+                    let operand_type = if operator.is_logic() {
+                        TypeCacheEntry::Concrete(TypeKind::Bool)
+                    } else {
+                        contextual_type.clone()
+                    };
+
+                    let left_type = self.infer_expr(ctx, &operand_type, &mut binary_expr.left);
+                    let right_type = self.infer_expr(ctx, &operand_type, &mut binary_expr.right);
+
+                    (left_type, right_type)
+                };
+
+                // only equality can compare optionals, either side may be the optional one
+                let is_equality = matches!(operator, OperatorKind::Equal | OperatorKind::NotEqual);
+                let unified = if is_equality {
+                    ctx.type_cache.borrow_mut().join(&left_type, &right_type).map(|_| ())
+                } else {
+                    ctx.type_cache.borrow_mut().unify(&left_type, &right_type)
+                };
+
+                let unwrapped = if let Err(err) = unified {
                     ctx.diagnostic(err.span(binary_expr.operator.span));
-                }
+                    true
+                } else {
+                    is_equality || Self::check_unwrapped(ctx, &left_type, binary_expr.left.span)
+                };
 
-                left_type
+                if operator.is_logic() || operator.is_comparison() {
+                    TypeCacheEntry::Concrete(TypeKind::Bool)
+                } else if unwrapped {
+                    left_type
+                } else {
+                    // the optional operand was reported, the result must not be reported again
+                    TypeCacheEntry::Concrete(TypeKind::Error)
+                }
             }
             ExprKind::Block(block_expr) => {
                 for stmt in &mut block_expr.statements {
@@ -131,34 +196,135 @@ impl TypeInference {
                     TypeCacheEntry::Concrete(TypeKind::Unit)
                 }
             }
-            ExprKind::Call(_) => todo!(),
+            ExprKind::Call(call_expr) => {
+                let Some((symbol_id, signature)) = Self::callee_signature(ctx, &call_expr.callee) else {
+                    ctx.diagnostic(error!(AnalyzerError::NotCallable).span(call_expr.callee.span));
+                    return TypeCacheEntry::Concrete(TypeKind::Error);
+                };
+
+                if call_expr.arguments.len() != signature.params.len() {
+                    ctx.diagnostic(
+                        error!(AnalyzerError::ArgumentCountMismatch {
+                            expected: signature.params.len(),
+                            found: call_expr.arguments.len(),
+                        })
+                        .span(expr.span),
+                    );
+                    return TypeCacheEntry::Concrete(TypeKind::Error);
+                }
+
+                for (argument, param) in call_expr.arguments.iter_mut().zip(&signature.params) {
+                    // parameters accepting any displayable value give no context, like comparison operands
+                    let param_type = TypeCacheEntry::Concrete(param.clone().unwrap_or(TypeKind::Unit));
+                    let argument_type = self.infer_expr(ctx, &param_type, argument);
+
+                    // the argument already reported its own error
+                    if param.is_none() || argument_type.as_concrete() == Some(&TypeKind::Error) {
+                        continue;
+                    }
+
+                    if let Err(err) = ctx.type_cache.borrow_mut().unify(&param_type, &argument_type) {
+                        ctx.diagnostic(err.span(argument.span));
+                    }
+                }
+
+                Self::call_type(ctx, symbol_id, &signature)
+            }
+            ExprKind::Catch(catch_expr) => {
+                // the value is the result being handled, it gets no context
+                let result_type = self.infer_expr(
+                    ctx,
+                    &TypeCacheEntry::Concrete(TypeKind::Unit),
+                    &mut catch_expr.value,
+                );
+
+                let resolved = ctx.type_cache.borrow_mut().resolve(&result_type).unwrap_or(TypeKind::Error);
+                let (value_type, error_type) = match resolved {
+                    TypeKind::Result(value_type, error_type) => (value_type.kind, error_type.kind),
+                    TypeKind::Error => (TypeKind::Error, TypeKind::Error),
+                    ty => {
+                        ctx.diagnostic(error!(AnalyzerError::NotResult { ty: ty.clone() }).span(catch_expr.value.span));
+                        (TypeKind::Error, TypeKind::Error)
+                    }
+                };
+
+                ctx.type_cache.borrow_mut().insert_concrete(catch_expr.binding.symbol.unwrap_id(), error_type);
+
+                // the handler stands in for the value, so it has to produce the same type
+                let value_type = TypeCacheEntry::Concrete(value_type);
+                let handler_context = match value_type {
+                    TypeCacheEntry::Concrete(TypeKind::Error) => TypeCacheEntry::Concrete(TypeKind::Unit),
+                    _ => value_type.clone(),
+                };
+                let handler_type = self.infer_expr(ctx, &handler_context, &mut catch_expr.handler);
+
+                if value_type.as_concrete() != Some(&TypeKind::Error)
+                    && handler_type.as_concrete() != Some(&TypeKind::Error)
+                    && let Err(err) = ctx.type_cache.borrow_mut().unify(&value_type, &handler_type)
+                {
+                    ctx.diagnostic(err.span(catch_expr.handler.span));
+                }
+
+                value_type
+            }
+            ExprKind::Deref(value) => {
+                let value_type = self.infer_expr(ctx, &TypeCacheEntry::Concrete(TypeKind::Unit), value);
+                let resolved = ctx.type_cache.borrow_mut().resolve(&value_type).unwrap_or(TypeKind::Error);
+
+                match resolved {
+                    TypeKind::Ptr(inner) => TypeCacheEntry::Concrete(inner.kind),
+                    TypeKind::Error => TypeCacheEntry::Concrete(TypeKind::Error),
+                    ty => {
+                        ctx.diagnostic(error!(AnalyzerError::NotPointer { ty: ty.clone() }).span(value.span));
+                        TypeCacheEntry::Concrete(TypeKind::Error)
+                    }
+                }
+            }
+            ExprKind::Err(value) => self.infer_result_constructor(ctx, contextual_type, value, false, expr.span),
             ExprKind::Get(_) => todo!(),
             ExprKind::Group(expr) => self.infer_expr(ctx, contextual_type, expr),
             ExprKind::Ident(ident_expr) => {
                 let symbol_id = ident_expr.symbol.unwrap_id();
-                ctx.type_cache
-                    .borrow()
-                    .get(symbol_id)
-                    .cloned()
-                    .unwrap_or_else(|| {
-                        let id = ctx.type_cache.borrow_mut().insert_relative(symbol_id);
-                        TypeCacheEntry::Relative(id)
-                    })
+                let mut ty_cache = ctx.type_cache.borrow_mut();
+
+                match ty_cache.get(symbol_id) {
+                    Some(entry) => entry.clone(),
+                    None => TypeCacheEntry::Relative(ty_cache.insert_relative(symbol_id)),
+                }
             }
             ExprKind::If(if_expr) => {
-                let cond_type = self.infer_expr(
-                    ctx,
-                    &TypeCacheEntry::Concrete(TypeKind::Bool),
-                    &mut if_expr.condition,
-                );
+                if let Some(binding) = &if_expr.binding {
+                    // the condition is the optional being unwrapped, it gets no context
+                    let optional_type = self.infer_expr(
+                        ctx,
+                        &TypeCacheEntry::Concrete(TypeKind::Unit),
+                        &mut if_expr.condition,
+                    );
 
-                if let Err(err) = ctx
-                    .type_cache
-                    .borrow_mut()
-                    .unify(&cond_type, &TypeCacheEntry::Concrete(TypeKind::Bool))
-                {
-                    ctx.diagnostic(err.span(if_expr.condition.span));
-                    return TypeCacheEntry::Concrete(TypeKind::Error);
+                    let Some(inner) = Self::unwrap_optional(ctx, &optional_type, if_expr.condition.span) else {
+                        return TypeCacheEntry::Concrete(TypeKind::Error);
+                    };
+
+                    ctx.type_cache.borrow_mut().insert_concrete(binding.symbol.unwrap_id(), inner);
+                } else {
+                    let cond_type = self.infer_expr(
+                        ctx,
+                        &TypeCacheEntry::Concrete(TypeKind::Bool),
+                        &mut if_expr.condition,
+                    );
+
+                    if let Err(err) = ctx
+                        .type_cache
+                        .borrow_mut()
+                        .unify(&cond_type, &TypeCacheEntry::Concrete(TypeKind::Bool))
+                    {
+                        ctx.diagnostic(err.span(if_expr.condition.span));
+                        return TypeCacheEntry::Concrete(TypeKind::Error);
+                    }
+
+                    if !Self::check_unwrapped(ctx, &cond_type, if_expr.condition.span) {
+                        return TypeCacheEntry::Concrete(TypeKind::Error);
+                    }
                 }
 
                 let then_type = self.infer_expr(ctx, contextual_type, &mut if_expr.then_branch);
@@ -196,28 +362,251 @@ impl TypeInference {
                         .resolve(&else_type)
                         .unwrap_or(TypeKind::Error);
 
-                    if let Err(err) = ctx.type_cache.borrow_mut().unify(
+                    return match ctx.type_cache.borrow_mut().join(
                         &TypeCacheEntry::Concrete(resolved_then_type.clone()),
                         &TypeCacheEntry::Concrete(resolved_else_type.clone()),
                     ) {
-                        ctx.diagnostic(err.span(expr.span));
-                        return TypeCacheEntry::Concrete(TypeKind::Error);
-                    }
+                        Ok(_) if resolved_else_type.is_optional() => else_type,
+                        Ok(_) => then_type,
+                        Err(err) => {
+                            ctx.diagnostic(err.span(expr.span));
+                            TypeCacheEntry::Concrete(TypeKind::Error)
+                        }
+                    };
                 }
 
                 then_type
             }
+            ExprKind::Interpolation(interpolation_expr) => {
+                // interpolated values can be of any displayable type, so they get no context
+                let no_context = TypeCacheEntry::Concrete(TypeKind::Unit);
+
+                for part in &mut interpolation_expr.parts {
+                    if let InterpolationPart::Expr(expr) = part {
+                        self.infer_expr(ctx, &no_context, expr);
+                    }
+                }
+
+                TypeCacheEntry::Concrete(TypeKind::String)
+            }
             ExprKind::Literal(lit) => {
                 Self::infer_literal_type(ctx, contextual_type, lit, expr.span)
             }
+            ExprKind::Ok(value) => self.infer_result_constructor(ctx, contextual_type, value, true, expr.span),
+            ExprKind::Propagate(value) => {
+                let propagated_type = self.infer_expr(ctx, &TypeCacheEntry::Concrete(TypeKind::Unit), value);
+                let resolved = ctx.type_cache.borrow_mut().resolve(&propagated_type);
+
+                // the enclosing function returns none or the error for it, so it has to return the same kind
+                let return_type = ctx.return_types.borrow().last().cloned();
+                let return_type = return_type
+                    .and_then(|entry| ctx.type_cache.borrow_mut().resolve(&entry))
+                    .unwrap_or(TypeKind::Unit);
+
+                // the unwrapped value is still usable, so only the `?` itself is reported
+                match resolved {
+                    Some(TypeKind::Optional(inner)) => {
+                        if !return_type.is_optional() && return_type != TypeKind::Error {
+                            ctx.diagnostic(
+                                error!(AnalyzerError::PropagationOutsideOptional { ty: return_type.clone() })
+                                    .span(expr.span),
+                            );
+                        }
+
+                        TypeCacheEntry::Concrete(inner.kind)
+                    }
+                    Some(TypeKind::Result(value_type, error_type)) => {
+                        match return_type {
+                            TypeKind::Result(_, returned_error) if returned_error.kind != error_type.kind => {
+                                ctx.diagnostic(
+                                    error!(AnalyzerError::ErrorTypeMismatch {
+                                        expected: returned_error.kind.clone(),
+                                        found: error_type.kind.clone(),
+                                    })
+                                    .span(expr.span),
+                                );
+                            }
+                            TypeKind::Result(..) | TypeKind::Error => {}
+                            ty => {
+                                ctx.diagnostic(error!(AnalyzerError::PropagationOutsideResult { ty: ty.clone() }).span(expr.span));
+                            }
+                        }
+
+                        TypeCacheEntry::Concrete(value_type.kind)
+                    }
+                    // a value that can't be propagated is used as is after the error
+                    Some(TypeKind::Error) | None => propagated_type,
+                    Some(ty) => {
+                        ctx.diagnostic(error!(AnalyzerError::NothingToPropagate { ty: ty.clone() }).span(value.span));
+                        propagated_type
+                    }
+                }
+            }
             ExprKind::Struct(_) => todo!(),
+            ExprKind::Try(value) => {
+                let value_type = self.infer_expr(ctx, &TypeCacheEntry::Concrete(TypeKind::Unit), value);
+                let resolved = ctx.type_cache.borrow_mut().resolve(&value_type).unwrap_or(TypeKind::Error);
+
+                // runtime errors are caught as strings, a result with string errors is merged with them
+                let value_type = match resolved {
+                    TypeKind::Result(value_type, error_type) => {
+                        if error_type.kind != TypeKind::String {
+                            ctx.diagnostic(
+                                error!(AnalyzerError::ErrorTypeMismatch {
+                                    expected: TypeKind::String,
+                                    found: error_type.kind.clone(),
+                                })
+                                .span(value.span),
+                            );
+                        }
+
+                        value_type.kind
+                    }
+                    ty => ty,
+                };
+
+                TypeCacheEntry::Concrete(caught_type(value_type))
+            }
             ExprKind::TupleLiteral(_) => todo!(),
             ExprKind::Unary(unary_expr) => {
-                self.infer_expr(ctx, contextual_type, &mut unary_expr.value)
+                if unary_expr.operator.kind == OperatorKind::Not {
+                    let bool_type = TypeCacheEntry::Concrete(TypeKind::Bool);
+                    let value_type = self.infer_expr(ctx, &bool_type, &mut unary_expr.value);
+
+                    if let Err(err) = ctx.type_cache.borrow_mut().unify(&value_type, &bool_type) {
+                        ctx.diagnostic(err.span(unary_expr.value.span));
+                    } else {
+                        Self::check_unwrapped(ctx, &value_type, unary_expr.value.span);
+                    }
+
+                    bool_type
+                } else {
+                    let value_type = self.infer_expr(ctx, contextual_type, &mut unary_expr.value);
+
+                    if Self::check_unwrapped(ctx, &value_type, unary_expr.value.span) {
+                        value_type
+                    } else {
+                        TypeCacheEntry::Concrete(TypeKind::Error)
+                    }
+                }
             },
         }
     }
 
+    /// Types `ok(..)` and `err(..)` by the result type expected of them
+    fn infer_result_constructor(
+        &self,
+        ctx: &mut AnalyzerContext,
+        contextual_type: &TypeCacheEntry,
+        value: &mut Expr,
+        is_ok: bool,
+        span: Span,
+    ) -> TypeCacheEntry {
+        let Some((value_type, error_type)) = Self::expected_result(ctx, contextual_type) else {
+            // the value may still have errors of its own
+            self.infer_expr(ctx, &TypeCacheEntry::Concrete(TypeKind::Unit), value);
+
+            let constructor = if is_ok { "ok" } else { "err" };
+            ctx.diagnostic(error!(AnalyzerError::UntypedResult { constructor }).span(span));
+
+            return TypeCacheEntry::Concrete(TypeKind::Error);
+        };
+
+        let expected = TypeCacheEntry::Concrete(if is_ok { value_type.clone() } else { error_type.clone() });
+        let found = self.infer_expr(ctx, &expected, value);
+
+        // a value that failed on its own was reported already
+        if found.as_concrete() != Some(&TypeKind::Error)
+            && let Err(err) = ctx.type_cache.borrow_mut().unify(&expected, &found)
+        {
+            ctx.diagnostic(err.span(value.span));
+        }
+
+        TypeCacheEntry::Concrete(result_type(value_type, error_type))
+    }
+
+    /// The value and error types of the result the context expects, if it expects one
+    pub(super) fn expected_result(ctx: &AnalyzerContext, contextual_type: &TypeCacheEntry) -> Option<(TypeKind, TypeKind)> {
+        match ctx.type_cache.borrow_mut().resolve(contextual_type)? {
+            TypeKind::Result(value_type, error_type) => Some((value_type.kind, error_type.kind)),
+            _ => None,
+        }
+    }
+
+    /// Reports an optional used where a present value is needed, returns whether the value is usable
+    fn check_unwrapped(ctx: &AnalyzerContext, entry: &TypeCacheEntry, span: Span) -> bool {
+        let ty = ctx.type_cache.borrow_mut().resolve(entry);
+
+        match ty {
+            Some(ty @ TypeKind::Optional(_)) => {
+                ctx.diagnostic(error!(AnalyzerError::UnwrappedOptional { ty: ty.clone() }).span(span));
+                false
+            }
+            _ => true,
+        }
+    }
+
+    /// The type wrapped by an optional, reports values that aren't optional
+    fn unwrap_optional(ctx: &AnalyzerContext, entry: &TypeCacheEntry, span: Span) -> Option<TypeKind> {
+        let ty = ctx.type_cache.borrow_mut().resolve(entry)?;
+
+        match ty {
+            TypeKind::Optional(inner) => Some(inner.kind),
+            // already reported
+            TypeKind::Error => None,
+            ty => {
+                ctx.diagnostic(error!(AnalyzerError::NotOptional { ty: ty.clone() }).span(span));
+                None
+            }
+        }
+    }
+
+    /// Returns the symbol and signature of the called function, `None` if the callee isn't a function
+    /// Types the parameters and the return type of the function, keeping the entry of a function already called
+    fn declare_function(ctx: &AnalyzerContext, func_decl: &FuncDeclStmt) -> TypeCacheEntry {
+        let symbol_id = func_decl.symbol.unwrap_id();
+        let mut ty_cache = ctx.type_cache.borrow_mut();
+
+        for param in &func_decl.parameters {
+            ty_cache.insert_concrete(param.symbol.unwrap_id(), param.ty.kind.clone());
+        }
+
+        if let Some(ret_type) = &func_decl.return_type {
+            ty_cache.insert_concrete(symbol_id, ret_type.kind.clone());
+            return TypeCacheEntry::Concrete(ret_type.kind.clone());
+        }
+
+        match ty_cache.get(symbol_id) {
+            Some(entry) => entry.clone(),
+            None => TypeCacheEntry::Relative(ty_cache.insert_relative(symbol_id)),
+        }
+    }
+
+    pub(super) fn callee_signature(ctx: &AnalyzerContext, callee: &Expr) -> Option<(SymbolId, FunctionSignature)> {
+        let ExprKind::Ident(ident_expr) = &callee.item else {
+            return None;
+        };
+
+        let symbol_id = ident_expr.symbol.unwrap_id();
+        let signature = ctx.symbols.borrow().get_signature(symbol_id).cloned()?;
+
+        Some((symbol_id, signature))
+    }
+
+    /// The type a call evaluates to, the function's own entry if its return type is inferred from the body
+    pub(super) fn call_type(ctx: &AnalyzerContext, symbol_id: SymbolId, signature: &FunctionSignature) -> TypeCacheEntry {
+        if let Some(return_type) = &signature.return_type {
+            return TypeCacheEntry::Concrete(return_type.clone());
+        }
+
+        let mut ty_cache = ctx.type_cache.borrow_mut();
+
+        match ty_cache.get(symbol_id) {
+            Some(entry) => entry.clone(),
+            None => TypeCacheEntry::Relative(ty_cache.insert_relative(symbol_id)),
+        }
+    }
+
     pub(super) fn infer_literal_type(
         ctx: &mut AnalyzerContext,
         contextual_type: &TypeCacheEntry,
@@ -226,6 +615,22 @@ impl TypeInference {
     ) -> TypeCacheEntry {
         let contextual_type = contextual_type.as_concrete().filter(|t| !t.is_unit());
 
+        if let LiteralExpr::None = lit {
+            return match contextual_type {
+                Some(ty @ TypeKind::Optional(_)) => TypeCacheEntry::Concrete(ty.clone()),
+                _ => {
+                    ctx.diagnostic(error!(AnalyzerError::UntypedNone).span(span));
+                    TypeCacheEntry::Concrete(TypeKind::Error)
+                }
+            };
+        }
+
+        // present values take the type wrapped by an optional
+        let contextual_type = match contextual_type {
+            Some(TypeKind::Optional(inner)) => Some(&inner.kind),
+            other => other,
+        };
+
         TypeCacheEntry::Concrete(match (lit, contextual_type) {
             // integer literals
             (LiteralExpr::Int(n), Some(TypeKind::UInt8)) if *n <= u8::MAX as u64 => TypeKind::UInt8,
@@ -272,6 +677,7 @@ impl TypeInference {
 
             // unit literals
             (LiteralExpr::Unit, _) => TypeKind::Unit,
+            (LiteralExpr::None, _) => unreachable!("none literals are typed by their context"),
 
             _ => {
                 // type mismatch
@@ -290,3 +696,44 @@ impl TypeInference {
         })
     }
 }
+
+/// The operands of a comparison in the order they are typed, the second is typed by the first.
+///
+/// Comparison operands are typed by each other rather than by the context, as the comparison itself produces a
+/// boolean. A literal takes the type of the other operand, so `0 != x` types the literal like `x != 0` does
+pub(super) fn comparison_operands(binary_expr: &mut BinaryExpr) -> (&mut Expr, &mut Expr) {
+    if types_right_first(binary_expr) {
+        (&mut binary_expr.right, &mut binary_expr.left)
+    } else {
+        (&mut binary_expr.left, &mut binary_expr.right)
+    }
+}
+
+/// Whether [`comparison_operands`] types the right operand first
+pub(super) fn types_right_first(binary_expr: &BinaryExpr) -> bool {
+    is_literal(&binary_expr.left) && !is_literal(&binary_expr.right)
+}
+
+/// Literals, negated or not, are typed by their context
+fn is_literal(expr: &Expr) -> bool {
+    match &expr.item {
+        ExprKind::Literal(_) => true,
+        ExprKind::Unary(unary_expr) => unary_expr.operator.kind != OperatorKind::Not && is_literal(&unary_expr.value),
+        _ => false,
+    }
+}
+
+/// The result of a value and an error
+pub(super) fn result_type(value_type: TypeKind, error_type: TypeKind) -> TypeKind {
+    TypeKind::Result(Box::new(Type::unspanned(value_type)), Box::new(Type::unspanned(error_type)))
+}
+
+/// A reference to a variable of the type
+pub(super) fn pointer_type(ty: TypeKind) -> TypeKind {
+    TypeKind::Ptr(Box::new(Type::unspanned(ty)))
+}
+
+/// The type of `try value`, runtime errors are caught as their message
+pub(super) fn caught_type(value_type: TypeKind) -> TypeKind {
+    result_type(value_type, TypeKind::String)
+}
@@ -2,7 +2,12 @@ use crate::{
     TypeKind,
     ast::*,
     stages::analyzer::{
-        AnalyzerContext, AnalyzerPass, passes::_01_ast::TypeInference, type_cache::TypeCacheEntry,
+        AnalyzerContext, AnalyzerPass,
+        passes::_01_ast::{
+            TypeInference,
+            _04_type_inference::{caught_type, comparison_operands, pointer_type, result_type, types_right_first},
+        },
+        type_cache::TypeCacheEntry,
     },
 };
 
@@ -51,7 +56,10 @@ impl TypeSolving {
                 }
             }
             StmtKind::Return(return_stmt) => todo!(),
-            StmtKind::Struct(struct_decl) => todo!(),
+            // field types are declared, there is nothing to infer
+            StmtKind::Struct(_) => {}
+            // the imported declarations are separate statements
+            StmtKind::Import(_) => {}
             StmtKind::Var(var_decl) => {
                 let symbol_id = var_decl.symbol.unwrap_id();
 
@@ -76,9 +84,19 @@ impl TypeSolving {
         expr: &mut Expr,
     ) -> TypeCacheEntry {
         match &mut expr.item {
+            ExprKind::AddressOf(value) => {
+                let value_type = self.infer_expr(ctx, &TypeCacheEntry::Concrete(TypeKind::Unit), value);
+
+                // anything that can't be referenced was reported during inference
+                match ctx.type_cache.borrow_mut().resolve(&value_type) {
+                    Some(ty) => TypeCacheEntry::Concrete(pointer_type(ty)),
+                    None => TypeCacheEntry::Concrete(TypeKind::Error),
+                }
+            }
             ExprKind::Assign(assign_expr) => {
                 let left_type = self.infer_expr(ctx, contextual_type, &mut assign_expr.target);
-                let right_type = self.infer_expr(ctx, contextual_type, &mut assign_expr.value);
+                // the value is typed by the variable it is assigned to
+                let right_type = self.infer_expr(ctx, &left_type, &mut assign_expr.value);
 
                 if let Err(err) = ctx.type_cache.borrow_mut().unify(&left_type, &right_type) {
                     ctx.diagnostic(err.span(expr.span));
@@ -87,14 +105,49 @@ impl TypeSolving {
                 left_type
             },
             ExprKind::Binary(binary_expr) => {
-                let left_type = self.infer_expr(ctx, contextual_type, &mut binary_expr.left);
-                let right_type = self.infer_expr(ctx, contextual_type, &mut binary_expr.right);
+                let operator = binary_expr.operator.kind.clone();
 
-                if let Err(err) = ctx.type_cache.borrow_mut().unify(&left_type, &right_type) {
+                let (left_type, right_type) = if operator.is_comparison() {
+                    let no_context = TypeCacheEntry::Concrete(TypeKind::Unit);
+                    let (first, second) = comparison_operands(binary_expr);
+
+                    let first_type = self.infer_expr(ctx, &no_context, first);
+                    let second_type = self.infer_expr(ctx, &first_type, second);
+
+                    if types_right_first(binary_expr) {
+                        (second_type, first_type)
+                    } else {
+                        (first_type, second_type)
+                    }
+                } else {
+                    // logic operands are always booleans
+                    let operand_type = if operator.is_logic() {
+                        TypeCacheEntry::Concrete(TypeKind::Bool)
+                    } else {
+                        contextual_type.clone()
+                    };
+
+                    let left_type = self.infer_expr(ctx, &operand_type, &mut binary_expr.left);
+                    let right_type = self.infer_expr(ctx, &operand_type, &mut binary_expr.right);
+
+                    (left_type, right_type)
+                };
+
+                let unified = if matches!(operator, OperatorKind::Equal | OperatorKind::NotEqual) {
+                    ctx.type_cache.borrow_mut().join(&left_type, &right_type).map(|_| ())
+                } else {
+                    ctx.type_cache.borrow_mut().unify(&left_type, &right_type)
+                };
+
+                if let Err(err) = unified {
                     ctx.diagnostic(err.span(binary_expr.operator.span));
                 }
 
-                left_type
+                if operator.is_logic() || operator.is_comparison() {
+                    TypeCacheEntry::Concrete(TypeKind::Bool)
+                } else {
+                    left_type
+                }
             }
             ExprKind::Block(block_expr) => {
                 for stmt in &mut block_expr.statements {
@@ -107,7 +160,59 @@ impl TypeSolving {
                     TypeCacheEntry::Concrete(TypeKind::Unit)
                 }
             }
-            ExprKind::Call(call_expr) => todo!(),
+            ExprKind::Call(call_expr) => {
+                // the callee and the argument count were checked during inference
+                let Some((symbol_id, signature)) = TypeInference::callee_signature(ctx, &call_expr.callee) else {
+                    return TypeCacheEntry::Concrete(TypeKind::Error);
+                };
+
+                for (argument, param) in call_expr.arguments.iter_mut().zip(&signature.params) {
+                    // parameters accepting any displayable value give no context, like comparison operands
+                    let param_type = TypeCacheEntry::Concrete(param.clone().unwrap_or(TypeKind::Unit));
+                    let argument_type = self.infer_expr(ctx, &param_type, argument);
+
+                    // the argument already reported its own error
+                    if param.is_none() || argument_type.as_concrete() == Some(&TypeKind::Error) {
+                        continue;
+                    }
+
+                    if let Err(err) = ctx.type_cache.borrow_mut().unify(&param_type, &argument_type) {
+                        ctx.diagnostic(err.span(argument.span));
+                    }
+                }
+
+                TypeInference::call_type(ctx, symbol_id, &signature)
+            }
+            ExprKind::Catch(catch_expr) => {
+                let result_type = self.infer_expr(
+                    ctx,
+                    &TypeCacheEntry::Concrete(TypeKind::Unit),
+                    &mut catch_expr.value,
+                );
+
+                // the binding was typed during inference, anything that isn't a result was reported then
+                let Some(TypeKind::Result(value_type, _)) = ctx.type_cache.borrow_mut().resolve(&result_type) else {
+                    return TypeCacheEntry::Concrete(TypeKind::Error);
+                };
+
+                let value_type = TypeCacheEntry::Concrete(value_type.kind);
+                let handler_type = self.infer_expr(ctx, &value_type, &mut catch_expr.handler);
+
+                if let Err(err) = ctx.type_cache.borrow_mut().unify(&value_type, &handler_type) {
+                    ctx.diagnostic(err.span(catch_expr.handler.span));
+                }
+
+                value_type
+            }
+            ExprKind::Deref(value) => {
+                let value_type = self.infer_expr(ctx, &TypeCacheEntry::Concrete(TypeKind::Unit), value);
+
+                match ctx.type_cache.borrow_mut().resolve(&value_type) {
+                    Some(TypeKind::Ptr(inner)) => TypeCacheEntry::Concrete(inner.kind),
+                    _ => TypeCacheEntry::Concrete(TypeKind::Error),
+                }
+            }
+            ExprKind::Err(value) => self.infer_result_constructor(ctx, contextual_type, value, false),
             ExprKind::Get(get_expr) => todo!(),
             ExprKind::Group(expr) => self.infer_expr(ctx, contextual_type, expr),
             ExprKind::Ident(ident_expr) => {
@@ -123,19 +228,24 @@ impl TypeSolving {
                     })
             }
             ExprKind::If(if_expr) => {
-                let cond_type = self.infer_expr(
-                    ctx,
-                    &TypeCacheEntry::Concrete(TypeKind::Bool),
-                    &mut if_expr.condition,
-                );
+                if if_expr.binding.is_some() {
+                    // the binding was typed during inference
+                    self.infer_expr(ctx, &TypeCacheEntry::Concrete(TypeKind::Unit), &mut if_expr.condition);
+                } else {
+                    let cond_type = self.infer_expr(
+                        ctx,
+                        &TypeCacheEntry::Concrete(TypeKind::Bool),
+                        &mut if_expr.condition,
+                    );
 
-                if let Err(err) = ctx
-                    .type_cache
-                    .borrow_mut()
-                    .unify(&cond_type, &TypeCacheEntry::Concrete(TypeKind::Bool))
-                {
-                    ctx.diagnostic(err.span(if_expr.condition.span));
-                    return TypeCacheEntry::Concrete(TypeKind::Error);
+                    if let Err(err) = ctx
+                        .type_cache
+                        .borrow_mut()
+                        .unify(&cond_type, &TypeCacheEntry::Concrete(TypeKind::Bool))
+                    {
+                        ctx.diagnostic(err.span(if_expr.condition.span));
+                        return TypeCacheEntry::Concrete(TypeKind::Error);
+                    }
                 }
 
                 let then_type = self.infer_expr(ctx, contextual_type, &mut if_expr.then_branch);
@@ -156,16 +266,40 @@ impl TypeSolving {
                     TypeCacheEntry::Concrete(TypeKind::Unit)
                 };
 
-                if let Err(err) = ctx.type_cache.borrow_mut().unify(&then_type, &else_type) {
-                    ctx.diagnostic(err.span(expr.span));
-                    return TypeCacheEntry::Concrete(TypeKind::Error);
+                match ctx.type_cache.borrow_mut().join(&then_type, &else_type) {
+                    Ok(if_type) => if_type,
+                    Err(err) => {
+                        ctx.diagnostic(err.span(expr.span));
+                        TypeCacheEntry::Concrete(TypeKind::Error)
+                    }
+                }
+            }
+            ExprKind::Interpolation(interpolation_expr) => {
+                let no_context = TypeCacheEntry::Concrete(TypeKind::Unit);
+
+                for part in &mut interpolation_expr.parts {
+                    if let InterpolationPart::Expr(expr) = part {
+                        self.infer_expr(ctx, &no_context, expr);
+                    }
                 }
 
-                then_type
+                TypeCacheEntry::Concrete(TypeKind::String)
             }
             ExprKind::Literal(literal_expr) => {
                 if let TypeCacheEntry::Relative(id) = contextual_type {
-                    if let Some(resolved) = ctx.type_cache.borrow_mut().resolve(contextual_type) {
+                    let resolved = ctx.type_cache.borrow_mut().resolve(contextual_type);
+
+                    // present values take the type wrapped by the optional
+                    if let Some(resolved @ TypeKind::Optional(_)) = resolved {
+                        return TypeInference::infer_literal_type(
+                            ctx,
+                            &TypeCacheEntry::Concrete(resolved),
+                            literal_expr,
+                            expr.span,
+                        );
+                    }
+
+                    if let Some(resolved) = resolved {
                         return TypeCacheEntry::Concrete(resolved);
                     }
 
@@ -174,11 +308,63 @@ impl TypeSolving {
 
                 TypeInference::infer_literal_type(ctx, contextual_type, literal_expr, expr.span)
             }
+            ExprKind::Ok(value) => self.infer_result_constructor(ctx, contextual_type, value, true),
+            ExprKind::Propagate(value) => {
+                let propagated_type = self.infer_expr(ctx, &TypeCacheEntry::Concrete(TypeKind::Unit), value);
+
+                // anything else was reported during inference
+                match ctx.type_cache.borrow_mut().resolve(&propagated_type) {
+                    Some(TypeKind::Optional(inner) | TypeKind::Result(inner, _)) => TypeCacheEntry::Concrete(inner.kind),
+                    _ => TypeCacheEntry::Concrete(TypeKind::Error),
+                }
+            }
             ExprKind::Struct(struct_expr) => todo!(),
+            ExprKind::Try(value) => {
+                let value_type = self.infer_expr(ctx, &TypeCacheEntry::Concrete(TypeKind::Unit), value);
+
+                match ctx.type_cache.borrow_mut().resolve(&value_type) {
+                    Some(TypeKind::Result(value_type, _)) => TypeCacheEntry::Concrete(caught_type(value_type.kind)),
+                    Some(value_type) => TypeCacheEntry::Concrete(caught_type(value_type)),
+                    None => TypeCacheEntry::Concrete(TypeKind::Error),
+                }
+            }
             ExprKind::TupleLiteral(tuple_expr) => todo!(),
             ExprKind::Unary(unary_expr) => {
-                self.infer_expr(ctx, contextual_type, &mut unary_expr.value)
+                if unary_expr.operator.kind == OperatorKind::Not {
+                    let bool_type = TypeCacheEntry::Concrete(TypeKind::Bool);
+                    let value_type = self.infer_expr(ctx, &bool_type, &mut unary_expr.value);
+
+                    if let Err(err) = ctx.type_cache.borrow_mut().unify(&value_type, &bool_type) {
+                        ctx.diagnostic(err.span(unary_expr.value.span));
+                    }
+
+                    bool_type
+                } else {
+                    self.infer_expr(ctx, contextual_type, &mut unary_expr.value)
+                }
             },
         }
     }
+
+    /// Types `ok(..)` and `err(..)` by the result type expected of them, untyped ones were reported during inference
+    fn infer_result_constructor(
+        &self,
+        ctx: &mut AnalyzerContext,
+        contextual_type: &TypeCacheEntry,
+        value: &mut Expr,
+        is_ok: bool,
+    ) -> TypeCacheEntry {
+        let Some((value_type, error_type)) = TypeInference::expected_result(ctx, contextual_type) else {
+            return TypeCacheEntry::Concrete(TypeKind::Error);
+        };
+
+        let expected = TypeCacheEntry::Concrete(if is_ok { value_type.clone() } else { error_type.clone() });
+        let found = self.infer_expr(ctx, &expected, value);
+
+        if let Err(err) = ctx.type_cache.borrow_mut().unify(&expected, &found) {
+            ctx.diagnostic(err.span(value.span));
+        }
+
+        TypeCacheEntry::Concrete(result_type(value_type, error_type))
+    }
 }
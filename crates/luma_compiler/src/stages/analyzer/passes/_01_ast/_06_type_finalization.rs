@@ -4,7 +4,11 @@ use crate::{
     Type, TypeKind,
     ast::*,
     stages::analyzer::{
-        AnalyzerContext, AnalyzerError, AnalyzerPass, passes::_01_ast::TypeInference,
+        AnalyzerContext, AnalyzerError, AnalyzerPass,
+        passes::_01_ast::{
+            TypeInference,
+            _04_type_inference::{caught_type, comparison_operands, pointer_type, result_type},
+        },
         type_cache::TypeCacheEntry,
     },
 };
@@ -38,8 +42,6 @@ impl TypeFinalization {
         match &mut stmt.item {
             StmtKind::Expr(expr) => {
                 self.finalize_expr(ctx, contextual_type, expr);
-
-                dbg!(&expr);
             }
             StmtKind::Func(func_decl) => {
                 let symbol_id = func_decl.symbol.unwrap_id();
@@ -55,7 +57,10 @@ impl TypeFinalization {
                 self.finalize_expr(ctx, &type_entry, &mut func_decl.body);
             }
             StmtKind::Return(return_stmt) => todo!(),
-            StmtKind::Struct(struct_decl) => todo!(),
+            // field types are declared, there is nothing to infer
+            StmtKind::Struct(_) => {}
+            // the imported declarations are separate statements
+            StmtKind::Import(_) => {}
             StmtKind::Var(var_decl) => {
                 let symbol_id = var_decl.symbol.unwrap_id();
 
@@ -95,17 +100,46 @@ impl TypeFinalization {
         expr: &mut Expr,
     ) -> Option<TypeKind> {
         match &mut expr.item {
+            ExprKind::AddressOf(value) => {
+                self.finalize_expr(ctx, &TypeCacheEntry::Concrete(TypeKind::Unit), value);
+                value.ty.clone().map(pointer_type)
+            }
             ExprKind::Assign(assign_expr) => {
                 self.finalize_expr(ctx, contextual_type, &mut assign_expr.target);
-                self.finalize_expr(ctx, contextual_type, &mut assign_expr.value);
+
+                let target_type = TypeCacheEntry::Concrete(assign_expr.target.ty.clone().unwrap_or(TypeKind::Unit));
+                self.finalize_expr(ctx, &target_type, &mut assign_expr.value);
 
                 assign_expr.target.ty.clone()
             },
             ExprKind::Binary(binary_expr) => {
-                self.finalize_expr(ctx, contextual_type, &mut binary_expr.left);
-                self.finalize_expr(ctx, contextual_type, &mut binary_expr.right);
-                
-                binary_expr.left.ty.clone()
+                let operator = binary_expr.operator.kind.clone();
+
+                if operator.is_comparison() {
+                    // the first operand was typed without a context, see `comparison_operands`
+                    let no_context = TypeCacheEntry::Concrete(TypeKind::Unit);
+                    let (first, second) = comparison_operands(binary_expr);
+
+                    self.finalize_expr(ctx, &no_context, first);
+
+                    let second_context = first.ty.clone().map_or(no_context, TypeCacheEntry::Concrete);
+                    self.finalize_expr(ctx, &second_context, second);
+                } else {
+                    let operand_type = if operator.is_logic() {
+                        TypeCacheEntry::Concrete(TypeKind::Bool)
+                    } else {
+                        contextual_type.clone()
+                    };
+
+                    self.finalize_expr(ctx, &operand_type, &mut binary_expr.left);
+                    self.finalize_expr(ctx, &operand_type, &mut binary_expr.right);
+                }
+
+                if operator.is_logic() || operator.is_comparison() {
+                    Some(TypeKind::Bool)
+                } else {
+                    binary_expr.left.ty.clone()
+                }
             }
             ExprKind::Block(block_expr) => {
                 for stmt in &mut block_expr.statements {
@@ -119,7 +153,47 @@ impl TypeFinalization {
                     Some(TypeKind::Unit)
                 }
             }
-            ExprKind::Call(call_expr) => todo!(),
+            ExprKind::Call(call_expr) => {
+                let (symbol_id, signature) = TypeInference::callee_signature(ctx, &call_expr.callee)?;
+
+                for (argument, param) in call_expr.arguments.iter_mut().zip(&signature.params) {
+                    self.finalize_expr(ctx, &TypeCacheEntry::Concrete(param.clone().unwrap_or(TypeKind::Unit)), argument);
+
+                    if param.is_none()
+                        && let Some(ty) = &argument.ty
+                        && !ty.is_displayable()
+                    {
+                        ctx.diagnostic(not_displayable(ty).span(argument.span));
+                    }
+                }
+
+                // the callee is typed by what the function returns, as there are no function types yet
+                self.finalize_expr(ctx, contextual_type, &mut call_expr.callee);
+
+                let call_type = TypeInference::call_type(ctx, symbol_id, &signature);
+                ctx.type_cache.borrow_mut().resolve(&call_type)
+            }
+            ExprKind::Catch(catch_expr) => {
+                self.finalize_expr(ctx, &TypeCacheEntry::Concrete(TypeKind::Unit), &mut catch_expr.value);
+
+                let value_type = match &catch_expr.value.ty {
+                    Some(TypeKind::Result(value_type, _)) => value_type.kind.clone(),
+                    _ => TypeKind::Error,
+                };
+
+                self.finalize_expr(ctx, &TypeCacheEntry::Concrete(value_type.clone()), &mut catch_expr.handler);
+
+                Some(value_type)
+            }
+            ExprKind::Deref(value) => {
+                self.finalize_expr(ctx, &TypeCacheEntry::Concrete(TypeKind::Unit), value);
+
+                match &value.ty {
+                    Some(TypeKind::Ptr(inner)) => Some(inner.kind.clone()),
+                    _ => None,
+                }
+            }
+            ExprKind::Err(value) => self.finalize_result_constructor(ctx, contextual_type, value, false),
             ExprKind::Get(get_expr) => todo!(),
             ExprKind::Group(expr) => {
                 self.finalize_expr(ctx, contextual_type, expr);
@@ -137,33 +211,120 @@ impl TypeFinalization {
                 }
             }
             ExprKind::If(if_expr) => {
-                self.finalize_expr(ctx, &TypeCacheEntry::Concrete(TypeKind::Bool), &mut if_expr.condition);
+                let condition_type = if if_expr.binding.is_some() {
+                    TypeKind::Unit
+                } else {
+                    TypeKind::Bool
+                };
+
+                self.finalize_expr(ctx, &TypeCacheEntry::Concrete(condition_type), &mut if_expr.condition);
                 self.finalize_expr(ctx, contextual_type, &mut if_expr.then_branch);
 
                 if let Some(else_branch) = &mut if_expr.else_branch {
                     self.finalize_expr(ctx, contextual_type, else_branch);
                 }
 
-                if_expr.then_branch.ty.clone()
+                // a present value in one branch and none in the other make the whole if optional
+                match (&if_expr.then_branch.ty, if_expr.else_branch.as_ref().and_then(|branch| branch.ty.as_ref())) {
+                    (Some(then_type), Some(else_type @ TypeKind::Optional(inner))) if inner.kind == *then_type => {
+                        Some(else_type.clone())
+                    }
+                    _ => if_expr.then_branch.ty.clone(),
+                }
             },
+            ExprKind::Interpolation(interpolation_expr) => {
+                let no_context = TypeCacheEntry::Concrete(TypeKind::Unit);
+
+                for part in &mut interpolation_expr.parts {
+                    if let InterpolationPart::Expr(expr) = part {
+                        self.finalize_expr(ctx, &no_context, expr);
+
+                        if let Some(ty) = &expr.ty
+                            && !ty.is_displayable()
+                        {
+                            ctx.diagnostic(not_displayable(ty).span(expr.span));
+                        }
+                    }
+                }
+
+                Some(TypeKind::String)
+            }
             ExprKind::Literal(literal_expr) => {
-                let entry = if let TypeCacheEntry::Relative(id) = contextual_type
-                    && let Some(resolved) = ctx.type_cache.borrow_mut().resolve(contextual_type)
-                {
-                    TypeCacheEntry::Concrete(resolved)
-                } else {
-                    TypeInference::infer_literal_type(ctx, contextual_type, literal_expr, expr.span)
+                let resolved = match contextual_type {
+                    TypeCacheEntry::Relative(_) => ctx.type_cache.borrow_mut().resolve(contextual_type),
+                    TypeCacheEntry::Concrete(_) => None,
+                };
+
+                let entry = match resolved {
+                    // present values take the type wrapped by the optional
+                    Some(resolved @ TypeKind::Optional(_)) => TypeInference::infer_literal_type(
+                        ctx,
+                        &TypeCacheEntry::Concrete(resolved),
+                        literal_expr,
+                        expr.span,
+                    ),
+                    Some(resolved) => TypeCacheEntry::Concrete(resolved),
+                    None => TypeInference::infer_literal_type(ctx, contextual_type, literal_expr, expr.span),
                 };
 
                 entry.as_concrete().cloned()
             }
+            ExprKind::Ok(value) => self.finalize_result_constructor(ctx, contextual_type, value, true),
+            ExprKind::Propagate(value) => {
+                self.finalize_expr(ctx, &TypeCacheEntry::Concrete(TypeKind::Unit), value);
+
+                match &value.ty {
+                    Some(TypeKind::Optional(inner) | TypeKind::Result(inner, _)) => Some(inner.kind.clone()),
+                    other => other.clone(),
+                }
+            }
             ExprKind::Struct(struct_expr) => todo!(),
+            ExprKind::Try(value) => {
+                self.finalize_expr(ctx, &TypeCacheEntry::Concrete(TypeKind::Unit), value);
+
+                match &value.ty {
+                    Some(TypeKind::Result(value_type, _)) => Some(caught_type(value_type.kind.clone())),
+                    other => other.clone().map(caught_type),
+                }
+            }
             ExprKind::TupleLiteral(tuple_expr) => todo!(),
             ExprKind::Unary(unary_expr) => {
-                self.finalize_expr(ctx, contextual_type, &mut unary_expr.value);
-                
+                if unary_expr.operator.kind == OperatorKind::Not {
+                    let bool_type = TypeCacheEntry::Concrete(TypeKind::Bool);
+                    self.finalize_expr(ctx, &bool_type, &mut unary_expr.value);
+                } else {
+                    self.finalize_expr(ctx, contextual_type, &mut unary_expr.value);
+                }
+
                 unary_expr.value.ty.clone()
             },
         }
     }
 }
+
+impl TypeFinalization {
+    /// Finalizes the value of `ok(..)` or `err(..)` with the part of the expected result it provides
+    fn finalize_result_constructor(
+        &self,
+        ctx: &mut AnalyzerContext,
+        contextual_type: &TypeCacheEntry,
+        value: &mut Expr,
+        is_ok: bool,
+    ) -> Option<TypeKind> {
+        let (value_type, error_type) = TypeInference::expected_result(ctx, contextual_type)?;
+        let expected = if is_ok { value_type.clone() } else { error_type.clone() };
+
+        self.finalize_expr(ctx, &TypeCacheEntry::Concrete(expected), value);
+
+        Some(result_type(value_type, error_type))
+    }
+}
+
+/// Optionals aren't displayable until they are unwrapped, which is the more helpful error for them
+fn not_displayable(ty: &TypeKind) -> luma_diagnostic::Diagnostic {
+    if ty.is_optional() {
+        error!(AnalyzerError::UnwrappedOptional { ty: ty.clone() })
+    } else {
+        error!(AnalyzerError::NotDisplayable { ty: ty.clone() })
+    }
+}
@@ -0,0 +1,323 @@
+use std::collections::{HashMap, HashSet};
+
+use luma_core::Span;
+use luma_diagnostic::warning;
+
+use crate::{
+    SymbolId,
+    ast::*,
+    lints::{UNUSED_FIELDS, UNUSED_FUNCTIONS, UNUSED_IMPORTS, UNUSED_PARAMETERS, UNUSED_VARIABLES},
+    stages::analyzer::{AnalyzerContext, AnalyzerError, AnalyzerPass},
+};
+
+/// Warns about locals, parameters, private functions, struct fields and imports that are never used.
+///
+/// Private functions are used when they are reachable from the public functions or the code outside of functions,
+/// so functions only calling each other are still reported.
+///
+/// Names starting with `_` and everything declared in a statement allowing the lint, like `@allow(unused)`, are skipped,
+/// declarations merged in from imported libraries are skipped
+pub struct UsageAnalysis;
+
+impl AnalyzerPass<Ast> for UsageAnalysis {
+    fn name(&self) -> String {
+        String::from("usage_analysis")
+    }
+
+    fn analyze(&self, ctx: &mut AnalyzerContext, input: &mut Ast) {
+        let source_id = input.span.source_id;
+        let mut usage = Usage::default();
+
+        for stmt in &mut input.statements {
+            match &stmt.item {
+                // globals are part of the module's state, they may be used by the host
+                StmtKind::Var(var_decl) => {
+                    usage.globals.insert(var_decl.symbol.unwrap_id());
+                }
+                // functions may be called before their declaration, they are known up front
+                StmtKind::Func(func_decl) => {
+                    usage.functions.insert(func_decl.symbol.unwrap_id());
+                }
+                _ => {}
+            }
+        }
+
+        for stmt in input.statements.iter_mut().filter(|stmt| stmt.span.source_id == source_id) {
+            UsageCollector.walk_stmt(&mut usage, stmt);
+        }
+
+        // silenced functions are kept on purpose, and so are the functions they call
+        let silenced = usage
+            .declarations
+            .iter()
+            .filter(|declaration| matches!(declaration.kind, DeclarationKind::Function))
+            .filter(|declaration| declaration.silenced || declaration.name.starts_with('_'))
+            .map(|declaration| declaration.id)
+            .collect::<Vec<_>>();
+        usage.entry_points.extend(silenced);
+
+        let reachable = usage.reachable_functions();
+
+        for declaration in &usage.declarations {
+            let used = match declaration.kind {
+                DeclarationKind::Function => reachable.contains(&declaration.id),
+                DeclarationKind::Variable | DeclarationKind::Parameter => usage.reads(declaration.id) > 0,
+            };
+
+            if used || declaration.silenced || declaration.name.starts_with('_') {
+                continue;
+            }
+
+            let name = declaration.name.clone();
+            let warning = match declaration.kind {
+                DeclarationKind::Variable => AnalyzerError::UnusedVariable { name },
+                DeclarationKind::Parameter => AnalyzerError::UnusedParameter { name },
+                DeclarationKind::Function => AnalyzerError::UnusedFunction { name },
+            };
+
+            ctx.diagnostic(warning!(warning, declaration.span).lint(declaration.kind.lint()));
+        }
+
+        for field in &usage.fields {
+            if field.silenced || field.name.starts_with('_') || usage.read_fields.contains(&field.name) {
+                continue;
+            }
+
+            ctx.diagnostic(
+                warning!(
+                    AnalyzerError::UnusedField {
+                        name: field.name.clone(),
+                        struct_name: field.struct_name.clone(),
+                    },
+                    field.span,
+                )
+                .lint(UNUSED_FIELDS),
+            );
+        }
+
+        self.check_imports(ctx, &usage, input);
+    }
+}
+
+impl UsageAnalysis {
+    /// Reports the imports of the module none of whose declarations are used.
+    ///
+    /// The resolver places the declarations of a library right before its import
+    fn check_imports(&self, ctx: &mut AnalyzerContext, usage: &Usage, input: &Ast) {
+        let source_id = input.span.source_id;
+        let mut imported = Vec::new();
+
+        for stmt in &input.statements {
+            if stmt.span.source_id != source_id {
+                imported.extend(declared_symbol(stmt));
+                continue;
+            }
+
+            let declarations = std::mem::take(&mut imported);
+
+            let StmtKind::Import(import) = &stmt.item else {
+                continue;
+            };
+
+            if stmt.allows(UNUSED_IMPORTS) || declarations.iter().any(|&id| usage.reads(id) > 0) {
+                continue;
+            }
+
+            ctx.diagnostic(warning!(AnalyzerError::UnusedImport { module: import.module_name() }, stmt.span).lint(UNUSED_IMPORTS));
+        }
+    }
+}
+
+/// The symbol a top-level statement declares, struct types are only checked by their fields
+fn declared_symbol(stmt: &Stmt) -> Option<SymbolId> {
+    match &stmt.item {
+        StmtKind::Func(func_decl) => func_decl.symbol.id(),
+        StmtKind::Var(var_decl) => var_decl.symbol.id(),
+        StmtKind::Struct(struct_decl) => struct_decl.symbol.id(),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Copy)]
+enum DeclarationKind {
+    Variable,
+    Parameter,
+    Function,
+}
+
+impl DeclarationKind {
+    fn lint(self) -> &'static str {
+        match self {
+            DeclarationKind::Variable => UNUSED_VARIABLES,
+            DeclarationKind::Parameter => UNUSED_PARAMETERS,
+            DeclarationKind::Function => UNUSED_FUNCTIONS,
+        }
+    }
+}
+
+struct Declaration {
+    kind: DeclarationKind,
+    id: SymbolId,
+    name: String,
+    span: Span,
+    silenced: bool,
+}
+
+struct Field {
+    name: String,
+    struct_name: String,
+    span: Span,
+    silenced: bool,
+}
+
+#[derive(Default)]
+struct Usage {
+    globals: HashSet<SymbolId>,
+    functions: HashSet<SymbolId>,
+    declarations: Vec<Declaration>,
+    fields: Vec<Field>,
+
+    /// occurrences of every identifier, as values, callees or assignment targets
+    idents: HashMap<SymbolId, usize>,
+    assignments: HashMap<SymbolId, usize>,
+    read_fields: HashSet<String>,
+
+    /// the functions referenced in the body of each function
+    callees: HashMap<SymbolId, HashSet<SymbolId>>,
+    /// public functions and the functions referenced outside of any function
+    entry_points: HashSet<SymbolId>,
+
+    /// functions being walked, calls to them from their own body don't count as uses
+    enclosing: Vec<SymbolId>,
+    /// attributes of the statements being walked
+    attributes: Vec<Vec<Attribute>>,
+}
+
+impl Usage {
+    /// How often the symbol is read, assignments only write it
+    fn reads(&self, id: SymbolId) -> usize {
+        let idents = self.idents.get(&id).copied().unwrap_or(0);
+        idents.saturating_sub(self.assignments.get(&id).copied().unwrap_or(0))
+    }
+
+    /// The functions reachable from the entry points
+    fn reachable_functions(&self) -> HashSet<SymbolId> {
+        let mut reachable = HashSet::new();
+        let mut pending = self.entry_points.iter().copied().collect::<Vec<_>>();
+
+        while let Some(id) = pending.pop() {
+            if reachable.insert(id) {
+                pending.extend(self.callees.get(&id).into_iter().flatten().copied());
+            }
+        }
+
+        reachable
+    }
+
+    /// Whether a statement being walked allows the lint
+    fn allows(&self, lint: &str) -> bool {
+        self.attributes.iter().flatten().any(|attribute| attribute.allows(lint))
+    }
+
+    fn declare(&mut self, kind: DeclarationKind, symbol: &Symbol) {
+        self.declarations.push(Declaration {
+            kind,
+            id: symbol.unwrap_id(),
+            name: symbol.name().to_string(),
+            span: symbol.span,
+            silenced: self.allows(kind.lint()),
+        });
+    }
+}
+
+/// Records the declarations and uses of the module's own statements
+struct UsageCollector;
+
+impl AstVisitor<'_> for UsageCollector {
+    type Ctx = Usage;
+
+    fn visit_stmt(&self, ctx: &mut Self::Ctx, stmt: &mut Stmt) {
+        ctx.attributes.push(stmt.attributes.clone());
+
+        match &stmt.item {
+            StmtKind::Func(func_decl) => {
+                if func_decl.visibility.kind.is_public() {
+                    ctx.entry_points.insert(func_decl.symbol.unwrap_id());
+                } else {
+                    ctx.declare(DeclarationKind::Function, &func_decl.symbol);
+                }
+
+                ctx.enclosing.push(func_decl.symbol.unwrap_id());
+            }
+            StmtKind::Struct(struct_decl) => {
+                let fields = struct_decl
+                    .fields
+                    .iter()
+                    .filter(|field| !field.visibility.kind.is_public())
+                    .map(|field| Field {
+                        name: field.symbol.name().to_string(),
+                        struct_name: struct_decl.symbol.name().to_string(),
+                        span: field.symbol.span,
+                        silenced: ctx.allows(UNUSED_FIELDS),
+                    })
+                    .collect::<Vec<_>>();
+
+                ctx.fields.extend(fields);
+            }
+            StmtKind::Var(var_decl) if !ctx.globals.contains(&var_decl.symbol.unwrap_id()) => {
+                ctx.declare(DeclarationKind::Variable, &var_decl.symbol);
+            }
+            _ => {}
+        }
+    }
+
+    fn leave_stmt(&self, ctx: &mut Self::Ctx, stmt: &mut Stmt) {
+        if let StmtKind::Func(_) = &stmt.item {
+            ctx.enclosing.pop();
+        }
+
+        ctx.attributes.pop();
+    }
+
+    fn visit_expr(&self, ctx: &mut Self::Ctx, expr: &mut Expr) {
+        match &expr.item {
+            ExprKind::Assign(assign_expr) => {
+                if let ExprKind::Ident(ident_expr) = &assign_expr.target.item {
+                    *ctx.assignments.entry(ident_expr.symbol.unwrap_id()).or_default() += 1;
+                }
+            }
+            ExprKind::Get(get_expr) => {
+                ctx.read_fields.insert(get_expr.property.name().to_string());
+            }
+            ExprKind::Ident(ident_expr) => {
+                let id = ident_expr.symbol.unwrap_id();
+
+                let is_function = ctx.functions.contains(&id);
+
+                if !(is_function && ctx.enclosing.contains(&id)) {
+                    *ctx.idents.entry(id).or_default() += 1;
+                }
+
+                if is_function {
+                    match ctx.enclosing.last() {
+                        Some(caller) => {
+                            ctx.callees.entry(*caller).or_default().insert(id);
+                        }
+                        None => {
+                            ctx.entry_points.insert(id);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn visit_func_param<'node>(&self, ctx: &mut Self::Ctx, _func: &'node FuncDeclStmt, param: &'node mut FuncParam) {
+        ctx.declare(DeclarationKind::Parameter, &param.symbol);
+    }
+
+    fn visit_binding(&self, ctx: &mut Self::Ctx, binding: &mut Binding) {
+        ctx.declare(DeclarationKind::Variable, &binding.symbol);
+    }
+}
@@ -0,0 +1,57 @@
+use std::rc::Rc;
+
+use luma_core::Span;
+
+use crate::{
+    ast::*,
+    lints::{AstLint, LintRegistry, lint_module},
+    stages::analyzer::{AnalyzerContext, AnalyzerPass},
+};
+
+/// Runs the lints registered with [`crate::LumaCompiler::with_lint`].
+///
+/// Only the statements of the module itself are linted, the statements merged in from imported libraries are skipped
+pub struct RegisteredLints {
+    lints: Vec<Rc<dyn AstLint>>,
+}
+
+impl RegisteredLints {
+    pub fn new(registry: &LintRegistry) -> Self {
+        Self {
+            lints: registry.ast.clone(),
+        }
+    }
+}
+
+impl AnalyzerPass<Ast> for RegisteredLints {
+    fn name(&self) -> String {
+        String::from("registered_lints")
+    }
+
+    fn analyze(&self, ctx: &mut AnalyzerContext, input: &mut Ast) {
+        for lint in &self.lints {
+            let name = lint.info().name;
+            let reported = lint_module(name, input.span, &mut input.statements, |lint_ctx, allowed, stmt| {
+                lint.walk_stmt(lint_ctx, stmt);
+                AllowedSpans { lint: name }.walk_stmt(allowed, stmt);
+            });
+
+            ctx.diagnostics.borrow_mut().extend(reported);
+        }
+    }
+}
+
+/// Collects the spans of the statements allowing a lint
+struct AllowedSpans {
+    lint: &'static str,
+}
+
+impl AstVisitor<'_> for AllowedSpans {
+    type Ctx = Vec<Span>;
+
+    fn visit_stmt(&self, ctx: &mut Self::Ctx, stmt: &mut Stmt) {
+        if stmt.allows(self.lint) {
+            ctx.push(stmt.span);
+        }
+    }
+}
@@ -4,6 +4,8 @@ mod _03_name_resolution;
 mod _04_type_inference;
 mod _05_type_solving;
 mod _06_type_finalization;
+mod _07_usage_analysis;
+mod _08_registered_lints;
 
 pub use _01_scope_identification::ScopeIdentification;
 pub use _02_name_declaration::NameDeclaration;
@@ -11,6 +13,8 @@ pub use _03_name_resolution::NameResolution;
 pub use _04_type_inference::TypeInference;
 pub use _05_type_solving::TypeSolving;
 pub use _06_type_finalization::TypeFinalization;
+pub use _07_usage_analysis::UsageAnalysis;
+pub use _08_registered_lints::RegisteredLints;
 
 #[cfg(test)]
 pub mod tests;
@@ -25,6 +29,7 @@ pub fn default_ast_passes() -> Vec<Box<dyn AnalyzerPass<Ast>>> {
         Box::new(TypeInference),
         Box::new(TypeSolving),
         Box::new(TypeFinalization),
+        Box::new(UsageAnalysis),
     ]
 }
 
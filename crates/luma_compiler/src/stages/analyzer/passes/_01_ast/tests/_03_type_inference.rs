@@ -99,3 +99,96 @@ fn func_type_inference() {
         Some(TypeKind::Int8)
     );
 }
+
+#[test]
+fn comparison_and_logic_inference() {
+    let ast = analyze_source(r#"
+        var x: u8 = 2;
+        var ok = x != 0 && !(x > 10);
+    "#).expect("failed to analyze source");
+
+    extract_stmt!(
+        StmtKind::Var(VarDeclStmt {
+            initializer, ty: var_ty, ..
+        }) = ast[1]
+    );
+
+    assert_eq!(
+        var_ty.as_ref().expect("variable type should be inferred").kind,
+        TypeKind::Bool
+    );
+
+    let ExprKind::Binary(and_expr) = &initializer.item else {
+        panic!("expected initializer to be a binary expression");
+    };
+
+    let ExprKind::Binary(comparison) = &and_expr.left.item else {
+        panic!("expected left operand to be a comparison");
+    };
+
+    // the literal is typed by the other operand rather than by the boolean context
+    assert_eq!(
+        comparison.right.ty,
+        Some(TypeKind::UInt8)
+    );
+
+    assert_eq!(
+        and_expr.left.ty,
+        Some(TypeKind::Bool)
+    );
+}
+
+#[test]
+fn literals_on_the_left_of_a_comparison() {
+    let ast = analyze_source(r#"
+        var x: u8 = 2;
+        var ok = 0 != x && 10 > x;
+    "#).expect("failed to analyze source");
+
+    extract_stmt!(
+        StmtKind::Var(VarDeclStmt { initializer, .. }) = ast[1]
+    );
+
+    let ExprKind::Binary(and_expr) = &initializer.item else {
+        panic!("expected initializer to be a binary expression");
+    };
+
+    // the literal is typed by the right operand like it is by the left one
+    for operand in [&and_expr.left, &and_expr.right] {
+        let ExprKind::Binary(comparison) = &operand.item else {
+            panic!("expected the operands to be comparisons");
+        };
+
+        assert_eq!(
+            comparison.left.ty,
+            Some(TypeKind::UInt8)
+        );
+    }
+}
+
+#[test]
+fn parameters_have_their_declared_type() {
+    let ast = analyze_source(r#"
+        func f(a: u8) {
+            var b = a;
+        };
+    "#).expect("failed to analyze source");
+
+    extract_stmt!(
+        StmtKind::Func(FuncDeclStmt { body, .. }) = ast[0]
+    );
+
+    let ExprKind::Block(block) = &body.item else {
+        panic!("expected the function body to be a block");
+    };
+
+    let StmtKind::Var(VarDeclStmt { ty: var_ty, .. }) = &block.statements[0].item else {
+        panic!("expected a variable declaration");
+    };
+
+    // the parameter's annotation types every use of it in the body
+    assert_eq!(
+        var_ty.as_ref().expect("variable type should be inferred").kind,
+        TypeKind::UInt8
+    );
+}
@@ -0,0 +1,48 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    LumaCompiler, NativeSignature, TypeKind, stages::analyzer::passes::_01_ast::tests::compile_errors,
+};
+
+/// Compiles the source with a `clamp(i32, i32): i32` native, returning the titles of the reported errors
+fn diagnostics(src: &str) -> Vec<String> {
+    let clamp = NativeSignature::new("clamp", [TypeKind::Int32, TypeKind::Int32], TypeKind::Int32);
+
+    compile_errors(LumaCompiler::new().with_natives([clamp]), src)
+}
+
+#[test]
+fn native_calls_type_check() {
+    assert_eq!(diagnostics("var x: i32 = clamp(1, 2);"), Vec::<String>::new());
+    assert_eq!(diagnostics("var x: bool = clamp(1, 2);"), vec!["type mismatch"]);
+    assert_eq!(diagnostics("var x = clamp(1, true);"), vec!["literal type mismatch"]);
+    assert_eq!(diagnostics("var x = clamp(1);"), vec!["argument count mismatch"]);
+}
+
+#[test]
+fn function_calls_type_check() {
+    assert_eq!(diagnostics("func f(a: i64): i64 { a }; var x: i64 = f(1);"), Vec::<String>::new());
+    assert_eq!(diagnostics("func f(a: i64): i64 { a }; var x = f(1, 2);"), vec!["argument count mismatch"]);
+    assert_eq!(diagnostics("var y = 1; var x = y(1);"), vec!["not callable"]);
+}
+
+#[test]
+fn declarations_shadow_natives() {
+    assert_eq!(diagnostics("func clamp(a: bool): bool { a }; var x: bool = clamp(true);"), Vec::<String>::new());
+}
+
+#[test]
+fn intrinsics_type_check() {
+    assert_eq!(diagnostics("var n: i32 = len(\"abc\"); var s: str = slice(\"abc\", 0, n);"), Vec::<String>::new());
+    assert_eq!(diagnostics("var s: str = to_string(1.5); var t: str = to_string('c');"), Vec::<String>::new());
+    assert_eq!(diagnostics("var n: i64 = parse_int(\"12\") + 1;"), Vec::<String>::new());
+    assert_eq!(diagnostics("var n = len(1);"), vec!["literal type mismatch"]);
+    assert_eq!(diagnostics("var s = to_string(());"), vec!["not displayable"]);
+}
+
+#[test]
+fn interpolated_values_must_be_displayable() {
+    assert_eq!(diagnostics("var n = 2; var s: str = \"{n} and {clamp(n, true)}\";"), vec!["literal type mismatch"]);
+    assert_eq!(diagnostics("var n = 2; var s: str = \"{n}: {clamp(n, 3)} {true}\";"), Vec::<String>::new());
+    assert_eq!(diagnostics("var s = \"unit {()}\";"), vec!["not displayable"]);
+}
@@ -0,0 +1,51 @@
+use pretty_assertions::assert_eq;
+
+use crate::stages::analyzer::passes::_01_ast::tests::diagnostics;
+
+const MAYBE: &str = "func maybe(x: i32): i32? { if x > 0 { x } else { none } };";
+
+#[test]
+fn present_values_and_none_are_optionals() {
+    assert_eq!(diagnostics("var x: i32? = 1; var y: i32? = none; var z: str? = \"a\";"), Vec::<String>::new());
+    assert_eq!(diagnostics(MAYBE), Vec::<String>::new());
+    assert_eq!(diagnostics(&format!("{MAYBE} var y: i32? = maybe(1);")), Vec::<String>::new());
+    assert_eq!(diagnostics("var x: i32? = 1; var y: bool? = x;"), vec!["type mismatch"]);
+    assert_eq!(diagnostics("var x = none;"), vec!["untyped none"]);
+}
+
+#[test]
+fn optionals_must_be_unwrapped_before_use() {
+    let check = |src: &str| diagnostics(&format!("{MAYBE} {src}"));
+
+    assert_eq!(check("var y = maybe(1) + 1;"), vec!["optional used without unwrapping"]);
+    assert_eq!(check("var y = 1 + maybe(1);"), vec!["optional used without unwrapping"]);
+    assert_eq!(check("var y: i32 = maybe(1);"), vec!["optional used without unwrapping"]);
+    assert_eq!(check("var y = -maybe(1);"), vec!["optional used without unwrapping"]);
+    assert_eq!(check("var y = \"{maybe(1)}\";"), vec!["optional used without unwrapping"]);
+    assert_eq!(check("func f(b: bool?): bool { !b };"), vec!["optional used without unwrapping"]);
+    assert_eq!(check("var y = maybe(1) == none; var z = maybe(1) != 2; var w = 2 == maybe(1);"), Vec::<String>::new());
+
+    // the expression is reported once, not again where its value is used
+    assert_eq!(check("func f(x: i32?): i32 { x + 1 };"), vec!["optional used without unwrapping"]);
+    assert_eq!(check("func f(x: i32?): i32 { -x };"), vec!["optional used without unwrapping"]);
+    assert_eq!(check("var y: i32 = maybe(1) + 1;"), vec!["optional used without unwrapping"]);
+}
+
+#[test]
+fn if_var_binds_the_unwrapped_value() {
+    let check = |src: &str| diagnostics(&format!("{MAYBE} {src}"));
+
+    assert_eq!(check("var y: i32 = if var x = maybe(1) { x + 1 } else { 0 };"), Vec::<String>::new());
+    assert_eq!(check("var y = if var x = 1 { x } else { 0 };"), vec!["not an optional"]);
+    assert_eq!(check("var y = if var x = maybe(1) { x } else { 0 }; var z = x;"), vec!["unresolved identifier"]);
+}
+
+#[test]
+fn question_mark_needs_an_optional_function() {
+    let check = |src: &str| diagnostics(&format!("{MAYBE} {src}"));
+
+    assert_eq!(check("func f(a: i32): i32? { maybe(a)? + 1 };"), Vec::<String>::new());
+    assert_eq!(check("func f(a: i32): i32 { maybe(a)? + 1 };"), vec!["'?' outside of an optional function"]);
+    assert_eq!(check("var y = maybe(1)?;"), vec!["'?' outside of an optional function"]);
+    assert_eq!(check("func f(a: i32): i32? { a? };"), vec!["nothing to propagate"]);
+}
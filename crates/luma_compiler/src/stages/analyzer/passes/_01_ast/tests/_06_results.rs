@@ -0,0 +1,44 @@
+use pretty_assertions::assert_eq;
+
+use crate::stages::analyzer::passes::_01_ast::tests::diagnostics;
+
+const CHECKED: &str = "func checked(x: i32): i32!str { if x > 0 { ok(x) } else { err(\"negative\") } };";
+
+#[test]
+fn ok_and_err_construct_results() {
+    assert_eq!(diagnostics(CHECKED), Vec::<String>::new());
+    assert_eq!(diagnostics("var x: i32!str = ok(1); var y: i32!str = err(\"no\");"), Vec::<String>::new());
+    assert_eq!(diagnostics("var x: i32!str = err(1);"), vec!["literal type mismatch"]);
+    assert_eq!(diagnostics("var x = ok(1);"), vec!["untyped result"]);
+    assert_eq!(diagnostics("var ok = 1; var err = ok + 1;"), Vec::<String>::new());
+    assert_eq!(diagnostics("var x: i32!str!bool = ok(1);"), vec!["nested result type"]);
+}
+
+#[test]
+fn results_must_be_handled_before_use() {
+    let check = |src: &str| diagnostics(&format!("{CHECKED} {src}"));
+
+    assert_eq!(check("var y: i32 = checked(1) catch e { 0 };"), Vec::<String>::new());
+    assert_eq!(check("var y: i32 = checked(1) catch e { e };"), vec!["type mismatch"]);
+    assert_eq!(check("var y = 1 catch e { 0 };"), vec!["not a result"]);
+    assert_eq!(check("var y: i32 = checked(1);"), vec!["type mismatch"]);
+}
+
+#[test]
+fn question_mark_needs_a_result_function_with_the_same_errors() {
+    let check = |src: &str| diagnostics(&format!("{CHECKED} {src}"));
+
+    assert_eq!(check("func f(a: i32): i32!str { ok(checked(a)? + 1) };"), Vec::<String>::new());
+    assert_eq!(check("func f(a: i32): i32 { checked(a)? };"), vec!["'?' outside of a fallible function"]);
+    assert_eq!(check("func f(a: i32): i32!bool { ok(checked(a)?) };"), vec!["error type mismatch"]);
+}
+
+#[test]
+fn try_catches_runtime_errors_as_strings() {
+    let check = |src: &str| diagnostics(&format!("{CHECKED} {src}"));
+
+    assert_eq!(check("func f(a: i32): i32!str { try 10 / a };"), Vec::<String>::new());
+    assert_eq!(check("func f(a: i32): i32 { try 10 / a catch e { 0 } };"), Vec::<String>::new());
+    assert_eq!(check("func f(a: i32): i32!str { try checked(a) };"), Vec::<String>::new());
+    assert_eq!(check("func f(a: i32): i32!bool { try 10 / a };"), vec!["type mismatch"]);
+}
@@ -0,0 +1,30 @@
+use pretty_assertions::assert_eq;
+
+use crate::stages::analyzer::passes::_01_ast::tests::diagnostics;
+
+#[test]
+fn references_are_taken_and_followed() {
+    assert_eq!(diagnostics("var x = 1; var p = &x; var y: i32 = *p; *p = y * 2;"), Vec::<String>::new());
+    assert_eq!(diagnostics("func inc(p: *i32) { *p = *p + 1; }; var x = 1; inc(&x);"), Vec::<String>::new());
+    assert_eq!(diagnostics("var x = 1; var p: *bool = &x;"), vec!["type mismatch"]);
+    assert_eq!(diagnostics("var p = &1;"), vec!["not addressable"]);
+    assert_eq!(diagnostics("var x = 1; var y = *x;"), vec!["not a reference"]);
+    assert_eq!(diagnostics("var x = 1; var p = &x; var q = &p;"), vec!["reference to a reference"]);
+}
+
+#[test]
+fn binary_operators_are_not_references() {
+    assert_eq!(diagnostics("var x = 6; var y = x * 2;"), Vec::<String>::new());
+    assert_eq!(diagnostics("var x = 6; var p = &x; var y = *p * *p;"), Vec::<String>::new());
+}
+
+#[test]
+fn references_cannot_outlive_their_variable() {
+    assert_eq!(diagnostics("func f(): *i32 { var x = 1; &x };"), vec!["escaping reference"]);
+    assert_eq!(diagnostics("func f(x: i32): *i32 { &x };"), vec!["escaping reference"]);
+    assert_eq!(diagnostics("func f(p: *i32): *i32 { p };"), Vec::<String>::new());
+    assert_eq!(diagnostics("var p = { var y = 1; &y };"), vec!["escaping reference"]);
+    assert_eq!(diagnostics("var x = 1; var p = &x; { var y = 2; p = &y; };"), vec!["escaping reference"]);
+    assert_eq!(diagnostics("var x = 1; { var p = &x; var y: i32 = *p; };"), Vec::<String>::new());
+    assert_eq!(diagnostics("func f(p: *i32) { var x = 1; *p = x; };"), Vec::<String>::new());
+}
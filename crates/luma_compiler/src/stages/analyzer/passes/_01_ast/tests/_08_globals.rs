@@ -0,0 +1,23 @@
+use pretty_assertions::assert_eq;
+
+use crate::stages::analyzer::passes::_01_ast::tests::diagnostics;
+
+#[test]
+fn functions_use_globals() {
+    assert_eq!(diagnostics("var count = 0; func inc() { count = count + 1; }; inc();"), Vec::<String>::new());
+    assert_eq!(diagnostics("func get(): i32 { count }; var count = 1; var x = get();"), Vec::<String>::new());
+    assert_eq!(diagnostics("var x = 1; var p = &x; func set() { *p = 2; };"), Vec::<String>::new());
+}
+
+#[test]
+fn globals_are_initialized_in_source_order() {
+    assert_eq!(diagnostics("func get(): i32 { count }; var x = get(); var count = 1;"), vec!["global used before initialization"]);
+    assert_eq!(
+        diagnostics("func get(): i32 { count }; func twice(): i32 { get() * 2 }; var x = twice(); var count = 1;"),
+        vec!["global used before initialization"]
+    );
+    assert_eq!(
+        diagnostics("func f(n: i32): i32 { if n > 0 { f(n - 1) } else { count } }; var x = f(2); var count = 1;"),
+        vec!["global used before initialization"]
+    );
+}
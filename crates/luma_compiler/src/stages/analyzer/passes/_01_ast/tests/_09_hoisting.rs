@@ -0,0 +1,17 @@
+use pretty_assertions::assert_eq;
+
+use crate::stages::analyzer::passes::_01_ast::tests::diagnostics;
+
+#[test]
+fn items_are_used_before_their_declaration() {
+    assert_eq!(diagnostics("var x = twice(2); func twice(n: i32): i32 { n * 2 };"), Vec::<String>::new());
+    assert_eq!(diagnostics("func f(): i32 { later(1) }; func later(n: i32) { n };"), Vec::<String>::new());
+    assert_eq!(diagnostics("func id(p: Point): Point { p }; struct Point { x: i32 };"), Vec::<String>::new());
+}
+
+#[test]
+fn recursive_struct_layouts_are_reported() {
+    assert_eq!(diagnostics("struct A { a: A };"), vec!["recursive struct"]);
+    assert_eq!(diagnostics("struct A { b: B? }; struct B { a: (i32, A) };"), vec!["recursive struct"]);
+    assert_eq!(diagnostics("struct Node { value: i32, next: *Node };"), Vec::<String>::new());
+}
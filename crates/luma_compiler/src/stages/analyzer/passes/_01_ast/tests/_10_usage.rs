@@ -0,0 +1,122 @@
+use luma_core::CodeSource;
+use luma_diagnostic::DiagnosticLevel;
+use pretty_assertions::assert_eq;
+
+use crate::{CompilerOptions, Library, LumaCompiler, lints::LintOptions};
+
+/// Compiles the source against a `util` library, returning the annotations of the reported warnings
+fn warnings(src: &str) -> Vec<String> {
+    LumaCompiler::new()
+        .with_libraries([Library::new("util", "pub func one(): i32 { 1 }; func helper() { };")])
+        .compile([CodeSource::from(src)])
+        .diagnostics
+        .into_iter()
+        .filter(|diag| diag.level == DiagnosticLevel::Warning)
+        .filter_map(|diag| diag.annotation)
+        .collect()
+}
+
+#[test]
+fn unused_locals_and_parameters_are_reported() {
+    assert_eq!(
+        warnings("pub func f(a: i32, _b: i32) { var x = 1; var y = 2; y = 3; };"),
+        vec![
+            "'a' is never read, prefix it with '_' to silence this",
+            "'x' is never read, prefix it with '_' to silence this",
+            "'y' is never read, prefix it with '_' to silence this",
+        ]
+    );
+    assert_eq!(warnings("pub func f(o: i32?): i32 { if var v = o { v } else { 2 } };"), Vec::<String>::new());
+    assert_eq!(warnings("var unread = 1;"), Vec::<String>::new());
+}
+
+#[test]
+fn unused_items_are_reported() {
+    assert_eq!(
+        warnings("func count(n: i32): i32 { if n == 0 { 0 } else { count(n - 1) } };"),
+        vec!["'count' is never called, prefix it with '_' or make it 'pub' to silence this"]
+    );
+    assert_eq!(warnings("func later() { }; var x = later();"), Vec::<String>::new());
+    assert_eq!(
+        warnings("func a(): i32 { b() }; func b(): i32 { a() };"),
+        vec![
+            "'a' is never called, prefix it with '_' or make it 'pub' to silence this",
+            "'b' is never called, prefix it with '_' or make it 'pub' to silence this",
+        ]
+    );
+    assert_eq!(warnings("pub func a(): i32 { b() }; func b(): i32 { c() }; func c(): i32 { b() };"), Vec::<String>::new());
+    assert_eq!(warnings("struct Point { x: i32, pub y: i32, _z: i32 };"), vec!["field 'x' of 'Point' is never read"]);
+}
+
+#[test]
+fn unused_imports_are_reported() {
+    assert_eq!(warnings("import util; var x = 1;"), vec!["nothing imported from 'util' is used"]);
+    assert_eq!(warnings("import util; var x = one();"), Vec::<String>::new());
+}
+
+#[test]
+fn warnings_are_silenced_per_item() {
+    assert_eq!(warnings("@allow(unused) import util; @allow(unused) func f(a: i32) { var x = 1; };"), Vec::<String>::new());
+    assert_eq!(
+        warnings("@allow(unused) struct Point { x: i32 }; struct Size { w: i32 };"),
+        vec!["field 'w' of 'Size' is never read"]
+    );
+
+    let result = LumaCompiler::new().compile([CodeSource::from("@deny(unused) func f() { };")]);
+    assert_eq!(result.diagnostics[0].title, "unknown attribute");
+}
+
+#[test]
+fn warnings_do_not_stop_compilation() {
+    let result = LumaCompiler::new().compile([CodeSource::from("pub func f(a: i32): i32 { var x = 1; 2 };")]);
+
+    assert!(!result.has_errors());
+    assert_eq!(result.diagnostics.len(), 2);
+    assert!(result.result.is_some());
+}
+
+/// Compiles the source with the lint levels, returning the level and lint of every diagnostic
+fn levels(src: &str, lints: LintOptions) -> Vec<(DiagnosticLevel, Option<String>)> {
+    let mut options = CompilerOptions::new();
+    options.lints = lints;
+
+    LumaCompiler::configure(options)
+        .compile([CodeSource::from(src)])
+        .diagnostics
+        .into_iter()
+        .map(|diag| (diag.level, diag.lint))
+        .collect()
+}
+
+#[test]
+fn lint_levels_are_configurable() {
+    const SRC: &str = "pub func f(a: i32) { var x = 1; };";
+    let variable = Some(String::from("unused_variables"));
+    let parameter = Some(String::from("unused_parameters"));
+
+    assert_eq!(
+        levels(SRC, LintOptions::new()),
+        vec![(DiagnosticLevel::Warning, parameter.clone()), (DiagnosticLevel::Warning, variable.clone())]
+    );
+    assert_eq!(levels(SRC, LintOptions::new().allow("unused_parameters")), vec![(DiagnosticLevel::Warning, variable.clone())]);
+    assert_eq!(
+        levels(SRC, LintOptions::new().deny("unused").warn("unused_variables")),
+        vec![(DiagnosticLevel::Error, parameter.clone()), (DiagnosticLevel::Warning, variable.clone())]
+    );
+    assert_eq!(
+        levels(SRC, LintOptions::new().deny_warnings(true)),
+        vec![(DiagnosticLevel::Error, parameter), (DiagnosticLevel::Error, variable)]
+    );
+    assert_eq!(levels("@allow(unused_parameters) pub func f(a: i32) { };", LintOptions::new()), vec![]);
+}
+
+#[test]
+fn denied_lints_fail_compilation() {
+    let mut options = CompilerOptions::new();
+    options.lints = LintOptions::new().deny("unused_variables");
+
+    let result = LumaCompiler::configure(options).compile([CodeSource::from("pub func f() { var x = 1; };")]);
+
+    assert!(result.has_errors());
+    assert!(result.result.is_none());
+}
@@ -0,0 +1,189 @@
+use luma_core::CodeSource;
+use luma_diagnostic::{DiagnosticLevel, define_diagnostics, warning};
+use pretty_assertions::assert_eq;
+
+use crate::{
+    CompilerOptions, LumaCompiler, VisibilityKind,
+    aast::{AnnotAstVisitor, AnnotStmt, AnnotStmtKind},
+    ast::{AstVisitor, Stmt, StmtKind},
+    lints::{AnnotAstLint, AstLint, LintContext, LintInfo, LintLevel, LintOptions, LintRegistry},
+};
+
+define_diagnostics! {
+    enum TeamLint {
+        #[Warning(L0001, "short variable name", "'{name}' should have a descriptive name")]
+        ShortName { name: String },
+        #[Warning(L0002, "public function", "'{name}' is part of the module's interface")]
+        PublicFunction { name: String },
+        #[Warning(L0003, "struct declaration", "'{name}' declares a struct")]
+        StructDecl { name: String },
+    }
+}
+
+struct ShortNames;
+
+impl AstVisitor<'_> for ShortNames {
+    type Ctx = LintContext;
+
+    fn visit_stmt(&self, ctx: &mut Self::Ctx, stmt: &mut Stmt) {
+        if let StmtKind::Var(var_decl) = &stmt.item
+            && var_decl.symbol.name().len() == 1
+        {
+            ctx.report(warning!(TeamLint::ShortName { name: var_decl.symbol.name().to_string() }, stmt.span));
+        }
+    }
+}
+
+impl AstLint for ShortNames {
+    fn info(&self) -> LintInfo {
+        LintInfo::new("short_names", LintLevel::Warn, "variables named with a single letter")
+    }
+}
+
+struct PublicFunctions;
+
+impl AnnotAstVisitor<'_> for PublicFunctions {
+    type Ctx = LintContext;
+
+    fn try_visit_stmt(&self, ctx: &mut Self::Ctx, stmt: &mut AnnotStmt) -> luma_diagnostic::CompilerResult<()> {
+        if let AnnotStmtKind::Func(func_decl) = &stmt.item
+            && func_decl.visibility.kind == VisibilityKind::Public
+        {
+            ctx.report(warning!(TeamLint::PublicFunction { name: func_decl.symbol.name.clone() }, stmt.span));
+        }
+
+        Ok(())
+    }
+}
+
+impl AnnotAstLint for PublicFunctions {
+    fn info(&self) -> LintInfo {
+        LintInfo::new("public_functions", LintLevel::Allow, "public functions")
+    }
+}
+
+struct Structs;
+
+impl AnnotAstVisitor<'_> for Structs {
+    type Ctx = LintContext;
+
+    // stops at the first struct rather than reporting through the context
+    fn try_visit_stmt(&self, _ctx: &mut Self::Ctx, stmt: &mut AnnotStmt) -> luma_diagnostic::CompilerResult<()> {
+        if let AnnotStmtKind::Struct(struct_decl) = &stmt.item {
+            return Err(warning!(TeamLint::StructDecl { name: struct_decl.symbol.name.clone() }, stmt.span));
+        }
+
+        Ok(())
+    }
+}
+
+impl AnnotAstLint for Structs {
+    fn info(&self) -> LintInfo {
+        LintInfo::new("structs", LintLevel::Warn, "struct declarations")
+    }
+}
+
+/// Compiles the source with both lints registered, returning the lint and level of every reported diagnostic
+fn lints(src: &str, options: LintOptions) -> Vec<(String, DiagnosticLevel)> {
+    let mut compiler_options = CompilerOptions::new();
+    compiler_options.lints = options;
+
+    LumaCompiler::configure(compiler_options)
+        .with_lint(ShortNames)
+        .with_annot_lint(PublicFunctions)
+        .compile([CodeSource::from(src)])
+        .diagnostics
+        .into_iter()
+        .map(|diag| (diag.lint.expect("expected a lint diagnostic"), diag.level))
+        .collect()
+}
+
+#[test]
+fn registered_lints_report_with_their_default_level() {
+    assert_eq!(
+        lints("pub func f(): i32 { var x = 1; var count = 2; x + count };", LintOptions::new()),
+        vec![(String::from("short_names"), DiagnosticLevel::Warning)]
+    );
+    assert_eq!(lints("pub func f() { };", LintOptions::new()), Vec::new());
+}
+
+#[test]
+fn registered_lints_are_configurable() {
+    let src = "pub func f(): i32 { var x = 1; x };";
+
+    assert_eq!(
+        lints(src, LintOptions::new().deny("short_names").warn("public_functions")),
+        vec![(String::from("short_names"), DiagnosticLevel::Error)]
+    );
+    assert_eq!(
+        lints(src, LintOptions::new().allow("short_names").warn("public_functions")),
+        vec![(String::from("public_functions"), DiagnosticLevel::Warning)]
+    );
+    assert_eq!(lints("pub func f(): i32 { @allow(short_names) var x = 1; x };", LintOptions::new()), Vec::new());
+    assert_eq!(lints("@allow(public_functions) pub func f() { };", LintOptions::new().deny("public_functions")), Vec::new());
+}
+
+#[test]
+fn lowered_ast_lints_report_their_errors_as_lint_diagnostics() {
+    let src = "struct Point { pub x: i32 }; @allow(structs) struct Size { pub w: i32 };";
+    let reported = LumaCompiler::new()
+        .with_annot_lint(Structs)
+        .compile([CodeSource::from(src)])
+        .diagnostics
+        .into_iter()
+        .map(|diag| (diag.lint, diag.annotation, diag.level))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        reported,
+        vec![(
+            Some(String::from("structs")),
+            Some(String::from("'Point' declares a struct")),
+            DiagnosticLevel::Warning
+        )]
+    );
+}
+
+#[test]
+fn unknown_lint_names_are_reported() {
+    let mut options = CompilerOptions::new();
+    options.lints = LintOptions::new().deny("nonexistent_lint").allow("unused").warn("short_names").allow("shrot_names");
+
+    let result = LumaCompiler::configure(options).with_lint(ShortNames).compile([CodeSource::from("var count = 1;")]);
+    let reported = result
+        .diagnostics
+        .iter()
+        .map(|diag| (diag.code, diag.annotation.as_deref(), diag.level))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        reported,
+        vec![
+            (Some("W0001"), Some("no lint or lint group is named 'nonexistent_lint'"), DiagnosticLevel::Warning),
+            (Some("W0001"), Some("no lint or lint group is named 'shrot_names'"), DiagnosticLevel::Warning),
+        ]
+    );
+    assert!(!result.has_errors());
+}
+
+#[test]
+fn registry_lists_every_lint() {
+    let mut registry = LintRegistry::new();
+    registry.register(ShortNames);
+    registry.register_annot(PublicFunctions);
+
+    let names = registry.lints().map(|info| info.name).collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        vec![
+            "unused_variables",
+            "unused_parameters",
+            "unused_functions",
+            "unused_fields",
+            "unused_imports",
+            "short_names",
+            "public_functions"
+        ]
+    );
+    assert_eq!(registry.find("public_functions").map(|info| info.default_level), Some(LintLevel::Allow));
+}
@@ -1,9 +1,18 @@
-use crate::{CompilerOptions, ast::*, compiler::run_stage, stages::lexer::LexerOptions};
+use crate::{CompilerOptions, LumaCompiler, ast::*, compiler::run_stage, stages::lexer::LexerOptions};
 use luma_core::{CodeSource, CodeSourceId};
+use luma_diagnostic::DiagnosticLevel;
 
 use crate::{AnalyzerStage, CompilerContext, LexerStage, ParserStage};
 
 pub mod _03_type_inference;
+pub mod _04_calls;
+pub mod _05_optionals;
+pub mod _06_results;
+pub mod _07_references;
+pub mod _08_globals;
+pub mod _09_hoisting;
+pub mod _10_usage;
+pub mod _11_lints;
 
 mod macros {
     macro_rules! extract_stmt {
@@ -44,4 +53,20 @@ pub fn analyze_source(src: &str) -> Option<Ast> {
     } else {
         Some(asts.into_iter().next().unwrap())
     }
-}
\ No newline at end of file
+}
+
+/// Compiles the source with the compiler, returning the titles of the reported errors
+pub fn compile_errors(compiler: LumaCompiler, src: &str) -> Vec<String> {
+    compiler
+        .compile([CodeSource::from(src)])
+        .diagnostics
+        .into_iter()
+        .filter(|diag| diag.level == DiagnosticLevel::Error)
+        .map(|diag| diag.title)
+        .collect()
+}
+
+/// Compiles the source, returning the titles of the reported errors
+pub fn diagnostics(src: &str) -> Vec<String> {
+    compile_errors(LumaCompiler::new(), src)
+}
@@ -0,0 +1,265 @@
+use std::collections::HashMap;
+
+use luma_core::Span;
+use luma_diagnostic::error;
+
+use crate::{
+    TypeKind,
+    aast::*,
+    stages::analyzer::{AnalyzerContext, AnalyzerError, AnalyzerPass},
+};
+
+/// Reports references that outlive the variable they refer to.
+///
+/// Variables live until the end of the scope they are declared in, their slots are reused afterwards.
+/// Globals live as long as the module, references to them never escape.
+/// A reference may only be stored in a variable declared at the same depth or deeper than the referenced one,
+/// and may only leave a scope or a function if it refers to a variable declared outside of it
+pub struct ReferenceChecking;
+
+impl AnalyzerPass<AnnotatedAst> for ReferenceChecking {
+    fn name(&self) -> String {
+        String::from("reference_checking")
+    }
+
+    fn analyze(&self, ctx: &mut AnalyzerContext, input: &mut AnnotatedAst) {
+        let mut frame = Frame::default();
+
+        for stmt in &input.statements {
+            self.check_stmt(ctx, &mut frame, stmt);
+        }
+    }
+}
+
+/// The variables of the function being checked
+#[derive(Default)]
+struct Frame {
+    /// scope depth of the function's parameters, deeper scopes are blocks inside its body
+    base: usize,
+    depth: usize,
+    variables: HashMap<usize, Variable>,
+}
+
+struct Variable {
+    depth: usize,
+
+    /// the variable the value may refer to
+    referent: Option<Referent>,
+}
+
+/// A referenced variable, with the `&variable` expression taking the reference
+#[derive(Clone)]
+struct Referent {
+    depth: usize,
+    name: String,
+    span: Span,
+}
+
+impl ReferenceChecking {
+    fn check_stmt(&self, ctx: &mut AnalyzerContext, frame: &mut Frame, stmt: &AnnotStmt) {
+        match &stmt.item {
+            AnnotStmtKind::Expr(expr) => {
+                self.check_expr(ctx, frame, expr);
+            }
+            AnnotStmtKind::Func(func_decl) => {
+                let mut function = Frame {
+                    base: 1,
+                    depth: 1,
+                    variables: HashMap::new(),
+                };
+
+                // the caller's variables outlive the call
+                for param in &func_decl.parameters {
+                    let referent = holds_reference(&param.ty.kind).then(|| Referent {
+                        depth: 0,
+                        name: param.symbol.name.clone(),
+                        span: param.span,
+                    });
+
+                    function.variables.insert(param.symbol.id, Variable { depth: 1, referent });
+                }
+
+                let referent = self.check_expr(ctx, &mut function, &func_decl.body);
+                escape(ctx, referent, function.base);
+            }
+            AnnotStmtKind::Return(return_stmt) => {
+                if let Some(value) = &return_stmt.value {
+                    let referent = self.check_expr(ctx, frame, value);
+                    escape(ctx, referent, frame.base);
+                }
+            }
+            AnnotStmtKind::Struct(_) => {}
+            AnnotStmtKind::Var(var_decl) => {
+                let referent = self.check_expr(ctx, frame, &var_decl.initializer);
+
+                frame.variables.insert(var_decl.symbol.id, Variable { depth: frame.depth, referent });
+            }
+        }
+    }
+
+    /// Checks the expression, returning the deepest variable its value may refer to
+    fn check_expr(&self, ctx: &mut AnalyzerContext, frame: &mut Frame, expr: &AnnotExpr) -> Option<Referent> {
+        let referent = match &expr.item {
+            AnnotExprKind::AddressOf(value) => match &value.item {
+                AnnotExprKind::Ident(ident) => frame.variables.get(&ident.symbol.id).map(|variable| Referent {
+                    depth: variable.depth,
+                    name: ident.symbol.name.clone(),
+                    span: expr.span,
+                }),
+                _ => self.check_expr(ctx, frame, value),
+            },
+            AnnotExprKind::Assign(assign_expr) => {
+                let referent = self.check_expr(ctx, frame, &assign_expr.value);
+
+                match &assign_expr.target.item {
+                    AnnotExprKind::Ident(ident) => match frame.variables.get_mut(&ident.symbol.id) {
+                        Some(variable) => {
+                            if escape(ctx, referent.clone(), variable.depth + 1) {
+                                return None;
+                            }
+
+                            variable.referent = deepest(variable.referent.take(), referent.clone());
+                        }
+                        // globals outlive every frame, they may only refer to other globals
+                        None => {
+                            if escape(ctx, referent.clone(), 0) {
+                                return None;
+                            }
+                        }
+                    },
+                    // the variable behind a reference may be any variable outside of the function
+                    AnnotExprKind::Deref(target) => {
+                        self.check_expr(ctx, frame, target);
+
+                        if escape(ctx, referent.clone(), frame.base) {
+                            return None;
+                        }
+                    }
+                    _ => {
+                        self.check_expr(ctx, frame, &assign_expr.target);
+                    }
+                }
+
+                referent
+            }
+            AnnotExprKind::Binary(binary_expr) => {
+                let left = self.check_expr(ctx, frame, &binary_expr.left);
+                let right = self.check_expr(ctx, frame, &binary_expr.right);
+                deepest(left, right)
+            }
+            AnnotExprKind::Block(block_expr) => {
+                frame.depth += 1;
+
+                for stmt in &block_expr.statements {
+                    self.check_stmt(ctx, frame, stmt);
+                }
+
+                let referent = block_expr
+                    .tail_expr
+                    .as_ref()
+                    .and_then(|tail_expr| self.check_expr(ctx, frame, tail_expr));
+
+                let depth = frame.depth;
+                frame.variables.retain(|_, variable| variable.depth < depth);
+                frame.depth -= 1;
+
+                if escape(ctx, referent.clone(), depth) {
+                    return None;
+                }
+
+                referent
+            }
+            // a returned reference can only refer to what the arguments refer to
+            AnnotExprKind::Call(call_expr) => call_expr
+                .arguments
+                .iter()
+                .fold(None, |referent, argument| deepest(referent, self.check_expr(ctx, frame, argument))),
+            AnnotExprKind::Catch(catch_expr) => {
+                let value = self.check_expr(ctx, frame, &catch_expr.value);
+
+                let variable = Variable { depth: frame.depth + 1, referent: value.clone() };
+                frame.variables.insert(catch_expr.binding.id, variable);
+
+                let handler = self.check_expr(ctx, frame, &catch_expr.handler);
+                deepest(value, handler)
+            }
+            AnnotExprKind::Get(get_expr) => self.check_expr(ctx, frame, &get_expr.object),
+            AnnotExprKind::Deref(value)
+            | AnnotExprKind::Err(value)
+            | AnnotExprKind::Group(value)
+            | AnnotExprKind::Ok(value)
+            | AnnotExprKind::Propagate(value)
+            | AnnotExprKind::Try(value) => self.check_expr(ctx, frame, value),
+            AnnotExprKind::Ident(ident) => frame
+                .variables
+                .get(&ident.symbol.id)
+                .and_then(|variable| variable.referent.clone()),
+            AnnotExprKind::If(if_expr) => {
+                let condition = self.check_expr(ctx, frame, &if_expr.condition);
+
+                if let Some(binding) = &if_expr.binding {
+                    let variable = Variable { depth: frame.depth + 1, referent: condition };
+                    frame.variables.insert(binding.id, variable);
+                }
+
+                let then_branch = self.check_expr(ctx, frame, &if_expr.then_branch);
+                let else_branch = if_expr
+                    .else_branch
+                    .as_ref()
+                    .and_then(|else_branch| self.check_expr(ctx, frame, else_branch));
+
+                deepest(then_branch, else_branch)
+            }
+            AnnotExprKind::Interpolation(interpolation_expr) => {
+                for part in &interpolation_expr.parts {
+                    if let InterpolationAnnotPart::Expr(expr) = part {
+                        self.check_expr(ctx, frame, expr);
+                    }
+                }
+
+                None
+            }
+            AnnotExprKind::Literal(_) => None,
+            AnnotExprKind::Struct(struct_expr) => struct_expr
+                .fields
+                .iter()
+                .fold(None, |referent, field| deepest(referent, self.check_expr(ctx, frame, &field.value))),
+            AnnotExprKind::TupleLiteral(tuple_expr) => tuple_expr
+                .elements
+                .iter()
+                .fold(None, |referent, element| deepest(referent, self.check_expr(ctx, frame, element))),
+            AnnotExprKind::Unary(unary_expr) => self.check_expr(ctx, frame, &unary_expr.value),
+        };
+
+        referent.filter(|_| holds_reference(&expr.ty))
+    }
+}
+
+/// Whether a value of the type can be or contain a reference
+fn holds_reference(ty: &TypeKind) -> bool {
+    match ty {
+        TypeKind::Ptr(_) => true,
+        TypeKind::Optional(inner) => holds_reference(&inner.kind),
+        TypeKind::Result(value, error) => holds_reference(&value.kind) || holds_reference(&error.kind),
+        TypeKind::Tuple(elements) => elements.iter().any(|element| holds_reference(&element.kind)),
+        _ => false,
+    }
+}
+
+fn deepest(left: Option<Referent>, right: Option<Referent>) -> Option<Referent> {
+    match (left, right) {
+        (Some(left), Some(right)) => Some(if right.depth > left.depth { right } else { left }),
+        (left, right) => left.or(right),
+    }
+}
+
+/// Reports the reference if it refers to a variable declared at `depth` or deeper, returns whether it did
+fn escape(ctx: &mut AnalyzerContext, referent: Option<Referent>, depth: usize) -> bool {
+    match referent {
+        Some(referent) if referent.depth >= depth => {
+            ctx.diagnostic(error!(AnalyzerError::EscapingReference { name: referent.name.clone() }).span(referent.span));
+            true
+        }
+        _ => false,
+    }
+}
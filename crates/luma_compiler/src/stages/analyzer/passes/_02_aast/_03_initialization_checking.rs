@@ -0,0 +1,158 @@
+use std::collections::{HashMap, HashSet};
+
+use luma_core::Span;
+use luma_diagnostic::{CompilerResult, error};
+
+use crate::{
+    SymbolId,
+    aast::*,
+    stages::analyzer::{AnalyzerContext, AnalyzerError, AnalyzerPass},
+};
+
+/// Reports globals used before their declaration ran.
+///
+/// Top-level statements run in source order, a function uses every global its body or the functions it calls use.
+/// Calling a function is only allowed once all of those are initialized
+pub struct InitializationChecking;
+
+impl AnalyzerPass<AnnotatedAst> for InitializationChecking {
+    fn name(&self) -> String {
+        String::from("initialization_checking")
+    }
+
+    fn analyze(&self, ctx: &mut AnalyzerContext, input: &mut AnnotatedAst) {
+        let globals = input
+            .statements
+            .iter()
+            .filter_map(|stmt| match &stmt.item {
+                AnnotStmtKind::Var(var_decl) => Some((var_decl.symbol.id, var_decl.symbol.name.clone())),
+                _ => None,
+            })
+            .collect::<HashMap<_, _>>();
+
+        // functions can be called before their declaration, so every use is collected up front
+        let mut uses = Uses::default();
+        let statements = input
+            .statements
+            .iter_mut()
+            .map(|stmt| {
+                uses.scopes.push(Vec::new());
+                let _ = UseCollector.walk_stmt(&mut uses, stmt);
+                uses.scopes.pop().unwrap_or_default()
+            })
+            .collect::<Vec<_>>();
+
+        let mut initialized = HashSet::new();
+
+        for (stmt, stmt_uses) in input.statements.iter().zip(statements) {
+            for used in stmt_uses {
+                match used {
+                    Use::Variable { id, span } if globals.contains_key(&id) && !initialized.contains(&id) => {
+                        ctx.diagnostic(error!(AnalyzerError::UninitializedGlobal { name: globals[&id].clone() }).span(span));
+                    }
+                    Use::Call { function, span } => {
+                        let Some(id) = uses.uninitialized(function, &globals, &initialized, &mut HashSet::new()) else {
+                            continue;
+                        };
+
+                        ctx.diagnostic(
+                            error!(AnalyzerError::UninitializedGlobalCall {
+                                name: globals[&id].clone(),
+                                function: uses.functions[&function].name.clone(),
+                            })
+                            .span(span),
+                        );
+                    }
+                    Use::Variable { .. } => {}
+                }
+            }
+
+            if let AnnotStmtKind::Var(var_decl) = &stmt.item {
+                initialized.insert(var_decl.symbol.id);
+            }
+        }
+    }
+}
+
+/// A variable read or written, or a function called
+enum Use {
+    Variable { id: SymbolId, span: Span },
+    Call { function: SymbolId, span: Span },
+}
+
+struct Function {
+    name: String,
+    uses: Vec<Use>,
+}
+
+#[derive(Default)]
+struct Uses {
+    /// uses of the statement and the functions being walked, innermost function last
+    scopes: Vec<Vec<Use>>,
+    functions: HashMap<SymbolId, Function>,
+}
+
+impl Uses {
+    /// The first global used by the function, or any function it calls, that isn't initialized yet
+    fn uninitialized(
+        &self,
+        function: SymbolId,
+        globals: &HashMap<SymbolId, String>,
+        initialized: &HashSet<SymbolId>,
+        visited: &mut HashSet<SymbolId>,
+    ) -> Option<SymbolId> {
+        // natives and intrinsics use no globals
+        let function = self.functions.get(&function).filter(|_| visited.insert(function))?;
+
+        function.uses.iter().find_map(|used| match *used {
+            Use::Variable { id, .. } => (globals.contains_key(&id) && !initialized.contains(&id)).then_some(id),
+            Use::Call { function, .. } => self.uninitialized(function, globals, initialized, visited),
+        })
+    }
+
+    fn record(&mut self, used: Use) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.push(used);
+        }
+    }
+}
+
+/// Collects the uses of a statement, the uses of functions declared in it are kept separately
+struct UseCollector;
+
+impl AnnotAstVisitor<'_> for UseCollector {
+    type Ctx = Uses;
+
+    fn try_visit_stmt(&self, ctx: &mut Self::Ctx, stmt: &mut AnnotStmt) -> CompilerResult<()> {
+        if let AnnotStmtKind::Func(_) = &stmt.item {
+            ctx.scopes.push(Vec::new());
+        }
+
+        Ok(())
+    }
+
+    fn try_leave_stmt(&self, ctx: &mut Self::Ctx, stmt: &mut AnnotStmt) -> CompilerResult<()> {
+        if let AnnotStmtKind::Func(func_decl) = &stmt.item {
+            let uses = ctx.scopes.pop().unwrap_or_default();
+            let function = Function { name: func_decl.symbol.name.clone(), uses };
+
+            ctx.functions.insert(func_decl.symbol.id, function);
+        }
+
+        Ok(())
+    }
+
+    fn try_visit_expr(&self, ctx: &mut Self::Ctx, expr: &mut AnnotExpr) -> CompilerResult<()> {
+        match &expr.item {
+            AnnotExprKind::Call(call_expr) => {
+                if let AnnotExprKind::Ident(callee) = &call_expr.callee.item {
+                    ctx.record(Use::Call { function: callee.symbol.id, span: expr.span });
+                }
+            }
+            AnnotExprKind::Ident(ident) => ctx.record(Use::Variable { id: ident.symbol.id, span: expr.span }),
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use std::rc::Rc;
+
+use luma_core::Span;
+use luma_diagnostic::CompilerResult;
+
+use crate::{
+    aast::*,
+    lints::{AnnotAstLint, LintRegistry, lint_module},
+    stages::analyzer::{AnalyzerContext, AnalyzerPass},
+};
+
+/// Runs the lints registered with [`crate::LumaCompiler::with_annot_lint`].
+///
+/// Only the statements of the module itself are linted, the statements merged in from imported libraries are skipped
+pub struct RegisteredLints {
+    lints: Vec<Rc<dyn AnnotAstLint>>,
+}
+
+impl RegisteredLints {
+    pub fn new(registry: &LintRegistry) -> Self {
+        Self {
+            lints: registry.aast.clone(),
+        }
+    }
+}
+
+impl AnalyzerPass<AnnotatedAst> for RegisteredLints {
+    fn name(&self) -> String {
+        String::from("registered_lints")
+    }
+
+    fn analyze(&self, ctx: &mut AnalyzerContext, input: &mut AnnotatedAst) {
+        for lint in &self.lints {
+            let name = lint.info().name;
+            let reported = lint_module(name, input.span, &mut input.statements, |lint_ctx, allowed, stmt| {
+                // a lint stopping at a statement reports the error like any other diagnostic
+                if let Err(diag) = lint.walk_stmt(lint_ctx, stmt) {
+                    lint_ctx.report(diag);
+                }
+
+                let _ = AllowedSpans { lint: name }.walk_stmt(allowed, stmt);
+            });
+
+            ctx.diagnostics.borrow_mut().extend(reported);
+        }
+    }
+}
+
+/// Collects the spans of the statements allowing a lint
+struct AllowedSpans {
+    lint: &'static str,
+}
+
+impl AnnotAstVisitor<'_> for AllowedSpans {
+    type Ctx = Vec<Span>;
+
+    fn try_visit_stmt(&self, ctx: &mut Self::Ctx, stmt: &mut AnnotStmt) -> CompilerResult<()> {
+        if stmt.allows(self.lint) {
+            ctx.push(stmt.span);
+        }
+
+        Ok(())
+    }
+}
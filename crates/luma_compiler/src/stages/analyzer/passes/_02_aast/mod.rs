@@ -1,12 +1,20 @@
 mod _01_type_checking;
+mod _02_reference_checking;
+mod _03_initialization_checking;
+mod _04_registered_lints;
 
 pub use _01_type_checking::TypeChecking;
+pub use _02_reference_checking::ReferenceChecking;
+pub use _03_initialization_checking::InitializationChecking;
+pub use _04_registered_lints::RegisteredLints;
 
 use crate::{AnalyzerStage, aast::AnnotatedAst, stages::analyzer::AnalyzerPass};
 
 pub fn default_aast_passes() -> Vec<Box<dyn AnalyzerPass<AnnotatedAst>>> {
     vec![
         Box::new(TypeChecking),
+        Box::new(ReferenceChecking),
+        Box::new(InitializationChecking),
     ]
 }
 
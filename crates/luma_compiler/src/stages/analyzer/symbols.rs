@@ -1,11 +1,12 @@
 use std::collections::HashMap;
 
-use crate::{ScopeId, SymbolId, Type, stages::analyzer::scopes::ScopeManager};
+use crate::{ScopeId, SymbolId, Type, TypeKind, stages::analyzer::scopes::ScopeManager};
 
 #[derive(Debug)]
 pub struct SymbolTable {
     symbols: Vec<SymbolEntry>,
     lookup_map: HashMap<ScopeId, HashMap<SymbolNamespace, HashMap<String, SymbolId>>>,
+    signatures: HashMap<SymbolId, FunctionSignature>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -13,9 +14,8 @@ pub enum SymbolNamespace {
     ControlFlow,
     Type,
     Value,
-    // /// field symbols within a struct
-    // /// usize - index of the field within the struct
-    // StructField(usize),
+    /// fields of the struct with the given symbol id
+    StructField(SymbolId),
 }
 
 #[derive(Debug)]
@@ -26,11 +26,22 @@ pub struct SymbolEntry {
     pub declared_ty: Option<Type>,
 }
 
+/// Parameter types of a callable symbol
+#[derive(Debug, Clone)]
+pub struct FunctionSignature {
+    /// `None` accepts any displayable value
+    pub params: Vec<Option<TypeKind>>,
+
+    /// `None` if the return type is inferred from the function body
+    pub return_type: Option<TypeKind>,
+}
+
 impl SymbolTable {
     pub fn new() -> Self {
         SymbolTable {
             symbols: Vec::new(),
             lookup_map: HashMap::new(),
+            signatures: HashMap::new(),
         }
     }
 
@@ -67,19 +78,17 @@ impl SymbolTable {
         mut scope: ScopeId,
         name: &str,
     ) -> Option<SymbolId> {
-        while let Some(scope_map) = self.lookup_map.get(&scope) {
-            if let Some(ns_map) = scope_map.get(&namespace)
+        loop {
+            // scopes without any declarations have no map, their parents may still declare the name
+            if let Some(scope_map) = self.lookup_map.get(&scope)
+                && let Some(ns_map) = scope_map.get(&namespace)
                 && let Some(&id) = ns_map.get(name)
             {
                 return Some(id);
             }
 
-            scope = match scopes.parent(scope) {
-                Some(p) => p,
-                None => break,
-            };
+            scope = scopes.parent(scope)?;
         }
-        None
     }
 
     pub fn enter_scope(&mut self, scope_id: ScopeId) {
@@ -93,4 +102,13 @@ impl SymbolTable {
     pub fn get_symbol(&self, id: SymbolId) -> Option<&SymbolEntry> {
         self.symbols.get(id)
     }
+
+    /// Marks the symbol as callable with the signature
+    pub fn set_signature(&mut self, id: SymbolId, signature: FunctionSignature) {
+        self.signatures.insert(id, signature);
+    }
+
+    pub fn get_signature(&self, id: SymbolId) -> Option<&FunctionSignature> {
+        self.signatures.get(&id)
+    }
 }
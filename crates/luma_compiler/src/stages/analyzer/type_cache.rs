@@ -79,6 +79,20 @@ impl TypeCache {
             (TypeCacheEntry::Concrete(source_ty), TypeCacheEntry::Concrete(target_ty)) => {
                 if source_ty == target_ty {
                     Ok(())
+                } else if *source_ty == TypeKind::Error || *target_ty == TypeKind::Error {
+                    // the erroneous side was reported already
+                    Ok(())
+                } else if let TypeKind::Optional(inner) = source_ty
+                    && inner.kind == *target_ty
+                {
+                    // present values are wrapped implicitly
+                    Ok(())
+                } else if let TypeKind::Optional(inner) = target_ty
+                    && inner.kind == *source_ty
+                {
+                    Err(error!(AnalyzerError::UnwrappedOptional {
+                        ty: target_ty.clone(),
+                    }))
                 } else {
                     Err(error!(AnalyzerError::TypeMismatch {
                         expected: source_ty.clone(),
@@ -130,7 +144,10 @@ impl TypeCache {
                     .ok_or(error!(AnalyzerError::TypeInferenceFailure))?;
 
                 if let Some(existing) = self.resolved.get(&root) {
-                    if existing != ty {
+                    // a present value doesn't narrow an optional
+                    let wraps = matches!(existing, TypeKind::Optional(inner) if inner.kind == *ty);
+
+                    if existing != ty && !wraps {
                         self.resolved.insert(root, ty.clone());
                     }
                 } else {
@@ -142,6 +159,20 @@ impl TypeCache {
         }
     }
 
+    /// Unifies the types of two branches, either may be the optional form of the other.
+    ///
+    /// Returns the type of the whole conditional
+    pub fn join(&mut self, first: &TypeCacheEntry, second: &TypeCacheEntry) -> CompilerResult<TypeCacheEntry> {
+        match (self.resolve(first), self.resolve(second)) {
+            (Some(ty), Some(TypeKind::Optional(inner))) | (Some(TypeKind::Optional(inner)), Some(ty))
+                if inner.kind == ty =>
+            {
+                Ok(TypeCacheEntry::Concrete(TypeKind::Optional(inner)))
+            }
+            _ => self.unify(first, second).map(|_| first.clone()),
+        }
+    }
+
     fn resolve_conflict(&self, t1: &TypeKind, t2: &TypeKind) -> CompilerResult<TypeKind> {
         if t1 == &TypeKind::UInt32 || t2 == &TypeKind::UInt32 {
             Ok(TypeKind::UInt32)
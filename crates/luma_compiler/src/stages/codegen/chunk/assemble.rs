@@ -0,0 +1,169 @@
+use luma_core::Span;
+use luma_diagnostic::{CompilerResult, error};
+
+use crate::{
+    bytecode::Opcode,
+    stages::codegen::{
+        CodegenError,
+        chunk::{CodeChunk, DebugInfo, InstrOffset, LineEntry, LocalInfo},
+    },
+};
+
+/// Debug info of a chunk decoded through [`CodeChunk::decode_instructions`].
+///
+/// Spans are stored per instruction and the live ranges of locals use instruction indices instead of offsets
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct DecodedDebugInfo {
+    pub spans: Vec<Option<Span>>,
+    pub locals: Vec<LocalInfo>,
+}
+
+impl CodeChunk {
+    /// Encodes instructions whose jumps target instruction indices into a chunk whose jumps target byte offsets.
+    ///
+    /// Jumps are kept narrow where possible, a jump only becomes wide if its target offset doesn't fit in 16 bits
+    pub fn assemble(instructions: &[Opcode], max_locals: usize) -> CompilerResult<CodeChunk> {
+        // every jump starts out narrow and is widened until all targets fit,
+        // widening only ever moves offsets further back so this always settles
+        let mut wide = vec![false; instructions.len()];
+
+        let offsets = loop {
+            let offsets = layout(instructions, &wide)?;
+            let mut changed = false;
+
+            for (index, opcode) in instructions.iter().enumerate() {
+                if let Some(target) = opcode.jump_target()
+                    && !wide[index]
+                    && offsets[target as usize] > u16::MAX as InstrOffset
+                {
+                    wide[index] = true;
+                    changed = true;
+                }
+            }
+
+            if !changed {
+                break offsets;
+            }
+        };
+
+        let mut code = Vec::with_capacity(offsets[instructions.len()] as usize);
+
+        for opcode in instructions {
+            let mut opcode = *opcode;
+
+            if let Some(target) = opcode.jump_target() {
+                opcode.set_jump_target(offsets[target as usize]);
+            }
+
+            opcode.encode(&mut code);
+        }
+
+        Ok(CodeChunk {
+            code,
+            max_locals,
+            debug: DebugInfo::default(),
+            span: None,
+            last_offset: instructions.len().checked_sub(1).map(|last| offsets[last]),
+            jumps: Vec::new(),
+        })
+    }
+
+    /// Like [`Self::assemble`] but also carries over the debug info of the instructions
+    pub fn assemble_with_debug_info(
+        instructions: &[Opcode],
+        max_locals: usize,
+        debug: &DecodedDebugInfo,
+    ) -> CompilerResult<CodeChunk> {
+        let mut chunk = CodeChunk::assemble(instructions, max_locals)?;
+
+        let mut offsets = chunk.instruction_offsets();
+        offsets.push(chunk.len());
+
+        let offset_of = |index: InstrOffset| offsets[(index as usize).min(instructions.len())];
+
+        chunk.debug.lines = debug
+            .spans
+            .iter()
+            .zip(&offsets)
+            .filter_map(|(span, &offset)| Some(LineEntry { offset, span: (*span)? }))
+            .collect();
+
+        chunk.debug.locals = debug
+            .locals
+            .iter()
+            .map(|local| LocalInfo {
+                start: offset_of(local.start),
+                end: offset_of(local.end),
+                ..local.clone()
+            })
+            .collect();
+
+        Ok(chunk)
+    }
+
+    /// Decodes the debug info into the per instruction form matching [`Self::decode_instructions`]
+    pub fn decode_debug_info(&self) -> DecodedDebugInfo {
+        let index_of = self.index_map();
+        let offsets = self.instruction_offsets();
+
+        // live ranges of locals still in scope may extend past the end
+        let index = |offset: InstrOffset| index_of(offset).unwrap_or(offsets.len()) as InstrOffset;
+
+        DecodedDebugInfo {
+            spans: offsets.iter().map(|&offset| self.debug.lines.span_at(offset)).collect(),
+            locals: self
+                .debug
+                .locals
+                .iter()
+                .map(|local| LocalInfo {
+                    start: index(local.start),
+                    end: index(local.end),
+                    ..local.clone()
+                })
+                .collect(),
+        }
+    }
+
+    /// Decodes the chunk into one opcode per instruction, with jumps targeting instruction indices instead of offsets.
+    ///
+    /// This is the inverse of [`Self::assemble`] and the form passes that add or remove instructions work on
+    pub fn decode_instructions(&self) -> Vec<Opcode> {
+        let index_of = self.index_map();
+
+        self.iter()
+            .map(|(_, mut opcode)| {
+                if let Some(target) = opcode.jump_target() {
+                    // jumps into the middle of an instruction never come out of the compiler
+                    let index = index_of(target).expect("jump target is not an instruction boundary");
+                    opcode.set_jump_target(index as InstrOffset);
+                }
+
+                opcode
+            })
+            .collect()
+    }
+}
+
+/// Computes the offset of every instruction plus the end of the chunk, given which jumps are wide
+fn layout(instructions: &[Opcode], wide: &[bool]) -> CompilerResult<Vec<InstrOffset>> {
+    let mut offsets = Vec::with_capacity(instructions.len() + 1);
+    let mut offset = 0usize;
+
+    for (opcode, wide) in instructions.iter().zip(wide) {
+        offsets.push(offset as InstrOffset);
+
+        offset += match opcode.jump_target() {
+            Some(_) if *wide => Opcode::JumpWide(0).encoded_len(),
+            Some(_) => Opcode::Jump(0).encoded_len(),
+            None => opcode.encoded_len(),
+        };
+
+        if offset > InstrOffset::MAX as usize {
+            return Err(error!(CodegenError::ChunkTooLarge));
+        }
+    }
+
+    offsets.push(offset as InstrOffset);
+
+    Ok(offsets)
+}
@@ -1,10 +1,12 @@
-use luma_diagnostic::CompilerResult;
+use luma_diagnostic::{CompilerResult, error};
 
 use crate::{
+    Intrinsic,
     aast::*,
     bytecode::*,
     stages::codegen::{
-        chunk::{ChunkBuilderEnv, CodeChunk, FunctionChunk},
+        CodegenError,
+        chunk::{ChunkBuilderEnv, CodeChunk, FunctionChunk, JumpHandle, LocalSlot},
         module::ModuleContext,
     },
 };
@@ -20,14 +22,47 @@ impl ChunkBuilder {
     ) -> CompilerResult<CodeChunk> {
         let mut env = ChunkBuilderEnv::new();
 
+        // top-level variables are globals and top-level functions are hoisted,
+        // both are declared up front so that every function can use them
+        for stmt in statements.iter() {
+            if let AnnotStmtKind::Func(func_decl) = &stmt.item {
+                module.function_table.declare_function(
+                    func_decl.symbol.id,
+                    &func_decl.symbol.name,
+                    func_decl.parameters.len(),
+                );
+            }
+
+            if let AnnotStmtKind::Var(var_decl) = &stmt.item {
+                let index = module.global_table.declare_global(
+                    var_decl.symbol.id,
+                    &var_decl.symbol.name,
+                    var_decl.ty.kind.clone(),
+                );
+
+                if var_decl.visibility.kind.is_public() && stmt.span.source_id == module.source_id {
+                    module.export_table.add_variable(var_decl.symbol.name.clone(), index);
+                }
+            }
+        }
+
         for stmt in statements {
             self.compile_stmt(module, &mut env, stmt)?;
+
+            // functions of imported libraries aren't exported by the importing module
+            if let AnnotStmtKind::Func(func_decl) = &stmt.item
+                && func_decl.visibility.kind.is_public()
+                && stmt.span.source_id == module.source_id
+                && let Some(index) = module.function_table.get_module_index(&func_decl.symbol.id)
+            {
+                module.export_table.add_function(func_decl.symbol.name.clone(), index as u32);
+            }
         }
 
         self.emit_unit(module, &mut env)?;
-        env.chunk.emit(Opcode::Return);
+        env.chunk.emit(Opcode::Return)?;
 
-        Ok(env.chunk)
+        Ok(env.finish())
     }
 
     pub fn build_function(
@@ -37,21 +72,30 @@ impl ChunkBuilder {
     ) -> CompilerResult<FunctionChunk> {
         let mut env: ChunkBuilderEnv = ChunkBuilderEnv::new();
 
+        // the caller places the arguments in the first slots
+        for param in &func_decl.parameters {
+            env.declare_local(param.symbol.id, &param.symbol.name)?;
+        }
+
         self.compile_expr(module, &mut env, &func_decl.body, true)?;
 
+        // a jump past the last return, like the one of a trailing `?`, still needs the body's value returned
+        let end = env.chunk.len();
         let has_return = env
             .chunk
             .last()
-            .is_some_and(|instr| matches!(instr, Opcode::Return));
+            .is_some_and(|instr| instr == Opcode::Return)
+            && !env.chunk.iter().any(|(_, instr)| instr.jump_target() == Some(end));
 
-        // means its a void function, emit a Unit value to stack to replace the call stack slot, then return to indicate end of function
+        // the body's value (unit for void functions) is already on the stack, return it to end the function
         if !has_return {
-            self.emit_unit(module, &mut env)?;
-            env.chunk.emit(Opcode::Return);
+            env.chunk.set_span(Some(func_decl.body.span));
+            env.chunk.emit(Opcode::Return)?;
         }
 
         Ok(FunctionChunk {
-            code: env.chunk,
+            name: func_decl.symbol.name.clone(),
+            code: env.finish(),
             arity: func_decl.parameters.len(),
         })
     }
@@ -62,14 +106,20 @@ impl ChunkBuilder {
         env: &mut ChunkBuilderEnv,
         stmt: &AnnotStmt,
     ) -> CompilerResult<()> {
+        let outer_span = env.chunk.set_span(Some(stmt.span));
+
         match &stmt.item {
             AnnotStmtKind::Expr(expr) => self.compile_expr(module, env, expr, false)?,
             AnnotStmtKind::Func(func_decl) => {
-                let func_chunk = self.build_function(module, func_decl)?;
+                // declared before the body is built so that it can call itself, top-level functions already are
+                let func_index = module.function_table.declare_function(
+                    func_decl.symbol.id,
+                    &func_decl.symbol.name,
+                    func_decl.parameters.len(),
+                );
 
-                let func_index = module
-                    .function_table
-                    .add_function(func_decl.symbol.id, func_chunk);
+                let func_chunk = self.build_function(module, func_decl)?;
+                module.function_table.define_function(func_index, func_chunk);
             }
             AnnotStmtKind::Return(ret_stmt) => {
                 if let Some(expr) = &ret_stmt.value {
@@ -78,18 +128,32 @@ impl ChunkBuilder {
                     self.emit_unit(module, env)?;
                 }
 
-                env.chunk.emit(Opcode::Return);
+                env.chunk.emit(Opcode::Return)?;
             },
-            AnnotStmtKind::Struct(struct_decl) => todo!(),
+            AnnotStmtKind::Struct(struct_decl) => {
+                // structs have no runtime representation yet, only their layout is recorded
+                let fields = struct_decl.fields.iter().map(|field| FieldLayout {
+                    name: field.symbol.name.clone(),
+                    ty: field.ty.kind.clone(),
+                });
+
+                module.struct_table.add_struct(struct_decl.symbol.name.clone(), fields);
+            }
             AnnotStmtKind::Var(var_decl) => {
-                let slot = env.declare_local(var_decl.symbol.id)?;
-
                 self.compile_expr(module, env, &var_decl.initializer, true)?;
 
-                env.chunk.emit(Opcode::SetLocal(slot));
+                if let Some(index) = module.global_table.get_global(&var_decl.symbol.id) {
+                    env.chunk.emit(Opcode::set_global(index))?;
+                } else {
+                    // declared after the initializer so that locals of blocks inside it can share the slot
+                    let slot = env.declare_local(var_decl.symbol.id, &var_decl.symbol.name)?;
+                    env.chunk.emit(Opcode::set_local(slot))?;
+                }
             }
         }
 
+        env.chunk.set_span(outer_span);
+
         Ok(())
     }
 
@@ -100,7 +164,22 @@ impl ChunkBuilder {
         expr: &AnnotExpr,
         value_used: bool,
     ) -> CompilerResult<()> {
+        let outer_span = env.chunk.set_span(Some(expr.span));
+
         match &expr.item {
+            AnnotExprKind::AddressOf(value) => {
+                // the analyzer only accepts references to variables
+                let AnnotExprKind::Ident(ident) = &value.item else {
+                    unreachable!("reference to a temporary value");
+                };
+
+                let variable = Variable::resolve(module, env, &ident.symbol.id)?;
+                env.chunk.emit(variable.reference())?;
+
+                if !value_used {
+                    env.chunk.emit(Opcode::Pop)?;
+                }
+            }
             AnnotExprKind::Assign(assign_expr) => {
                 self.compile_expr(module, env, &assign_expr.value, true)?;
                 if let Some(operator) = &assign_expr.operator {
@@ -110,30 +189,64 @@ impl ChunkBuilder {
                     // simple assign (no special operator like +=, -=, etc.)
                     match &assign_expr.target.item {
                         AnnotExprKind::Ident(ident) => {
-                            let slot = env.resolve_local_slot(&ident.symbol.id)?;
+                            let variable = Variable::resolve(module, env, &ident.symbol.id)?;
 
-                            env.chunk.emit(Opcode::SetLocal(slot));
+                            env.chunk.emit(variable.set())?;
 
                             if value_used {
-                                env.chunk.emit(Opcode::GetLocal(slot));
+                                env.chunk.emit(variable.get())?;
                             }
                         }
+                        AnnotExprKind::Deref(reference) => {
+                            if value_used {
+                                env.chunk.emit(Opcode::Dup)?;
+                            }
+
+                            self.compile_expr(module, env, reference, true)?;
+                            env.chunk.emit(Opcode::StoreRef)?;
+                        }
                         _ => todo!(),
                     };
                 }
             }
+            AnnotExprKind::Binary(binary_expr)
+                if matches!(binary_expr.operator.kind, AnnotOperatorKind::And | AnnotOperatorKind::Or) =>
+            {
+                // the left operand's value is the result if it decides the outcome,
+                // otherwise it is dropped and the right operand's value is the result
+                self.compile_expr(module, env, &binary_expr.left, true)?;
+                env.chunk.emit(Opcode::Dup)?;
+
+                let jump_to_end = if binary_expr.operator.kind == AnnotOperatorKind::And {
+                    env.chunk.emit_jump(Opcode::jump_if_false(0))?
+                } else {
+                    env.chunk.emit_jump(Opcode::jump_if_true(0))?
+                };
+
+                env.chunk.emit(Opcode::Pop)?;
+                self.compile_expr(module, env, &binary_expr.right, true)?;
+
+                let end = env.chunk.len();
+                env.chunk.patch_jump(jump_to_end, end)?;
+
+                if !value_used {
+                    env.chunk.emit(Opcode::Pop)?;
+                }
+            }
             AnnotExprKind::Binary(binary_expr) => {
                 self.compile_expr(module, env, &binary_expr.left, true)?;
                 self.compile_expr(module, env, &binary_expr.right, true)?;
 
                 let opcode = operator_to_opcode(binary_expr.operator.kind.clone());
-                env.chunk.emit(opcode);
+                env.chunk.emit(opcode)?;
 
                 if !value_used {
-                    env.chunk.emit(Opcode::Pop);
+                    env.chunk.emit(Opcode::Pop)?;
                 }
             }
             AnnotExprKind::Block(block_expr) => {
+                env.begin_scope();
+
                 for stmt in &block_expr.statements {
                     self.compile_stmt(module, env, stmt)?;
                 }
@@ -144,74 +257,224 @@ impl ChunkBuilder {
                     // if there's no tail expression but the block's value is used, push a unit value to the stack
                     self.emit_unit(module, env)?;
                 }
+
+                env.end_scope();
+            }
+            AnnotExprKind::Call(call_expr) => {
+                // the analyzer only accepts calls of named functions
+                let AnnotExprKind::Ident(callee) = &call_expr.callee.item else {
+                    unreachable!("callee is not a function");
+                };
+
+                // arguments are passed on the stack, the callee finds them in its first local slots
+                for argument in &call_expr.arguments {
+                    self.compile_expr(module, env, argument, true)?;
+                }
+
+                let opcode = if let Some(index) = module.function_table.get_module_index(&callee.symbol.id) {
+                    Opcode::call(index as u32)
+                } else if let Some(index) = module.native_table.import(&callee.symbol.name) {
+                    Opcode::call_native(index)
+                } else if let Some(intrinsic) = Intrinsic::from_name(&callee.symbol.name) {
+                    Opcode::CallIntrinsic(intrinsic.id())
+                } else {
+                    return Err(error!(CodegenError::UndefinedFunction {
+                        name: callee.symbol.name.clone(),
+                    })
+                    .span(call_expr.callee.span));
+                };
+
+                env.chunk.emit(opcode)?;
+
+                if !value_used {
+                    env.chunk.emit(Opcode::Pop)?;
+                }
+            }
+            AnnotExprKind::Catch(catch_expr) => {
+                // values are kept as they are, errors are unwrapped into the binding and replaced by the handler's value
+                self.compile_expr(module, env, &catch_expr.value, true)?;
+                env.chunk.emit(Opcode::Dup)?;
+                env.chunk.emit(Opcode::IsErr)?;
+                let jump_to_end = env.chunk.emit_jump(Opcode::jump_if_false(0))?;
+
+                env.chunk.emit(Opcode::UnwrapErr)?;
+                env.begin_scope();
+                let slot = env.declare_local(catch_expr.binding.id, &catch_expr.binding.name)?;
+                env.chunk.emit(Opcode::set_local(slot))?;
+                self.compile_expr(module, env, &catch_expr.handler, true)?;
+                env.end_scope();
+
+                let end = env.chunk.len();
+                env.chunk.patch_jump(jump_to_end, end)?;
+
+                if !value_used {
+                    env.chunk.emit(Opcode::Pop)?;
+                }
+            }
+            AnnotExprKind::Deref(reference) => {
+                self.compile_expr(module, env, reference, true)?;
+                env.chunk.emit(Opcode::LoadRef)?;
+
+                if !value_used {
+                    env.chunk.emit(Opcode::Pop)?;
+                }
+            }
+            AnnotExprKind::Err(value) => {
+                self.compile_expr(module, env, value, true)?;
+                env.chunk.emit(Opcode::WrapErr)?;
+
+                if !value_used {
+                    env.chunk.emit(Opcode::Pop)?;
+                }
             }
-            AnnotExprKind::Call(call_expr) => todo!(),
             AnnotExprKind::Get(get_expr) => todo!(),
             AnnotExprKind::Group(expr) => self.compile_expr(module, env, expr, value_used)?,
             AnnotExprKind::Ident(ident_expr) => {
-                let slot = env.resolve_local_slot(&ident_expr.symbol.id)?;
+                let variable = Variable::resolve(module, env, &ident_expr.symbol.id)?;
 
-                env.chunk.emit(Opcode::GetLocal(slot));
+                env.chunk.emit(variable.get())?;
 
                 if !value_used {
-                    env.chunk.emit(Opcode::Pop);
+                    env.chunk.emit(Opcode::Pop)?;
                 }
             }
             AnnotExprKind::If(if_expr) => {
-                // condition
-                self.compile_expr(module, env, &if_expr.condition, true)?;
-                let jump_to_else = if if_expr.else_branch.is_some() {
-                    Some(env.chunk.emit(Opcode::JumpIfFalse(0))?)
+                // condition, a binding stores a present value in its local and takes the else branch for none
+                let jumps_to_else = if let Some(binding) = &if_expr.binding {
+                    self.compile_expr(module, env, &if_expr.condition, true)?;
+                    env.chunk.emit(Opcode::Dup)?;
+                    env.chunk.emit(Opcode::PushNone)?;
+                    env.chunk.emit(Opcode::NotEqual)?;
+                    let jump_to_else = env.chunk.emit_jump(Opcode::jump_if_false(0))?;
+
+                    env.begin_scope();
+                    let slot = env.declare_local(binding.id, &binding.name)?;
+                    env.chunk.emit(Opcode::set_local(slot))?;
+
+                    vec![jump_to_else]
                 } else {
-                    None
+                    self.compile_condition(module, env, &if_expr.condition, false)?
                 };
 
                 // then branch
-                let pre_max_locals = env.chunk.max_locals;
-                let then_locals = {
-                    self.compile_expr(module, env, &if_expr.then_branch, value_used)?;
-                    env.chunk.max_locals - pre_max_locals
-                };
+                self.compile_expr(module, env, &if_expr.then_branch, value_used)?;
+
+                if if_expr.binding.is_some() {
+                    env.end_scope();
+                }
 
-                let jump_to_end = env.chunk.emit(Opcode::Jump(0))?;
+                // without an else branch there's nothing to skip, unless a unit value has to be produced
+                // or the none of a binding has to be dropped instead
+                let needs_else = if_expr.else_branch.is_some() || value_used || if_expr.binding.is_some();
+                let jump_to_end = if needs_else {
+                    Some(env.chunk.emit_jump(Opcode::jump(0))?)
+                } else {
+                    None
+                };
 
                 // else branch
-                if let Some(else_branch) = &if_expr.else_branch {
-                    let else_start = env.chunk.instr_len();
-                    env.chunk
-                        .patch(jump_to_else.unwrap(), Opcode::JumpIfFalse(else_start))?;
-
-                    let else_locals = {
-                        let pre_else_locals = env.chunk.max_locals;
-                        self.compile_expr(module, env, else_branch, value_used)?;
-                        env.chunk.max_locals - pre_else_locals
-                    };
+                let mut else_start = env.chunk.len();
+                for jump in jumps_to_else {
+                    else_start = env.chunk.patch_jump(jump, else_start)?;
+                }
 
-                    env.chunk.max_locals = pre_max_locals + then_locals.max(else_locals);
-                } else {
-                    env.chunk.max_locals = pre_max_locals + then_locals;
+                if if_expr.binding.is_some() {
+                    env.chunk.emit(Opcode::Pop)?;
+                }
+
+                if let Some(else_branch) = &if_expr.else_branch {
+                    self.compile_expr(module, env, else_branch, value_used)?;
+                } else if value_used {
+                    self.emit_unit(module, env)?;
                 }
 
                 // end
-                let end = env.chunk.instr_len();
-                env.chunk.patch(jump_to_end, Opcode::Jump(end))?;
+                if let Some(jump_to_end) = jump_to_end {
+                    let end = env.chunk.len();
+                    env.chunk.patch_jump(jump_to_end, end)?;
+                }
+            }
+            AnnotExprKind::Interpolation(interpolation_expr) => {
+                // every part is pushed and joined at once, so no intermediate strings are allocated
+                for part in &interpolation_expr.parts {
+                    match part {
+                        InterpolationAnnotPart::Literal(text) => {
+                            let const_index = module
+                                .constant_table
+                                .add_constant(BytecodeValue::String(text.as_str().into()))?;
+                            env.chunk.emit(Opcode::load_const(const_index))?;
+                        }
+                        InterpolationAnnotPart::Expr(expr) => self.compile_expr(module, env, expr, true)?,
+                    }
+                }
+
+                let count = u16::try_from(interpolation_expr.parts.len()).map_err(|_| {
+                    error!(CodegenError::TooManyInterpolations {
+                        count: interpolation_expr.parts.len(),
+                    })
+                    .span(expr.span)
+                })?;
+                env.chunk.emit(Opcode::Concat(count))?;
+
+                if !value_used {
+                    env.chunk.emit(Opcode::Pop)?;
+                }
             }
             AnnotExprKind::Literal(literal_expr) => {
                 let bytecode_value = lit_to_value(literal_expr.clone());
 
-                if let BytecodeValue::Unit = bytecode_value {
-                    env.chunk.emit(Opcode::PushUnit);
-                } else {
-                    let const_index = module.constant_table.add_constant(bytecode_value)?;
-                    env.chunk.emit(Opcode::LoadConst(const_index));
+                match bytecode_value {
+                    BytecodeValue::Unit => env.chunk.emit(Opcode::PushUnit)?,
+                    BytecodeValue::None => env.chunk.emit(Opcode::PushNone)?,
+                    _ => {
+                        let const_index = module.constant_table.add_constant(bytecode_value)?;
+                        env.chunk.emit(Opcode::load_const(const_index))?
+                    }
+                };
+
+
+                if !value_used {
+                    env.chunk.emit(Opcode::Pop)?;
                 }
+            }
+            // values of results are stored as themselves, so only errors need wrapping
+            AnnotExprKind::Ok(value) => self.compile_expr(module, env, value, value_used)?,
+            AnnotExprKind::Propagate(value) => {
+                // none and errors are returned as they are, present values are already unwrapped
+                self.compile_expr(module, env, value, true)?;
+                env.chunk.emit(Opcode::Dup)?;
+
+                let jump_if_present = if value.ty.is_result() {
+                    env.chunk.emit(Opcode::IsErr)?;
+                    env.chunk.emit_jump(Opcode::jump_if_false(0))?
+                } else {
+                    env.chunk.emit(Opcode::PushNone)?;
+                    env.chunk.emit(Opcode::NotEqual)?;
+                    env.chunk.emit_jump(Opcode::jump_if_true(0))?
+                };
+                env.chunk.emit(Opcode::Return)?;
 
+                let present = env.chunk.len();
+                env.chunk.patch_jump(jump_if_present, present)?;
 
                 if !value_used {
-                    env.chunk.emit(Opcode::Pop);
+                    env.chunk.emit(Opcode::Pop)?;
                 }
             }
             AnnotExprKind::Struct(struct_expr) => todo!(),
+            AnnotExprKind::Try(value) => {
+                // a caught runtime error leaves its error on the stack in place of the value
+                let handler = env.chunk.emit_jump(Opcode::try_handler(0))?;
+                self.compile_expr(module, env, value, true)?;
+                env.chunk.emit(Opcode::EndTry)?;
+
+                let end = env.chunk.len();
+                env.chunk.patch_jump(handler, end)?;
+
+                if !value_used {
+                    env.chunk.emit(Opcode::Pop)?;
+                }
+            }
             AnnotExprKind::TupleLiteral(tuple_expr) => todo!(),
             AnnotExprKind::Unary(unary_expr) => {
                 self.compile_expr(module, env, &unary_expr.value, true)?;
@@ -222,28 +485,128 @@ impl ChunkBuilder {
                     _ => unreachable!(),
                 };
 
-                env.chunk.emit(opcode);
+                env.chunk.emit(opcode)?;
 
                 if !value_used {
-                    env.chunk.emit(Opcode::Pop);
+                    env.chunk.emit(Opcode::Pop)?;
                 }
             }
         }
 
+        env.chunk.set_span(outer_span);
+
         Ok(())
     }
 
+    /// Compiles a boolean expression that jumps when it evaluates to `jump_when` and falls through otherwise,
+    /// without materializing the boolean on the stack for `&&`, `||` and `!`.
+    ///
+    /// Returns the positions of the emitted jumps, which have to be patched with the target by the caller.
+    fn compile_condition(
+        &self,
+        module: &mut ModuleContext,
+        env: &mut ChunkBuilderEnv,
+        expr: &AnnotExpr,
+        jump_when: bool,
+    ) -> CompilerResult<Vec<JumpHandle>> {
+        let outer_span = env.chunk.set_span(Some(expr.span));
+
+        let jumps = match &expr.item {
+            AnnotExprKind::Group(expr) => self.compile_condition(module, env, expr, jump_when),
+            AnnotExprKind::Unary(unary_expr) if unary_expr.operator.kind == AnnotOperatorKind::Not => {
+                self.compile_condition(module, env, &unary_expr.value, !jump_when)
+            }
+            AnnotExprKind::Binary(binary_expr)
+                if matches!(binary_expr.operator.kind, AnnotOperatorKind::And | AnnotOperatorKind::Or) =>
+            {
+                // the value of the left operand that decides the whole expression on its own
+                let short_circuit_on = binary_expr.operator.kind == AnnotOperatorKind::Or;
+
+                if short_circuit_on == jump_when {
+                    // both operands jump to the same target
+                    let mut jumps = self.compile_condition(module, env, &binary_expr.left, jump_when)?;
+                    jumps.extend(self.compile_condition(module, env, &binary_expr.right, jump_when)?);
+
+                    Ok(jumps)
+                } else {
+                    // a deciding left operand skips the right one and falls through
+                    let skips = self.compile_condition(module, env, &binary_expr.left, short_circuit_on)?;
+                    let jumps = self.compile_condition(module, env, &binary_expr.right, jump_when)?;
+
+                    let mut end = env.chunk.len();
+                    for skip in skips {
+                        end = env.chunk.patch_jump(skip, end)?;
+                    }
+
+                    Ok(jumps)
+                }
+            }
+            _ => {
+                self.compile_expr(module, env, expr, true)?;
+
+                let jump = if jump_when {
+                    env.chunk.emit_jump(Opcode::jump_if_true(0))?
+                } else {
+                    env.chunk.emit_jump(Opcode::jump_if_false(0))?
+                };
+
+                Ok(vec![jump])
+            }
+        };
+
+        env.chunk.set_span(outer_span);
+
+        jumps
+    }
+
     fn emit_unit(
         &self,
         module: &mut ModuleContext,
         env: &mut ChunkBuilderEnv,
     ) -> CompilerResult<()> {
-        env.chunk.emit(Opcode::PushUnit);
+        env.chunk.emit(Opcode::PushUnit)?;
 
         Ok(())
     }
 }
 
+/// Where a variable lives, a local of the chunk being built or a global of the module
+#[derive(Clone, Copy)]
+enum Variable {
+    Local(LocalSlot),
+    Global(u32),
+}
+
+impl Variable {
+    fn resolve(module: &ModuleContext, env: &ChunkBuilderEnv, symbol_id: &usize) -> CompilerResult<Self> {
+        match module.global_table.get_global(symbol_id) {
+            Some(index) => Ok(Variable::Global(index)),
+            None => env.resolve_local_slot(symbol_id).map(Variable::Local),
+        }
+    }
+
+    fn get(self) -> Opcode {
+        match self {
+            Variable::Local(slot) => Opcode::get_local(slot),
+            Variable::Global(index) => Opcode::get_global(index),
+        }
+    }
+
+    fn set(self) -> Opcode {
+        match self {
+            Variable::Local(slot) => Opcode::set_local(slot),
+            Variable::Global(index) => Opcode::set_global(index),
+        }
+    }
+
+    fn reference(self) -> Opcode {
+        match self {
+            Variable::Local(slot) => Opcode::ref_local(slot),
+            Variable::Global(index) => Opcode::ref_global(index),
+        }
+    }
+}
+
 fn operator_to_opcode(op: AnnotOperatorKind) -> Opcode {
     match op {
         AnnotOperatorKind::Add => Opcode::Add,
@@ -286,7 +649,8 @@ fn lit_to_value(lit: LiteralAnnotExpr) -> BytecodeValue {
         },
         LiteralAnnotExpr::Bool(value) => BytecodeValue::Bool(value),
         LiteralAnnotExpr::Char(value) => BytecodeValue::Char(value),
-        LiteralAnnotExpr::String(value) => BytecodeValue::String(value),
+        LiteralAnnotExpr::String(value) => BytecodeValue::String(value.into()),
         LiteralAnnotExpr::Unit => BytecodeValue::Unit,
+        LiteralAnnotExpr::None => BytecodeValue::None,
     }
 }
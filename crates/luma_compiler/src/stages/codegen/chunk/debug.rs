@@ -0,0 +1,100 @@
+use luma_core::Span;
+
+use crate::stages::codegen::chunk::{InstrOffset, LocalSlot};
+
+/// Source information of a chunk, used to report runtime errors and stack traces
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct DebugInfo {
+    pub lines: LineTable,
+    pub locals: Vec<LocalInfo>,
+}
+
+/// Maps instruction offsets to the span they were compiled from.
+///
+/// The table is run-length encoded, an entry covers every instruction from its offset up to the next entry
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct LineTable {
+    entries: Vec<LineEntry>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineEntry {
+    pub offset: InstrOffset,
+    pub span: Span,
+}
+
+/// A named local variable and the instructions during which it occupies its slot
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalInfo {
+    pub name: String,
+    pub slot: LocalSlot,
+
+    /// offset of the first instruction the local is live at
+    pub start: InstrOffset,
+
+    /// offset of the first instruction after the local went out of scope
+    pub end: InstrOffset,
+}
+
+impl DebugInfo {
+    /// Moves every recorded offset to `relocate(offset)`, used when instructions change their position
+    pub fn relocate(&mut self, relocate: impl Fn(InstrOffset) -> InstrOffset) {
+        for entry in &mut self.lines.entries {
+            entry.offset = relocate(entry.offset);
+        }
+
+        for local in &mut self.locals {
+            local.start = relocate(local.start);
+            local.end = relocate(local.end);
+        }
+    }
+
+    /// Returns the locals whose slot is in use at the offset
+    pub fn locals_at(&self, offset: InstrOffset) -> impl Iterator<Item = &LocalInfo> {
+        self.locals
+            .iter()
+            .filter(move |local| (local.start..local.end).contains(&offset))
+    }
+}
+
+impl LineTable {
+    /// Records that the instructions starting at `offset` were compiled from `span`.
+    ///
+    /// Offsets have to be added in increasing order, a span equal to the previous one doesn't add a new entry
+    pub fn add(&mut self, offset: InstrOffset, span: Span) {
+        match self.entries.last_mut() {
+            Some(last) if last.span == span => {}
+            Some(last) if last.offset == offset => last.span = span,
+            _ => self.entries.push(LineEntry { offset, span }),
+        }
+    }
+
+    /// Returns the span of the instruction at `offset`
+    pub fn span_at(&self, offset: InstrOffset) -> Option<Span> {
+        let index = self.entries.partition_point(|entry| entry.offset <= offset);
+
+        index.checked_sub(1).map(|index| self.entries[index].span)
+    }
+
+    #[inline]
+    pub fn entries(&self) -> &[LineEntry] {
+        &self.entries
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl FromIterator<LineEntry> for LineTable {
+    fn from_iter<T: IntoIterator<Item = LineEntry>>(iter: T) -> Self {
+        let mut table = LineTable::default();
+
+        for entry in iter {
+            table.add(entry.offset, entry.span);
+        }
+
+        table
+    }
+}
@@ -0,0 +1,50 @@
+use std::fmt::Write;
+
+use crate::{
+    bytecode::BytecodeValue,
+    stages::codegen::chunk::{CodeChunk, FunctionChunk},
+};
+
+impl CodeChunk {
+    /// Renders the instructions in a human readable form, one instruction per line.
+    ///
+    /// Constants referenced by `LoadConst` are annotated with their value if they exist in `constants`
+    pub fn disassemble(&self, constants: &[BytecodeValue]) -> String {
+        let mut output = String::new();
+
+        for (offset, opcode) in self.iter() {
+            let _ = write!(output, "{offset:04}  {opcode}");
+
+            if let Some(slot) = opcode.local_slot().or(opcode.global_slot()) {
+                let _ = write!(output, " {slot}");
+            } else if let Some(slot) = opcode.const_slot() {
+                let _ = write!(output, " {slot}");
+
+                if let Some(value) = constants.get(slot as usize) {
+                    let _ = write!(output, "  ; {value:?}");
+                }
+            } else if let Some(target) = opcode.jump_target() {
+                let _ = write!(output, " -> {target:04}");
+            } else if let Some(index) = opcode.called_function().or(opcode.called_native()).or(opcode.concatenated()) {
+                let _ = write!(output, " {index}");
+            } else if let Some(intrinsic) = opcode.called_intrinsic() {
+                let _ = write!(output, " {intrinsic}");
+            }
+
+            output.push('\n');
+        }
+
+        output
+    }
+}
+
+impl FunctionChunk {
+    pub fn disassemble(&self, constants: &[BytecodeValue]) -> String {
+        format!(
+            "arity: {}, locals: {}\n{}",
+            self.arity,
+            self.code.max_locals,
+            self.code.disassemble(constants)
+        )
+    }
+}
@@ -2,9 +2,12 @@ use std::collections::HashMap;
 
 use luma_diagnostic::{CompilerResult, error};
 
-use crate::stages::codegen::{CodegenError, chunk::CodeChunk};
+use crate::stages::codegen::{
+    CodegenError,
+    chunk::{CodeChunk, InstrOffset, LocalInfo},
+};
 
-pub type LocalSlot = u16;
+pub type LocalSlot = u32;
 
 #[derive(Debug)]
 pub struct ChunkBuilderEnv {
@@ -13,6 +16,24 @@ pub struct ChunkBuilderEnv {
     /// maps local variables to their slot index
     /// symbol_id -> slot_index
     local_slots: HashMap<usize, LocalSlot>,
+
+    /// currently open scopes, innermost last
+    scopes: Vec<Scope>,
+
+    /// the next free slot, every slot below it belongs to a live local
+    next_slot: LocalSlot,
+}
+
+#[derive(Debug)]
+struct Scope {
+    /// the first slot used by the scope, every slot from here on is released when the scope ends
+    start_slot: LocalSlot,
+
+    /// locals declared in the scope, removed from `local_slots` when the scope ends
+    symbols: Vec<usize>,
+
+    /// indices of the scope's locals in the chunk's debug info, their live range ends with the scope
+    debug_locals: Vec<usize>,
 }
 
 impl ChunkBuilderEnv {
@@ -20,22 +41,78 @@ impl ChunkBuilderEnv {
         Self {
             chunk: CodeChunk::default(),
             local_slots: HashMap::new(),
+            scopes: vec![Scope {
+                start_slot: 0,
+                symbols: Vec::new(),
+                debug_locals: Vec::new(),
+            }],
+            next_slot: 0,
+        }
+    }
+
+    /// Opens a new scope, locals declared from now on are released by the matching [`Self::end_scope`]
+    pub fn begin_scope(&mut self) {
+        self.scopes.push(Scope {
+            start_slot: self.next_slot,
+            symbols: Vec::new(),
+            debug_locals: Vec::new(),
+        });
+    }
+
+    /// Closes the innermost scope, its slots are free to be reused by the following declarations
+    pub fn end_scope(&mut self) {
+        let scope = self
+            .scopes
+            .pop()
+            .expect("end_scope called without a matching begin_scope");
+
+        for symbol_id in scope.symbols {
+            self.local_slots.remove(&symbol_id);
+        }
+
+        let end = self.chunk.len();
+        for index in scope.debug_locals {
+            self.chunk.debug.locals[index].end = end;
         }
+
+        self.next_slot = scope.start_slot;
     }
 
-    /// Declares a new local variable and returns its slot index
-    pub fn declare_local(&mut self, symbol_id: usize) -> CompilerResult<LocalSlot> {
-        let slot_index = self.local_slots.len();
+    /// Closes the function scope and returns the finished chunk
+    pub fn finish(mut self) -> CodeChunk {
+        while !self.scopes.is_empty() {
+            self.end_scope();
+        }
 
-        if slot_index >= LocalSlot::MAX as usize {
+        self.chunk
+    }
+
+    /// Declares a new local variable in the innermost scope and returns its slot index.
+    ///
+    /// The local is live from the next emitted instruction until the scope ends
+    pub fn declare_local(&mut self, symbol_id: usize, name: &str) -> CompilerResult<LocalSlot> {
+        if self.next_slot == LocalSlot::MAX {
             return Err(error!(CodegenError::TooManyLocals));
         }
 
-        let slot_index = slot_index as LocalSlot;
+        let slot_index = self.next_slot;
+        self.next_slot += 1;
+
         self.local_slots.insert(symbol_id, slot_index);
 
-        // todo: proper max_locals counting with scope management
-        self.chunk.max_locals += 1;
+        // the end is only known once the scope is closed
+        self.chunk.debug.locals.push(LocalInfo {
+            name: name.to_string(),
+            slot: slot_index,
+            start: self.chunk.len(),
+            end: InstrOffset::MAX,
+        });
+
+        let scope = self.scopes.last_mut().expect("the function scope is never closed");
+        scope.symbols.push(symbol_id);
+        scope.debug_locals.push(self.chunk.debug.locals.len() - 1);
+
+        self.chunk.max_locals = self.chunk.max_locals.max(self.next_slot as usize);
 
         Ok(slot_index)
     }
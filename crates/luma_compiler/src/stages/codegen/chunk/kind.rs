@@ -1,56 +1,234 @@
+use luma_core::Span;
 use luma_diagnostic::{CompilerResult, error};
 
-use crate::{bytecode::Opcode, stages::codegen::CodegenError};
+use crate::{
+    bytecode::Opcode,
+    stages::codegen::{CodegenError, chunk::DebugInfo},
+};
 
-#[derive(Default, Debug, Clone, PartialEq)]
+/// Byte offset of an instruction within a chunk's instruction stream
+pub type InstrOffset = u32;
+
+/// Refers to a jump emitted through [`CodeChunk::emit_jump`] that still has to be patched.
+///
+/// Unlike a plain offset, a handle stays valid when other jumps are widened and the instructions move
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JumpHandle(usize);
+
+#[derive(Default, Debug, Clone)]
 pub struct CodeChunk {
-    instructions: Vec<Opcode>,
+    pub(super) code: Vec<u8>,
     pub max_locals: usize,
+    pub debug: DebugInfo,
+
+    /// span recorded for the instructions emitted from now on
+    pub(super) span: Option<Span>,
+
+    /// offset of the last emitted instruction
+    pub(super) last_offset: Option<InstrOffset>,
+
+    /// current offsets of the jumps handed out as [`JumpHandle`]s
+    pub(super) jumps: Vec<InstrOffset>,
 }
 
 impl CodeChunk {
-    pub fn emit(&mut self, opcode: Opcode) -> CompilerResult<u16> {
-        let index = self.instr_len();
-        if index == u16::MAX {
+    pub fn emit(&mut self, opcode: Opcode) -> CompilerResult<InstrOffset> {
+        let offset = self.len();
+
+        if offset as usize + opcode.encoded_len() > InstrOffset::MAX as usize {
             return Err(error!(CodegenError::ChunkTooLarge));
         }
 
-        self.instructions.push(opcode);
-        Ok(index)
+        opcode.encode(&mut self.code);
+        self.last_offset = Some(offset);
+
+        if let Some(span) = self.span {
+            self.debug.lines.add(offset, span);
+        }
+
+        Ok(offset)
     }
 
-    pub fn patch(&mut self, index: u16, opcode: Opcode) -> CompilerResult<()> {
-        if index >= self.instr_len() {
-            return Err(error!(CodegenError::InvalidPatchPosition { position: index }));
+    /// Creates a chunk from an already encoded instruction stream, like one read back from a serialized module
+    pub fn from_encoded(code: Vec<u8>, max_locals: usize, debug: DebugInfo) -> Self {
+        CodeChunk {
+            code,
+            max_locals,
+            debug,
+            ..Default::default()
         }
+    }
 
-        self.instructions[index as usize] = opcode;
-        Ok(())
+    /// Sets the span of the instructions emitted from now on, returns the previous one so it can be restored
+    pub fn set_span(&mut self, span: Option<Span>) -> Option<Span> {
+        std::mem::replace(&mut self.span, span)
     }
 
+    /// Emits a jump whose target is set later on through [`Self::patch_jump`]
+    pub fn emit_jump(&mut self, opcode: Opcode) -> CompilerResult<JumpHandle> {
+        let offset = self.emit(opcode)?;
+        self.jumps.push(offset);
+
+        Ok(JumpHandle(self.jumps.len() - 1))
+    }
+
+    /// Points the jump to `target`, keeping its kind of jump, and returns the offset of `target` afterwards.
+    ///
+    /// If the target doesn't fit in the jump's current operand it is widened, moving every following instruction.
+    /// Jump handles and already patched jumps are adjusted, other offsets handed out before are no longer valid.
+    /// Patch several jumps to the same target with the returned offset
+    pub fn patch_jump(&mut self, handle: JumpHandle, target: InstrOffset) -> CompilerResult<InstrOffset> {
+        let offset = self.jumps[handle.0];
+
+        let Some(mut opcode) = self.at(offset).filter(Opcode::is_jump) else {
+            return Err(error!(CodegenError::InvalidPatchPosition { position: offset }));
+        };
+
+        let old_len = opcode.encoded_len();
+        opcode.set_jump_target(target);
+
+        if opcode.encoded_len() == old_len {
+            let start = offset as usize;
+            let mut encoded = Vec::with_capacity(old_len);
+            opcode.encode(&mut encoded);
+
+            self.code[start..start + old_len].copy_from_slice(&encoded);
+            return Ok(target);
+        }
+
+        // the jump changes size, lay out the whole chunk again
+        let mut instructions = self.decode_instructions();
+        let index_of = self.index_map();
+
+        let (Some(index), Some(target_index)) = (index_of(offset), index_of(target)) else {
+            return Err(error!(CodegenError::InvalidPatchPosition { position: offset }));
+        };
+
+        instructions[index].set_jump_target(target_index as InstrOffset);
+
+        let jump_indices = self
+            .jumps
+            .iter()
+            .map(|&jump| index_of(jump).expect("jump handles always point at an instruction"))
+            .collect::<Vec<_>>();
+        let last_index = self.last_offset.and_then(&index_of);
+
+        let relocated = CodeChunk::assemble(&instructions, self.max_locals)?;
+        let mut offsets = relocated.instruction_offsets();
+        offsets.push(relocated.len());
+
+        // offsets that aren't instruction boundaries, like the end of locals still in scope, stay as they are
+        self.debug.relocate(|old| index_of(old).map_or(old, |index| offsets[index]));
+
+        self.code = relocated.code;
+        self.jumps = jump_indices.into_iter().map(|index| offsets[index]).collect();
+        self.last_offset = last_index.map(|index| offsets[index]);
+
+        Ok(offsets[target_index])
+    }
+
+    /// Byte length of the instruction stream, which is also the offset of the next emitted instruction
     #[inline]
-    pub const fn instr_len(&self) -> u16 {
-        self.instructions.len() as u16
+    pub const fn len(&self) -> InstrOffset {
+        self.code.len() as InstrOffset
     }
 
     #[inline]
     pub const fn is_empty(&self) -> bool {
-        self.instructions.is_empty()
+        self.code.is_empty()
+    }
+
+    /// Returns the last emitted instruction
+    #[inline]
+    pub fn last(&self) -> Option<Opcode> {
+        self.at(self.last_offset?)
+    }
+
+    /// Decodes the instruction starting at `offset`
+    #[inline]
+    pub fn at(&self, offset: InstrOffset) -> Option<Opcode> {
+        Opcode::decode(self.code.get(offset as usize..)?).map(|(opcode, _)| opcode)
     }
 
+    /// The encoded instruction stream
     #[inline]
-    pub fn last(&self) -> Option<&Opcode> {
-        self.instructions.last()
+    pub fn bytes(&self) -> &[u8] {
+        &self.code
     }
 
+    /// Iterates over the instructions along with their offsets
     #[inline]
-    pub fn at(&self, index: u16) -> Option<&Opcode> {
-        self.instructions.get(index as usize)
+    pub fn iter(&self) -> Instructions<'_> {
+        Instructions {
+            code: &self.code,
+            offset: 0,
+        }
+    }
+
+    /// Maps an offset to the index of the instruction starting there, the end of the chunk maps to the instruction count
+    pub(super) fn index_map(&self) -> impl Fn(InstrOffset) -> Option<usize> + use<> {
+        let mut offsets = self.instruction_offsets();
+        offsets.push(self.len());
+
+        move |offset| offsets.binary_search(&offset).ok()
+    }
+
+    pub(super) fn instruction_offsets(&self) -> Vec<InstrOffset> {
+        self.iter().map(|(offset, _)| offset).collect()
+    }
+}
+
+impl From<Vec<Opcode>> for CodeChunk {
+    /// Assembles a chunk from instructions whose jumps target instruction indices
+    fn from(instructions: Vec<Opcode>) -> Self {
+        // enough slots for every local referenced by the instructions
+        let max_locals = instructions
+            .iter()
+            .filter_map(|opcode| opcode.local_slot())
+            .map(|slot| slot as usize + 1)
+            .max()
+            .unwrap_or(0);
+
+        CodeChunk::assemble(&instructions, max_locals).expect("instructions exceed the chunk size limit")
+    }
+}
+
+impl PartialEq for CodeChunk {
+    /// Chunks are equal if they hold the same code and debug info, regardless of the state used while building them
+    fn eq(&self, other: &Self) -> bool {
+        self.code == other.code && self.max_locals == other.max_locals && self.debug == other.debug
+    }
+}
+
+/// Iterator over the instructions of a chunk, see [`CodeChunk::iter`].
+///
+/// Stops early at bytes that don't form a valid instruction
+pub struct Instructions<'chunk> {
+    code: &'chunk [u8],
+    offset: usize,
+}
+
+impl Iterator for Instructions<'_> {
+    type Item = (InstrOffset, Opcode);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (opcode, len) = Opcode::decode(self.code.get(self.offset..)?)?;
+        let offset = self.offset as InstrOffset;
+
+        self.offset += len;
+
+        Some((offset, opcode))
     }
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct FunctionChunk {
+    pub name: String,
     pub code: CodeChunk,
     pub arity: usize,
-}
\ No newline at end of file
+}
+
+impl FunctionChunk {
+    /// Name of the module's init chunk, which runs the top level statements
+    pub const INIT_NAME: &str = "<init>";
+}
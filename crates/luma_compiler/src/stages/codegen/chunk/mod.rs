@@ -1,7 +1,12 @@
+mod assemble;
 mod builder;
+mod debug;
+mod disassemble;
 mod env;
 mod kind;
 
+pub use assemble::DecodedDebugInfo;
 pub use builder::*;
+pub use debug::*;
 pub use env::*;
-pub use kind::*;
\ No newline at end of file
+pub use kind::*;
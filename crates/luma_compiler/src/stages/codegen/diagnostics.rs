@@ -2,19 +2,36 @@ use luma_diagnostic::define_diagnostics;
 
 define_diagnostics! {
     pub enum CodegenError {
-        #[Error("invalid instruction patch", "attempted to patch an instruction at an invalid position: {position}")]
+        #[Error(E0501, "invalid instruction patch", "attempted to patch an instruction at an invalid position: {position}")]
+        /// A jump was patched at an offset that holds no jump, this is a bug in the compiler.
         InvalidPatchPosition {
-            position: u16,
+            position: u32,
         },
-        #[Error("too many locals", "too many locals declared in a single chunk")]
+        #[Error(E0502, "too many locals", "too many locals declared in a single chunk")]
+        /// A function declares more locals than the VM can address, split it into smaller functions.
         TooManyLocals,
-        #[Error("too many constants", "too many constants in a single chunk")]
+        #[Error(E0503, "too many constants", "too many constants in a single chunk")]
+        /// A module uses more distinct constants than the VM can address, split it into several modules.
         TooManyConstants,
-        #[Error("chunk too large", "too many instructions in a single chunk")]
+        #[Error(E0504, "chunk too large", "too many instructions in a single chunk")]
+        /// A function compiles to more instructions than a jump can span, split it into smaller functions.
         ChunkTooLarge,
-        #[Error("undefined local", "local with symbol id {symbol_id} was not found")]
+        #[Error(E0505, "undefined local", "local with symbol id {symbol_id} was not found")]
+        /// A variable was read that has no slot in the current function, this is a bug in the compiler.
         UndefinedLocal {
             symbol_id: usize,
         },
+        #[Error(E0506, "undefined function", "function '{name}' is neither declared in the module nor as a native")]
+        /// The called function isn't declared by the module, an imported library or the host.
+        ///
+        /// Host functions have to be registered with `LumaCompiler::with_natives` to be callable.
+        UndefinedFunction {
+            name: String,
+        },
+        #[Error(E0507, "string too complex", "an interpolated string has {count} parts, at most 65535 are supported")]
+        /// An interpolated string has more parts than a single instruction can join, build it in several steps.
+        TooManyInterpolations {
+            count: usize,
+        },
     }
-}
\ No newline at end of file
+}
@@ -10,6 +10,9 @@ pub mod stores;
 
 pub use diagnostics::*;
 
+#[cfg(test)]
+mod tests;
+
 pub struct CodegenStage;
 
 impl CodegenStage {
@@ -31,7 +34,7 @@ impl CompilerStage<'_> for CodegenStage {
         let mut bytecodes = Vec::new();
 
         for ast in input {
-            let bytecode = match ModuleBuilder::generate(ast) {
+            let bytecode = match ModuleBuilder::generate(ast, &ctx.natives) {
                 Ok(bc) => bc,
                 Err(err) => {
                     ctx.add_diag(err);
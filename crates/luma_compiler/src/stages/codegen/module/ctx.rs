@@ -1,20 +1,32 @@
-use crate::stages::codegen::stores::{ConstantTable, ExportTable, FunctionTable};
+use luma_core::CodeSourceId;
+
+use crate::{
+    NativeSignature,
+    stages::codegen::stores::{ConstantTable, ExportTable, FunctionTable, GlobalTable, NativeTable, StructTable},
+};
 
 #[derive(Debug)]
 pub struct ModuleContext {
+    /// source of the module, declarations of imported libraries come from other sources
+    pub source_id: CodeSourceId,
     pub export_table: ExportTable,
     pub function_table: FunctionTable,
+    pub global_table: GlobalTable,
     pub constant_table: ConstantTable,
-
-    // TODO: implement struct table
+    pub native_table: NativeTable,
+    pub struct_table: StructTable,
 }
 
 impl ModuleContext {
-    pub fn new() -> Self {
+    pub fn new(source_id: CodeSourceId, natives: &[NativeSignature]) -> Self {
         Self {
+            source_id,
             export_table: ExportTable::new(),
             function_table: FunctionTable::new(),
+            global_table: GlobalTable::new(),
             constant_table: ConstantTable::new(),
+            native_table: NativeTable::new(natives),
+            struct_table: StructTable::new(),
         }
     }
 }
\ No newline at end of file
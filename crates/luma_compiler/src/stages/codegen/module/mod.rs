@@ -1,6 +1,6 @@
 use luma_diagnostic::CompilerResult;
 
-use crate::{aast::AnnotatedAst, bytecode::ModuleBytecode, stages::codegen::chunk::{ChunkBuilder, FunctionChunk}};
+use crate::{NativeSignature, aast::AnnotatedAst, bytecode::ModuleBytecode, stages::codegen::chunk::{ChunkBuilder, FunctionChunk}};
 
 mod ctx;
 
@@ -11,14 +11,15 @@ pub struct ModuleBuilder {
 }
 
 impl ModuleBuilder {
-    pub fn generate(mut ast: AnnotatedAst) -> CompilerResult<ModuleBytecode> {
-        let mut ctx = ModuleContext::new();
+    pub fn generate(mut ast: AnnotatedAst, natives: &[NativeSignature]) -> CompilerResult<ModuleBytecode> {
+        let mut ctx = ModuleContext::new(ast.span.source_id, natives);
         
         // build top level chunk into a function chunk
         // the function chunk is a specially reserved function that serves as the "init" function for the module
         let top_level_chunk = ChunkBuilder.build_top_level(&mut ctx, &mut ast.statements)?;
 
         let init_func = FunctionChunk {
+            name: FunctionChunk::INIT_NAME.to_string(),
             code: top_level_chunk,
             arity: 0,
         };
@@ -35,6 +36,10 @@ impl ModuleBuilder {
             source_id: ast.span.source_id,
             constants,
             functions,
+            natives: ctx.native_table.imports,
+            exports: ctx.export_table,
+            structs: ctx.struct_table.structs,
+            globals: ctx.global_table.globals,
         })
     }
 }
\ No newline at end of file
@@ -4,7 +4,7 @@ use luma_diagnostic::{CompilerResult, error};
 
 use crate::{bytecode::BytecodeValue, stages::codegen::CodegenError};
 
-pub type ConstSlot = u16;
+pub type ConstSlot = u32;
 
 #[derive(Debug)]
 pub struct ConstantTable {
@@ -1,11 +1,11 @@
 use std::collections::HashMap;
 
-use crate::SymbolId;
-
-#[derive(Debug)]
+/// Items of a module that the host can look up by name
+#[derive(Default, Debug, Clone, PartialEq)]
 pub struct ExportTable {
-    pub functions: HashMap<SymbolId, u16>,
-    pub variables: HashMap<SymbolId, u16>,
+    /// name -> index in the module's function table
+    pub functions: HashMap<String, u32>,
+    pub variables: HashMap<String, u32>,
 }
 
 impl ExportTable {
@@ -16,27 +16,27 @@ impl ExportTable {
         }
     }
 
-    pub fn add_function(&mut self, symbol: SymbolId, index: u16) {
-        self.functions.insert(symbol, index);
+    pub fn add_function(&mut self, name: String, index: u32) {
+        self.functions.insert(name, index);
     }
 
-    pub fn get_function(&self, symbol: &SymbolId) -> Option<u16> {
-        self.functions.get(symbol).copied()
+    pub fn get_function(&self, name: &str) -> Option<u32> {
+        self.functions.get(name).copied()
     }
 
-    pub fn get_functions(&self) -> &HashMap<SymbolId, u16> {
+    pub fn get_functions(&self) -> &HashMap<String, u32> {
         &self.functions
     }
 
-    pub fn add_variable(&mut self, symbol: SymbolId, index: u16) {
-        self.variables.insert(symbol, index);
+    pub fn add_variable(&mut self, name: String, index: u32) {
+        self.variables.insert(name, index);
     }
 
-    pub fn get_variable(&self, symbol: &SymbolId) -> Option<u16> {
-        self.variables.get(symbol).copied()
+    pub fn get_variable(&self, name: &str) -> Option<u32> {
+        self.variables.get(name).copied()
     }
-    
-    pub fn get_variables(&self) -> &HashMap<SymbolId, u16> {
+
+    pub fn get_variables(&self) -> &HashMap<String, u32> {
         &self.variables
     }
-}
\ No newline at end of file
+}
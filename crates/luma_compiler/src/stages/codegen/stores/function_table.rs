@@ -9,6 +9,9 @@ pub struct FunctionTable {
 }
 
 impl FunctionTable {
+    /// Index of the first function in the module's function table, the init chunk comes before it
+    pub const FIRST_MODULE_INDEX: usize = 1;
+
     pub fn new() -> Self {
         Self {
             functions: Vec::new(),
@@ -23,7 +26,31 @@ impl FunctionTable {
         index
     }
 
+    /// Reserves an index for the function before its body is compiled, so that the body can call it.
+    /// Declaring a function again returns the index it already has
+    pub fn declare_function(&mut self, symbol_id: SymbolId, name: &str, arity: usize) -> usize {
+        if let Some(&index) = self.lookup.get(&symbol_id) {
+            return index;
+        }
+
+        self.add_function(symbol_id, FunctionChunk {
+            name: name.to_string(),
+            code: Default::default(),
+            arity,
+        })
+    }
+
+    /// Replaces the chunk of a function reserved with [`Self::declare_function`]
+    pub fn define_function(&mut self, index: usize, chunk: FunctionChunk) {
+        self.functions[index] = chunk;
+    }
+
     pub fn get_function(&self, symbol_id: &SymbolId) -> Option<&FunctionChunk> {
         self.lookup.get(symbol_id).and_then(|&index| self.functions.get(index))
     }
-}
\ No newline at end of file
+
+    /// Returns the index of the function in the module's function table
+    pub fn get_module_index(&self, symbol_id: &SymbolId) -> Option<usize> {
+        self.lookup.get(symbol_id).map(|&index| index + Self::FIRST_MODULE_INDEX)
+    }
+}
@@ -0,0 +1,32 @@
+use std::collections::HashMap;
+
+use crate::{SymbolId, TypeKind, bytecode::GlobalLayout};
+
+/// Module-level variables, which live as long as the module rather than in the init chunk's frame
+#[derive(Debug)]
+pub struct GlobalTable {
+    pub globals: Vec<GlobalLayout>,
+    lookup: HashMap<SymbolId, u32>,
+}
+
+impl GlobalTable {
+    pub fn new() -> Self {
+        Self {
+            globals: Vec::new(),
+            lookup: HashMap::new(),
+        }
+    }
+
+    /// Reserves the global's slot before any function is compiled, so that functions declared earlier can use it
+    pub fn declare_global(&mut self, symbol_id: SymbolId, name: &str, ty: TypeKind) -> u32 {
+        let index = self.globals.len() as u32;
+        self.globals.push(GlobalLayout { name: name.to_string(), ty });
+        self.lookup.insert(symbol_id, index);
+        index
+    }
+
+    /// Returns the slot of the global in the module's globals
+    pub fn get_global(&self, symbol_id: &SymbolId) -> Option<u32> {
+        self.lookup.get(symbol_id).copied()
+    }
+}
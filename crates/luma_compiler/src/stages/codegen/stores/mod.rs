@@ -1,9 +1,15 @@
 mod constant_table;
 mod export_table;
 mod function_table;
+mod global_table;
+mod native_table;
 mod signature_table;
+mod struct_table;
 
 pub use constant_table::ConstantTable;
 pub use export_table::ExportTable;
 pub use function_table::FunctionTable;
-pub use signature_table::SignatureTable;
\ No newline at end of file
+pub use global_table::GlobalTable;
+pub use native_table::NativeTable;
+pub use signature_table::SignatureTable;
+pub use struct_table::StructTable;
\ No newline at end of file
@@ -0,0 +1,39 @@
+use std::collections::HashMap;
+
+use crate::NativeSignature;
+
+/// Host functions declared to the compiler and the ones the module imports
+#[derive(Debug)]
+pub struct NativeTable {
+    /// names of the imported natives, indexed by `CallNative`
+    pub imports: Vec<String>,
+    lookup: HashMap<String, u32>,
+    declared: Vec<String>,
+}
+
+impl NativeTable {
+    pub fn new(declared: &[NativeSignature]) -> Self {
+        Self {
+            imports: Vec::new(),
+            lookup: HashMap::new(),
+            declared: declared.iter().map(|native| native.name.clone()).collect(),
+        }
+    }
+
+    /// Imports a declared native (if it isn't already imported) and returns its index, `None` if it isn't declared
+    pub fn import(&mut self, name: &str) -> Option<u32> {
+        if let Some(&index) = self.lookup.get(name) {
+            return Some(index);
+        }
+
+        if !self.declared.iter().any(|declared| declared == name) {
+            return None;
+        }
+
+        let index = self.imports.len() as u32;
+        self.imports.push(name.to_string());
+        self.lookup.insert(name.to_string(), index);
+
+        Some(index)
+    }
+}
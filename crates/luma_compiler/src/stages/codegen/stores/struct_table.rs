@@ -0,0 +1,19 @@
+use crate::bytecode::{FieldLayout, StructLayout};
+
+#[derive(Debug)]
+pub struct StructTable {
+    pub structs: Vec<StructLayout>,
+}
+
+impl StructTable {
+    pub fn new() -> Self {
+        Self { structs: Vec::new() }
+    }
+
+    pub fn add_struct(&mut self, name: String, fields: impl IntoIterator<Item = FieldLayout>) {
+        self.structs.push(StructLayout {
+            name,
+            fields: fields.into_iter().collect(),
+        });
+    }
+}
@@ -0,0 +1,159 @@
+use pretty_assertions::assert_eq;
+
+use crate::stages::codegen::tests::compile_module;
+
+#[test]
+fn and_skips_right_operand() {
+    let module = compile_module(
+        r#"
+        var x = 2;
+        var ok = x != 0 && 10 / x > 1;
+    "#,
+    );
+
+    assert_eq!(
+        module.disassemble(),
+        "\
+fn#0 arity: 0, locals: 0
+0000  LoadConst 0  ; Int32(2)
+0003  SetGlobal 0
+0006  GetGlobal 0
+0009  LoadConst 1  ; Int32(0)
+0012  NotEqual
+0013  Dup
+0014  JumpIfFalse -> 0029
+0017  Pop
+0018  LoadConst 2  ; Int32(10)
+0021  GetGlobal 0
+0024  Div
+0025  LoadConst 3  ; Int32(1)
+0028  GreaterThan
+0029  SetGlobal 1
+0032  PushUnit
+0033  Return
+"
+    );
+}
+
+#[test]
+fn or_skips_right_operand() {
+    let module = compile_module(
+        r#"
+        var a = true;
+        var b = false;
+        var c = a || b;
+    "#,
+    );
+
+    assert_eq!(
+        module.disassemble(),
+        "\
+fn#0 arity: 0, locals: 0
+0000  LoadConst 0  ; Bool(true)
+0003  SetGlobal 0
+0006  LoadConst 1  ; Bool(false)
+0009  SetGlobal 1
+0012  GetGlobal 0
+0015  Dup
+0016  JumpIfTrue -> 0023
+0019  Pop
+0020  GetGlobal 1
+0023  SetGlobal 2
+0026  PushUnit
+0027  Return
+"
+    );
+}
+
+#[test]
+fn if_condition_jumps_directly() {
+    let module = compile_module(
+        r#"
+        var a = true;
+        var b = false;
+        var r = if a && b { 1 } else { 2 };
+    "#,
+    );
+
+    assert_eq!(
+        module.disassemble(),
+        "\
+fn#0 arity: 0, locals: 0
+0000  LoadConst 0  ; Bool(true)
+0003  SetGlobal 0
+0006  LoadConst 1  ; Bool(false)
+0009  SetGlobal 1
+0012  GetGlobal 0
+0015  JumpIfFalse -> 0030
+0018  GetGlobal 1
+0021  JumpIfFalse -> 0030
+0024  LoadConst 2  ; Int32(1)
+0027  Jump -> 0033
+0030  LoadConst 3  ; Int32(2)
+0033  SetGlobal 2
+0036  PushUnit
+0037  Return
+"
+    );
+}
+
+#[test]
+fn if_condition_with_or_and_not() {
+    let module = compile_module(
+        r#"
+        var a = true;
+        var b = false;
+        var r = if !a || (b && a) { 1 } else { 2 };
+    "#,
+    );
+
+    assert_eq!(
+        module.disassemble(),
+        "\
+fn#0 arity: 0, locals: 0
+0000  LoadConst 0  ; Bool(true)
+0003  SetGlobal 0
+0006  LoadConst 1  ; Bool(false)
+0009  SetGlobal 1
+0012  GetGlobal 0
+0015  JumpIfFalse -> 0030
+0018  GetGlobal 1
+0021  JumpIfFalse -> 0036
+0024  GetGlobal 0
+0027  JumpIfFalse -> 0036
+0030  LoadConst 2  ; Int32(1)
+0033  Jump -> 0039
+0036  LoadConst 3  ; Int32(2)
+0039  SetGlobal 2
+0042  PushUnit
+0043  Return
+"
+    );
+}
+
+#[test]
+fn if_without_else_skips_then_branch() {
+    let module = compile_module(
+        r#"
+        var a = true;
+        if a {
+            var z = 1;
+        };
+    "#,
+    );
+
+    assert_eq!(
+        module.disassemble(),
+        "\
+fn#0 arity: 0, locals: 1
+0000  LoadConst 0  ; Bool(true)
+0003  SetGlobal 0
+0006  GetGlobal 0
+0009  JumpIfFalse -> 0018
+0012  LoadConst 1  ; Int32(1)
+0015  SetLocal 0
+0018  PushUnit
+0019  Return
+"
+    );
+}
@@ -0,0 +1,202 @@
+use luma_core::{CodeSource, CodeSourceId, Span};
+use pretty_assertions::assert_eq;
+
+use crate::{
+    CompilerOptions, LumaCompiler,
+    bytecode::{FORMAT_VERSION, MAGIC, ModuleBytecode, Opcode},
+    stages::{
+        codegen::chunk::{CodeChunk, FunctionChunk, LineEntry},
+        optimizer::{OptimizationLevel, OptimizerOptions},
+    },
+};
+
+/// Compiles the source keeping the real spans, so the debug info can be checked against the source
+fn compile(src: &str, level: OptimizationLevel) -> ModuleBytecode {
+    let compiler = LumaCompiler::configure(CompilerOptions {
+        optimizer: OptimizerOptions {
+            level,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+
+    let result = compiler.compile([CodeSource::from(src)]);
+
+    assert!(!result.has_errors(), "compilation failed: {:#?}", result.diagnostics);
+
+    result
+        .result
+        .and_then(|modules| modules.into_iter().next())
+        .expect("expected at least one module")
+}
+
+/// Renders every instruction next to the source text its span covers
+fn source_map(src: &str, func: &FunctionChunk) -> String {
+    func.code
+        .iter()
+        .map(|(offset, opcode)| {
+            let text = func
+                .code
+                .debug
+                .lines
+                .span_at(offset)
+                .map_or("", |span| &src[span.start as usize..span.end as usize]);
+
+            format!("{offset:04}  {opcode:<12}{text}\n")
+        })
+        .collect()
+}
+
+/// Renders the locals with their slot and live range
+fn locals(func: &FunctionChunk) -> String {
+    func.code
+        .debug
+        .locals
+        .iter()
+        .map(|local| format!("{} @{} {:04}..{:04}\n", local.name, local.slot, local.start, local.end))
+        .collect()
+}
+
+#[test]
+fn instructions_map_to_their_expression() {
+    let src = "var x = 1 + 2; var y = x * 3;";
+    let module = compile(src, OptimizationLevel::None);
+    let init = &module.functions[0];
+
+    assert_eq!(
+        source_map(src, init),
+        "\
+0000  LoadConst   1
+0003  LoadConst   2
+0006  Add         1 + 2
+0007  SetGlobal   var x = 1 + 2
+0010  GetGlobal   x
+0013  LoadConst   3
+0016  Mul         x * 3
+0017  SetGlobal   var y = x * 3
+0020  PushUnit    var y = x * 3
+0021  Return      var y = x * 3
+"
+    );
+
+    // consecutive instructions of the same span share an entry
+    assert_eq!(init.code.debug.lines.entries().len(), 8);
+}
+
+#[test]
+fn locals_keep_names_and_live_ranges() {
+    let src = r#"
+        func area(w: i32, h: i32): i32 {
+            var a = { var t = w * h; t };
+            var b = { var u = a; u };
+            b
+        };
+    "#;
+    let module = compile(src, OptimizationLevel::None);
+
+    assert_eq!(module.functions[1].name, "area");
+    assert_eq!(module.functions[0].name, FunctionChunk::INIT_NAME);
+    assert_eq!(
+        locals(&module.functions[1]),
+        "\
+w @0 0000..0032
+h @1 0000..0032
+t @2 0007..0013
+a @2 0013..0031
+u @3 0019..0025
+b @3 0025..0031
+"
+    );
+}
+
+#[test]
+fn optimizer_keeps_debug_info_in_sync() {
+    let src = "func pick(c: bool): i32 { var unused = 5; var x = 7; if !c { x } else { 1 } };";
+    let module = compile(src, OptimizationLevel::Full);
+    let func = &module.functions[1];
+
+    assert_eq!(
+        source_map(src, func),
+        "\
+0000  LoadConst   7
+0003  SetLocal    var x = 7
+0006  GetLocal    c
+0009  JumpIfTrue  c
+0012  GetLocal    x
+0015  Return      if !c { x } else { 1 }
+0016  LoadConst   1
+0019  Return      { var unused = 5; var x = 7; if !c { x } else { 1 } }
+"
+    );
+    assert_eq!(
+        locals(func),
+        "\
+c @0 0000..0020
+x @1 0003..0019
+"
+    );
+}
+
+#[test]
+fn debug_info_survives_serialization() {
+    let src = r#"
+        func add(a: i32, b: i32): i32 {
+            var sum = a + b;
+            sum
+        };
+        var text = "hi";
+        var ok = 1.5 > 2.0 || 'c' == 'c';
+    "#;
+    let module = compile(src, OptimizationLevel::Basic);
+
+    let bytes = module.to_bytes();
+    let restored = ModuleBytecode::from_bytes(&bytes).expect("module should deserialize");
+
+    assert_eq!(restored, module);
+    assert_eq!(restored.functions[1].code.debug, module.functions[1].code.debug);
+    assert!(!restored.functions[1].code.debug.lines.is_empty());
+
+    // truncated or trailing bytes are rejected
+    assert_eq!(ModuleBytecode::from_bytes(&bytes[..bytes.len() - 1]), None);
+    assert_eq!(ModuleBytecode::from_bytes(&[bytes.as_slice(), &[0]].concat()), None);
+    assert_eq!(ModuleBytecode::from_bytes(b"LUMB"), None);
+}
+
+#[test]
+fn deeply_nested_constants_and_types_are_rejected() {
+    let header = [MAGIC.as_slice(), &FORMAT_VERSION.to_le_bytes(), &0u32.to_le_bytes()].concat();
+    let count = |count: u32| count.to_le_bytes();
+
+    // a single constant of `err(err(..))` nested two million times
+    let constant = [header.as_slice(), &count(1), &[18; 2_000_000]].concat();
+    assert_eq!(ModuleBytecode::from_bytes(&constant), None);
+
+    // no constants, functions, natives, exports or structs, then a global of type `i32??..?`
+    let sections = [0; 6].map(count).concat();
+    let global = [header.as_slice(), &sections, &count(1), &count(1), b"g", &[19; 2_000_000]].concat();
+    assert_eq!(ModuleBytecode::from_bytes(&global), None);
+}
+
+#[test]
+fn widening_a_jump_relocates_debug_info() {
+    let first = Span::new(CodeSourceId::ZERO, 0, 1);
+    let second = Span::new(CodeSourceId::ZERO, 1, 2);
+    let mut chunk = CodeChunk::default();
+
+    chunk.set_span(Some(first));
+    let jump = chunk.emit_jump(Opcode::jump(0)).unwrap();
+
+    chunk.set_span(Some(second));
+    for _ in 0..70_000 {
+        chunk.emit(Opcode::PushUnit).unwrap();
+    }
+
+    let end = chunk.len();
+    chunk.patch_jump(jump, end).unwrap();
+
+    assert_eq!(chunk.at(0), Some(Opcode::JumpWide(70_005)));
+    assert_eq!(
+        chunk.debug.lines.entries(),
+        [LineEntry { offset: 0, span: first }, LineEntry { offset: 5, span: second }]
+    );
+}
@@ -0,0 +1,77 @@
+use pretty_assertions::assert_eq;
+
+use crate::{bytecode::Opcode, stages::codegen::chunk::CodeChunk};
+
+#[test]
+fn operands_are_little_endian() {
+    let mut bytes = Vec::new();
+
+    Opcode::LoadConst(0x1234).encode(&mut bytes);
+    Opcode::JumpWide(0x0001_0000).encode(&mut bytes);
+    Opcode::Add.encode(&mut bytes);
+
+    assert_eq!(bytes, [0x06, 0x34, 0x12, 0x88, 0x00, 0x00, 0x01, 0x00, 0x10]);
+}
+
+#[test]
+fn decode_round_trips() {
+    let opcodes = [
+        Opcode::GetLocal(3),
+        Opcode::SetLocalWide(70_000),
+        Opcode::LoadConst(65_535),
+        Opcode::JumpIfFalse(12),
+        Opcode::JumpIfTrueWide(u32::MAX),
+        Opcode::Not,
+        Opcode::Return,
+    ];
+
+    let mut bytes = Vec::new();
+    for opcode in &opcodes {
+        opcode.encode(&mut bytes);
+    }
+
+    let mut decoded = Vec::new();
+    let mut rest = bytes.as_slice();
+
+    while let Some((opcode, len)) = Opcode::decode(rest) {
+        assert_eq!(len, opcode.encoded_len());
+
+        decoded.push(opcode);
+        rest = &rest[len..];
+    }
+
+    assert!(rest.is_empty());
+    assert_eq!(decoded, opcodes);
+}
+
+#[test]
+fn invalid_bytes_are_rejected() {
+    assert_eq!(Opcode::decode(&[]), None);
+    assert_eq!(Opcode::decode(&[0xFF]), None);
+
+    // operand cut off
+    assert_eq!(Opcode::decode(&[0x06, 0x01]), None);
+}
+
+#[test]
+fn assemble_converts_indices_to_offsets() {
+    let instructions = vec![
+        Opcode::GetLocal(0),
+        Opcode::JumpIfFalse(3),
+        Opcode::PushUnit,
+        Opcode::Return,
+    ];
+
+    let chunk = CodeChunk::from(instructions.clone());
+
+    assert_eq!(
+        chunk.iter().collect::<Vec<_>>(),
+        [
+            (0, Opcode::GetLocal(0)),
+            (3, Opcode::JumpIfFalse(7)),
+            (6, Opcode::PushUnit),
+            (7, Opcode::Return),
+        ]
+    );
+    assert_eq!(chunk.decode_instructions(), instructions);
+}
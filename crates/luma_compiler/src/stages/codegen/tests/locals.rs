@@ -0,0 +1,196 @@
+use pretty_assertions::assert_eq;
+
+use crate::stages::codegen::tests::compile_module;
+
+#[test]
+fn sibling_blocks_reuse_slots() {
+    let module = compile_module(
+        r#"
+        {
+            var a = 1;
+            var b = 2;
+        };
+        {
+            var c = 3;
+        };
+        var d = 4;
+    "#,
+    );
+
+    assert_eq!(
+        module.disassemble(),
+        "\
+fn#0 arity: 0, locals: 2
+0000  LoadConst 0  ; Int32(1)
+0003  SetLocal 0
+0006  LoadConst 1  ; Int32(2)
+0009  SetLocal 1
+0012  LoadConst 2  ; Int32(3)
+0015  SetLocal 0
+0018  LoadConst 3  ; Int32(4)
+0021  SetGlobal 0
+0024  PushUnit
+0025  Return
+"
+    );
+}
+
+#[test]
+fn max_locals_is_the_peak() {
+    let module = compile_module(
+        r#"
+        var a = 1;
+        {
+            var b = 2;
+            {
+                var c = 3;
+            };
+        };
+        {
+            var d = 4;
+        };
+    "#,
+    );
+
+    assert_eq!(
+        module.disassemble(),
+        "\
+fn#0 arity: 0, locals: 2
+0000  LoadConst 0  ; Int32(1)
+0003  SetGlobal 0
+0006  LoadConst 1  ; Int32(2)
+0009  SetLocal 0
+0012  LoadConst 2  ; Int32(3)
+0015  SetLocal 1
+0018  LoadConst 3  ; Int32(4)
+0021  SetLocal 0
+0024  PushUnit
+0025  Return
+"
+    );
+}
+
+#[test]
+fn shadowed_local_gets_own_slot() {
+    let module = compile_module(
+        r#"
+        var a = 1;
+        {
+            var a = 2;
+            a;
+        };
+        a;
+    "#,
+    );
+
+    assert_eq!(
+        module.disassemble(),
+        "\
+fn#0 arity: 0, locals: 1
+0000  LoadConst 0  ; Int32(1)
+0003  SetGlobal 0
+0006  LoadConst 1  ; Int32(2)
+0009  SetLocal 0
+0012  GetLocal 0
+0015  Pop
+0016  GetGlobal 0
+0019  Pop
+0020  PushUnit
+0021  Return
+"
+    );
+}
+
+#[test]
+fn initializer_block_shares_slot_with_variable() {
+    let module = compile_module(
+        r#"
+        var a = {
+            var tmp = 1;
+            tmp
+        };
+    "#,
+    );
+
+    assert_eq!(
+        module.disassemble(),
+        "\
+fn#0 arity: 0, locals: 1
+0000  LoadConst 0  ; Int32(1)
+0003  SetLocal 0
+0006  GetLocal 0
+0009  SetGlobal 0
+0012  PushUnit
+0013  Return
+"
+    );
+}
+
+#[test]
+fn if_branches_share_slots() {
+    let module = compile_module(
+        r#"
+        var t = true;
+        var r = if t {
+            var a = 1;
+            a
+        } else {
+            var b = 2;
+            var c = 3;
+            c
+        };
+    "#,
+    );
+
+    assert_eq!(
+        module.disassemble(),
+        "\
+fn#0 arity: 0, locals: 2
+0000  LoadConst 0  ; Bool(true)
+0003  SetGlobal 0
+0006  GetGlobal 0
+0009  JumpIfFalse -> 0024
+0012  LoadConst 1  ; Int32(1)
+0015  SetLocal 0
+0018  GetLocal 0
+0021  Jump -> 0039
+0024  LoadConst 2  ; Int32(2)
+0027  SetLocal 0
+0030  LoadConst 3  ; Int32(3)
+0033  SetLocal 1
+0036  GetLocal 1
+0039  SetGlobal 1
+0042  PushUnit
+0043  Return
+"
+    );
+}
+
+#[test]
+fn parameters_occupy_first_slots() {
+    let module = compile_module(
+        r#"
+        func add(a: i32, b: i32): i32 {
+            var sum = a + b;
+            sum
+        };
+    "#,
+    );
+
+    assert_eq!(
+        module.disassemble(),
+        "\
+fn#0 arity: 0, locals: 0
+0000  PushUnit
+0001  Return
+
+fn#1 arity: 2, locals: 3
+0000  GetLocal 0
+0003  GetLocal 1
+0006  Add
+0007  SetLocal 2
+0010  GetLocal 2
+0013  Return
+"
+    );
+}
@@ -0,0 +1,40 @@
+use luma_core::CodeSource;
+
+use crate::{
+    CompilerOptions, LumaCompiler,
+    bytecode::ModuleBytecode,
+    stages::{
+        lexer::LexerOptions,
+        optimizer::{OptimizationLevel, OptimizerOptions},
+    },
+};
+
+pub mod conditions;
+pub mod debug;
+pub mod encoding;
+pub mod locals;
+pub mod wide;
+
+/// Compiles the source without any optimizations so the raw codegen output can be inspected
+pub fn compile_module(src: &str) -> ModuleBytecode {
+    let compiler = LumaCompiler::configure(CompilerOptions {
+        lexer: LexerOptions {
+            zeroed_spans: true,
+            ..Default::default()
+        },
+        optimizer: OptimizerOptions {
+            level: OptimizationLevel::None,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+
+    let result = compiler.compile([CodeSource::from(src)]);
+
+    assert!(!result.has_errors(), "compilation failed: {:#?}", result.diagnostics);
+
+    result
+        .result
+        .and_then(|modules| modules.into_iter().next())
+        .expect("expected at least one module")
+}
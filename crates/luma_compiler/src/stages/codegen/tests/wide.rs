@@ -0,0 +1,62 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    bytecode::Opcode,
+    stages::codegen::{chunk::CodeChunk, tests::compile_module},
+};
+
+#[test]
+fn narrow_form_when_operand_fits() {
+    assert_eq!(Opcode::load_const(7), Opcode::LoadConst(7));
+    assert_eq!(Opcode::get_local(65_535), Opcode::GetLocal(65_535));
+    assert_eq!(Opcode::set_local(65_536), Opcode::SetLocalWide(65_536));
+    assert_eq!(Opcode::jump_if_false(100_000), Opcode::JumpIfFalseWide(100_000));
+}
+
+#[test]
+fn patching_switches_jump_width() {
+    let mut chunk = CodeChunk::default();
+
+    let skip_all = chunk.emit_jump(Opcode::jump_if_true(0)).unwrap();
+    let skip_one = chunk.emit_jump(Opcode::jump(0)).unwrap();
+    chunk.emit(Opcode::PushUnit).unwrap();
+
+    let after_first = chunk.len();
+    chunk.patch_jump(skip_one, after_first).unwrap();
+
+    for _ in 0..70_000 {
+        chunk.emit(Opcode::PushUnit).unwrap();
+    }
+
+    // widening the first jump moves everything after it by two bytes
+    let end = chunk.len();
+    chunk.patch_jump(skip_all, end).unwrap();
+
+    assert_eq!(chunk.at(0), Some(Opcode::JumpIfTrueWide(70_009)));
+    assert_eq!(chunk.at(5), Some(Opcode::Jump(9)));
+    assert_eq!(chunk.len(), 70_009);
+
+    // and narrowing it again moves them back
+    chunk.patch_jump(skip_all, 9).unwrap();
+
+    assert_eq!(chunk.at(0), Some(Opcode::JumpIfTrue(7)));
+    assert_eq!(chunk.at(3), Some(Opcode::Jump(7)));
+    assert_eq!(chunk.len(), 70_007);
+}
+
+#[test]
+fn constants_beyond_u16_use_wide_loads() {
+    let mut src = String::from("var a = 0;\n");
+    for n in 1..=70_000 {
+        src.push_str(&format!("a = {n};\n"));
+    }
+
+    let module = compile_module(&src);
+    let init = module.get_init_chunk().expect("expected an init chunk");
+    let instructions = init.code.iter().map(|(_, opcode)| opcode).collect::<Vec<_>>();
+
+    assert_eq!(module.constants.len(), 70_001);
+    assert_eq!(instructions[1], Opcode::SetGlobal(0));
+    assert_eq!(instructions[2], Opcode::LoadConst(1));
+    assert_eq!(instructions[instructions.len() - 4], Opcode::LoadConstWide(70_000));
+}
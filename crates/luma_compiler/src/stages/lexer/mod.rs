@@ -46,6 +46,9 @@ struct Tokenizer<'a> {
     span_start: u32,
     lexeme: String,
 
+    /// brace depth of each interpolated expression being scanned, innermost last
+    interpolations: Vec<u32>,
+
     options: &'a LexerOptions,
 }
 
@@ -60,6 +63,8 @@ impl<'a> Tokenizer<'a> {
             span_start: 0,
             lexeme: String::new(),
 
+            interpolations: Vec::new(),
+
             options,
         }
     }
@@ -134,10 +139,29 @@ impl<'a> Tokenizer<'a> {
             }
             '(' => TokenKind::LeftParen,
             ')' => TokenKind::RightParen,
-            '{' => TokenKind::LeftBrace,
-            '}' => TokenKind::RightBrace,
+            '{' => {
+                if let Some(depth) = self.interpolations.last_mut() {
+                    *depth += 1;
+                }
+
+                TokenKind::LeftBrace
+            }
+            '}' => match self.interpolations.last_mut() {
+                Some(0) => {
+                    // end of an interpolated expression, the string continues
+                    self.interpolations.pop();
+                    self.scan_string_literal(true)
+                }
+                Some(depth) => {
+                    *depth -= 1;
+                    TokenKind::RightBrace
+                }
+                None => TokenKind::RightBrace,
+            },
             '[' => TokenKind::LeftBracket,
             ']' => TokenKind::RightBracket,
+            '?' => TokenKind::Question,
+            '@' => TokenKind::At,
             '+' => match_next!('=' => TokenKind::PlusEqual, else => TokenKind::Plus),
             '-' => match_next!('=' => TokenKind::MinusEqual, else => TokenKind::Minus),
             '/' => match_next!(
@@ -190,7 +214,7 @@ impl<'a> Tokenizer<'a> {
                 else => TokenKind::Caret
             ),
             '\'' => self.scan_char_literal(),
-            '"' => self.scan_string_literal(),
+            '"' => self.scan_string_literal(false),
             '0'..='9' => self.scan_numeric_literal(c),
             _ => self.scan_identifier_or_keyword(),
         })
@@ -242,8 +266,11 @@ impl<'a> Tokenizer<'a> {
         TokenKind::CharLiteral
     }
 
-    /// Scan a string literal.
-    fn scan_string_literal(&mut self) -> TokenKind {
+    /// Scan a string literal, or a segment of an interpolated one.
+    ///
+    /// A `{` ends the segment and the tokens of the interpolated expression follow,
+    /// `continued` is set when resuming after its closing `}`.
+    fn scan_string_literal(&mut self, continued: bool) -> TokenKind {
         let mut builder = String::new();
 
         while let Some(c) = self.advance() {
@@ -252,7 +279,23 @@ impl<'a> Tokenizer<'a> {
                     // end of string literal
 
                     self.lexeme = builder;
-                    break;
+
+                    return if continued {
+                        TokenKind::InterpolationEnd
+                    } else {
+                        TokenKind::StringLiteral
+                    };
+                }
+                '{' => {
+                    // start of an interpolated expression
+                    self.lexeme = builder;
+                    self.interpolations.push(0);
+
+                    return if continued {
+                        TokenKind::InterpolationMiddle
+                    } else {
+                        TokenKind::InterpolationStart
+                    };
                 }
                 '\\' => {
                     if let Some(escaped_char) = self.peek().and_then(escaped) {
@@ -271,7 +314,11 @@ impl<'a> Tokenizer<'a> {
             }
         }
 
-        TokenKind::StringLiteral
+        if continued {
+            TokenKind::InterpolationEnd
+        } else {
+            TokenKind::StringLiteral
+        }
     }
 
     /// Scan a numeric literal (integer or float)
@@ -381,6 +428,10 @@ impl<'a> Tokenizer<'a> {
             return TokenKind::BoolLiteral;
         }
 
+        if self.lexeme == "none" {
+            return TokenKind::NoneLiteral;
+        }
+
         // check if its a keyword
         if let Some(keyword_kind) = TokenKind::try_from_keyword(&self.lexeme) {
             return keyword_kind;
@@ -434,6 +485,8 @@ fn escaped(c: char) -> Option<char> {
         '"' => '"',
         '\'' => '\'',
         '0' => '\0',
+        '{' => '{',
+        '}' => '}',
         _ => return None,
     })
 }
@@ -73,6 +73,12 @@ pub enum TokenKind {
     /// as
     #[strum(serialize = "as")]
     As,
+    /// try
+    #[strum(serialize = "try")]
+    Try,
+    /// catch
+    #[strum(serialize = "catch")]
+    Catch,
 
     //
     // === Punctuation ===
@@ -95,6 +101,12 @@ pub enum TokenKind {
     /// ..=
     #[strum(serialize = "..=")]
     DotDotEqual,
+    /// ?
+    #[strum(serialize = "?")]
+    Question,
+    /// @
+    #[strum(serialize = "@")]
+    At,
     /// (
     #[strum(serialize = "(")]
     LeftParen,
@@ -232,9 +244,21 @@ pub enum TokenKind {
     /// string literals enclosed in double quotes (e.g. "hello", "world")
     #[strum(serialize = "str")]
     StringLiteral,
+    /// the segment of an interpolated string before its first `{` (e.g. `"hello {`)
+    #[strum(serialize = "interpolation start")]
+    InterpolationStart,
+    /// a segment between two interpolated expressions (e.g. `}, you are {`)
+    #[strum(serialize = "interpolation middle")]
+    InterpolationMiddle,
+    /// the segment after the last interpolated expression (e.g. `} years old"`)
+    #[strum(serialize = "interpolation end")]
+    InterpolationEnd,
     /// boolean literals: true or false
     #[strum(serialize = "bool")]
     BoolLiteral,
+    /// the absent value of an optional: none
+    #[strum(serialize = "none")]
+    NoneLiteral,
 }
 
 impl TokenKind {
@@ -254,6 +278,8 @@ impl TokenKind {
             "import" => TokenKind::Import,
             "module" => TokenKind::Module,
             "as" => TokenKind::As,
+            "try" => TokenKind::Try,
+            "catch" => TokenKind::Catch,
             _ => return None,
         })
     }
@@ -36,9 +36,11 @@ impl CompilerStage<'_> for AstLoweringStage {
 
 fn annotate_ast(ast: Ast) -> CompilerResult<AnnotatedAst> {
     Ok(AnnotatedAst {
+        // imports were resolved into the declarations they brought in
         statements: ast
             .statements
             .into_iter()
+            .filter(|stmt| !matches!(stmt.item, StmtKind::Import(_)))
             .map(annotate_stmt)
             .try_collect()?,
         span: ast.span,
@@ -88,6 +90,15 @@ fn annotate_stmt(stmt: Stmt) -> CompilerResult<AnnotStmt> {
             StmtKind::Func(func_decl_stmt) => {
                 AnnotStmtKind::Func(annotate_func_decl(func_decl_stmt)?)
             }
+            StmtKind::Import(_) => {
+                return Err(error!(
+                    LoweringError::MismatchedNodes {
+                        expected: String::from("declaration"),
+                        found: String::from("import"),
+                    },
+                    stmt.span,
+                ));
+            }
             StmtKind::Return(return_stmt) => {
                 AnnotStmtKind::Return(annotate_return_stmt(return_stmt)?)
             }
@@ -100,6 +111,7 @@ fn annotate_stmt(stmt: Stmt) -> CompilerResult<AnnotStmt> {
             .scope_id
             .ok_or(error!(LoweringError::MissingScopeId, stmt.span,))?,
         span: stmt.span,
+        attributes: stmt.attributes,
     })
 }
 
@@ -172,10 +184,14 @@ fn annotate_var_decl(var_decl: VarDeclStmt) -> CompilerResult<VarDeclAnnotStmt>
 fn annotate_expr(expr: Expr) -> CompilerResult<AnnotExpr> {
     Ok(AnnotExpr {
         item: match expr.item {
+            ExprKind::AddressOf(value) => AnnotExprKind::AddressOf(Box::new(annotate_expr(*value)?)),
             ExprKind::Assign(assign_expr) => AnnotExprKind::Assign(annotate_assign(assign_expr)?),
             ExprKind::Binary(binary_expr) => AnnotExprKind::Binary(annotate_binary(binary_expr)?),
             ExprKind::Block(block_expr) => AnnotExprKind::Block(annotate_block(block_expr)?),
             ExprKind::Call(call_expr) => AnnotExprKind::Call(annotate_call(call_expr)?),
+            ExprKind::Catch(catch_expr) => AnnotExprKind::Catch(annotate_catch(catch_expr)?),
+            ExprKind::Deref(value) => AnnotExprKind::Deref(Box::new(annotate_expr(*value)?)),
+            ExprKind::Err(value) => AnnotExprKind::Err(Box::new(annotate_expr(*value)?)),
             ExprKind::Get(get_expr) => AnnotExprKind::Get(annotate_get(get_expr)?),
             ExprKind::Group(group_expr) => {
                 AnnotExprKind::Group(Box::new(annotate_expr(*group_expr)?))
@@ -184,8 +200,14 @@ fn annotate_expr(expr: Expr) -> CompilerResult<AnnotExpr> {
                 AnnotExprKind::Ident(annotate_ident(ident_expr, &expr.span)?)
             }
             ExprKind::If(if_expr) => AnnotExprKind::If(annotate_if(if_expr)?),
+            ExprKind::Interpolation(interpolation_expr) => {
+                AnnotExprKind::Interpolation(annotate_interpolation(interpolation_expr)?)
+            }
             ExprKind::Literal(_) => AnnotExprKind::Literal(lower_literal(&expr)?),
+            ExprKind::Ok(value) => AnnotExprKind::Ok(Box::new(annotate_expr(*value)?)),
+            ExprKind::Propagate(value) => AnnotExprKind::Propagate(Box::new(annotate_expr(*value)?)),
             ExprKind::Struct(struct_expr) => AnnotExprKind::Struct(annotate_struct(struct_expr)?),
+            ExprKind::Try(value) => AnnotExprKind::Try(Box::new(annotate_expr(*value)?)),
             ExprKind::TupleLiteral(tuple_expr) => {
                 AnnotExprKind::TupleLiteral(annotate_tuple(tuple_expr)?)
             }
@@ -248,6 +270,14 @@ fn annotate_call(call_expr: CallExpr) -> CompilerResult<CallAnnotExpr> {
     })
 }
 
+fn annotate_catch(catch_expr: CatchExpr) -> CompilerResult<CatchAnnotExpr> {
+    Ok(CatchAnnotExpr {
+        value: Box::new(annotate_expr(*catch_expr.value)?),
+        binding: annotate_symbol(catch_expr.binding.symbol)?,
+        handler: Box::new(annotate_expr(*catch_expr.handler)?),
+    })
+}
+
 fn annotate_get(get_expr: GetExpr) -> CompilerResult<GetAnnotExpr> {
     Ok(GetAnnotExpr {
         object: Box::new(annotate_expr(*get_expr.object)?),
@@ -267,6 +297,10 @@ fn annotate_ident(ident_expr: IdentExpr, span: &Span) -> CompilerResult<IdentAnn
 
 fn annotate_if(if_expr: IfExpr) -> CompilerResult<IfAnnotExpr> {
     Ok(IfAnnotExpr {
+        binding: if_expr
+            .binding
+            .map(|binding| annotate_symbol(binding.symbol))
+            .transpose()?,
         condition: Box::new(annotate_expr(*if_expr.condition)?),
         then_branch: Box::new(annotate_expr(*if_expr.then_branch)?),
         else_branch: match if_expr.else_branch {
@@ -276,6 +310,19 @@ fn annotate_if(if_expr: IfExpr) -> CompilerResult<IfAnnotExpr> {
     })
 }
 
+fn annotate_interpolation(interpolation_expr: InterpolationExpr) -> CompilerResult<InterpolationAnnotExpr> {
+    Ok(InterpolationAnnotExpr {
+        parts: interpolation_expr
+            .parts
+            .into_iter()
+            .map(|part| match part {
+                InterpolationPart::Literal(text) => Ok(InterpolationAnnotPart::Literal(text)),
+                InterpolationPart::Expr(expr) => annotate_expr(expr).map(InterpolationAnnotPart::Expr),
+            })
+            .try_collect()?,
+    })
+}
+
 fn lower_literal(expr: &Expr) -> CompilerResult<LiteralAnnotExpr> {
     let ExprKind::Literal(lit) = &expr.item else {
         return Err(error!(
@@ -466,6 +513,7 @@ fn lower_literal(expr: &Expr) -> CompilerResult<LiteralAnnotExpr> {
         LiteralExpr::Char(value) => Ok(LiteralAnnotExpr::Char(*value)),
         LiteralExpr::String(value) => Ok(LiteralAnnotExpr::String(value.to_string())),
         LiteralExpr::Unit => Ok(LiteralAnnotExpr::Unit),
+        LiteralExpr::None => Ok(LiteralAnnotExpr::None),
     }
 }
 
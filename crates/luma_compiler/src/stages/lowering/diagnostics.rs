@@ -4,36 +4,53 @@ use crate::TypeKind;
 
 define_diagnostics! {
     pub enum LoweringError {
-        #[Error("missing scope id", "no scope id was found for the given node")]
+        #[Error(E0401, "missing scope id", "no scope id was found for the given node")]
+        /// The analyzer didn't assign a scope to the node, this is a bug in the compiler.
         MissingScopeId,
-        #[Error("missing symbol id", "no symbol id was found for the given node")]
+        #[Error(E0402, "missing symbol id", "no symbol id was found for the given node")]
+        /// The analyzer didn't resolve the name of the node, this is a bug in the compiler.
         MissingSymbolId,
-        #[Error("unknown type", "type could not be determined")]
+        #[Error(E0403, "unknown type", "type could not be determined")]
+        /// The analyzer left the type of the node unresolved, this is a bug in the compiler.
         UnknownType,
-        #[Error("mismatched nodes", "the expected node '{expected}' was not found, instead found '{found}'")]
+        #[Error(E0404, "mismatched nodes", "the expected node '{expected}' was not found, instead found '{found}'")]
+        /// The analyzed AST doesn't have the shape lowering expects, this is a bug in the compiler.
         MismatchedNodes {
             expected: String,
             found: String,
         },
-        #[Error("invalid type for int lowering", "the type '{found}' is not valid for integer lowering")]
+        #[Error(E0405, "invalid type for int lowering", "the type '{found}' is not valid for integer lowering")]
+        /// An integer literal was inferred to have a type that isn't a number.
         InvalidTypeForIntLowering {
             found: TypeKind,
         },
-        #[Error("invalid type for float lowering", "the type '{found}' is not valid for float lowering")]
+        #[Error(E0406, "invalid type for float lowering", "the type '{found}' is not valid for float lowering")]
+        /// A float literal was inferred to have a type that isn't a float.
         InvalidTypeForFloatLowering {
             found: TypeKind,
         },
-        #[Error("integer overflow", "the amount '{amount}' is too large to fit in '{target}'")]
+        #[Error(E0407, "integer overflow", "the amount '{amount}' is too large to fit in '{target}'")]
+        /// The literal is larger than the largest value of its type, use a wider type or a smaller value.
+        ///
+        /// ```luma
+        /// var x: u8 = 300;
+        /// ```
         IntegerOverflow {
             amount: u64,
             target: String,
         },
-        #[Error("float overflow", "the amount '{amount}' is too large to fit in '{target}'")]
+        #[Error(E0408, "float overflow", "the amount '{amount}' is too large to fit in '{target}'")]
+        /// The literal is larger than the largest value of its float type, use `f64` instead.
+        ///
+        /// ```luma
+        /// var x: f32 = 1e39;
+        /// ```
         FloatOverflow {
             amount: f64,
             target: String,
         },
-        #[Error("invalid cast", "the cast from '{from}' to '{to}' is not allowed")]
+        #[Error(E0409, "invalid cast", "the cast from '{from}' to '{to}' is not allowed")]
+        /// The literal can't be represented by the type it's used as, like an integer too large to be a char.
         InvalidCast {
             from: String,
             to: String,
@@ -2,4 +2,6 @@ pub mod analyzer;
 pub mod codegen;
 pub mod lexer;
 pub mod lowering;
-pub mod parser;
\ No newline at end of file
+pub mod optimizer;
+pub mod parser;
+pub mod resolver;
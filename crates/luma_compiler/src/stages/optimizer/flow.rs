@@ -0,0 +1,84 @@
+use crate::{
+    bytecode::Opcode,
+    stages::{codegen::chunk::InstrOffset, optimizer::DecodedFunction},
+};
+
+/// Marks every instruction index that is the target of at least one jump
+pub fn jump_targets(code: &[Opcode]) -> Vec<bool> {
+    let mut targets = vec![false; code.len() + 1];
+
+    for opcode in code {
+        if let Some(target) = opcode.jump_target() {
+            targets[target as usize] = true;
+        }
+    }
+
+    targets
+}
+
+/// Returns the instruction indices execution may continue at after executing `code[index]`
+pub fn successors(code: &[Opcode], index: usize) -> impl Iterator<Item = usize> {
+    let opcode = code[index];
+
+    let fallthrough = (!opcode.is_terminator()).then_some(index + 1);
+    let jump = opcode.jump_target().map(|target| target as usize);
+
+    fallthrough.into_iter().chain(jump)
+}
+
+/// Removes every instruction whose `keep` entry is false, remapping jump targets and debug info accordingly.
+///
+/// A jump to a removed instruction is redirected to the next instruction that is kept.
+/// Returns `true` if any instruction was removed.
+pub fn retain_instructions(func: &mut DecodedFunction, keep: &[bool]) -> bool {
+    let code = &mut func.instructions;
+
+    if keep.iter().all(|kept| *kept) {
+        return false;
+    }
+
+    // new_index[i] is the amount of kept instructions before `i`, which is both the new position
+    // of a kept instruction and the position of the next kept one for a removed instruction
+    let mut new_index = Vec::with_capacity(code.len() + 1);
+    let mut kept_count: InstrOffset = 0;
+
+    for kept in keep {
+        new_index.push(kept_count);
+
+        if *kept {
+            kept_count += 1;
+        }
+    }
+
+    // jumps are allowed to target the end of the chunk
+    new_index.push(kept_count);
+
+    let mut index = 0;
+    code.retain(|_| {
+        let kept = keep[index];
+        index += 1;
+        kept
+    });
+
+    for opcode in code.iter_mut() {
+        if let Some(target) = opcode.jump_target() {
+            opcode.set_jump_target(new_index[target as usize]);
+        }
+    }
+
+    let mut index = 0;
+    func.debug.spans.retain(|_| {
+        let kept = keep.get(index).copied().unwrap_or(false);
+        index += 1;
+        kept
+    });
+
+    // locals whose whole live range was removed are dropped
+    func.debug.locals.retain_mut(|local| {
+        local.start = new_index[(local.start as usize).min(keep.len())];
+        local.end = new_index[(local.end as usize).min(keep.len())];
+        local.start < local.end
+    });
+
+    true
+}
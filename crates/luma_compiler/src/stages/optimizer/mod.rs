@@ -0,0 +1,117 @@
+use luma_diagnostic::CompilerResult;
+
+use crate::{
+    CompilerContext, CompilerStage,
+    bytecode::{BytecodeValue, ModuleBytecode, Opcode},
+    stages::codegen::chunk::{CodeChunk, DecodedDebugInfo, FunctionChunk},
+};
+
+mod flow;
+mod options;
+pub mod passes;
+
+pub use options::*;
+
+#[cfg(test)]
+mod tests;
+
+/// Upper bound of how many times the passes are re-run on a chunk while they keep finding changes
+const MAX_ITERATIONS: usize = 16;
+
+/// Rewrites the generated bytecode into a smaller and faster equivalent.
+///
+/// The amount of work done is controlled by [`OptimizerOptions::level`]
+pub struct OptimizerStage;
+
+impl OptimizerStage {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl CompilerStage<'_> for OptimizerStage {
+    type Input = Vec<ModuleBytecode>;
+    type Output = Vec<ModuleBytecode>;
+
+    fn name() -> &'static str {
+        "optimizer"
+    }
+
+    fn process(self, ctx: &CompilerContext, input: Self::Input) -> Self::Output {
+        let passes = passes::passes_for_level(ctx.options.optimizer.level);
+        let mut modules = input;
+
+        if passes.is_empty() {
+            return modules;
+        }
+
+        for module in &mut modules {
+            for func in &mut module.functions {
+                if let Err(err) = optimize_function(&passes, &module.constants, func) {
+                    ctx.add_diag(err);
+                    return Vec::new();
+                }
+            }
+        }
+
+        modules
+    }
+}
+
+/// A function chunk decoded into one [`Opcode`] per instruction, with jumps targeting instruction indices.
+///
+/// Passes work on this form so they can insert and remove instructions without worrying about byte offsets
+pub struct DecodedFunction {
+    pub instructions: Vec<Opcode>,
+    pub arity: usize,
+    pub max_locals: usize,
+
+    /// kept in sync with the instructions by the passes
+    pub debug: DecodedDebugInfo,
+}
+
+/// Runs all passes over the function until none of them reports a change
+pub fn optimize_function(
+    passes: &[Box<dyn OptimizerPass>],
+    constants: &[BytecodeValue],
+    func: &mut FunctionChunk,
+) -> CompilerResult<()> {
+    let mut decoded = DecodedFunction {
+        instructions: func.code.decode_instructions(),
+        arity: func.arity,
+        max_locals: func.code.max_locals,
+        debug: func.code.decode_debug_info(),
+    };
+
+    let mut modified = false;
+
+    for _ in 0..MAX_ITERATIONS {
+        let mut changed = false;
+
+        for pass in passes {
+            if pass.optimize(constants, &mut decoded) {
+                tracing::debug!("optimizer pass '{}' modified the chunk", pass.name());
+                changed = true;
+            }
+        }
+
+        if !changed {
+            break;
+        }
+
+        modified = true;
+    }
+
+    if modified {
+        func.code = CodeChunk::assemble_with_debug_info(&decoded.instructions, decoded.max_locals, &decoded.debug)?;
+    }
+
+    Ok(())
+}
+
+pub trait OptimizerPass {
+    fn name(&self) -> String;
+
+    /// Optimizes the function in place, returns `true` if anything was changed
+    fn optimize(&self, constants: &[BytecodeValue], func: &mut DecodedFunction) -> bool;
+}
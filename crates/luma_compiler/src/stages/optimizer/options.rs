@@ -0,0 +1,20 @@
+use crate::options::define_options;
+
+define_options! {
+    pub struct OptimizerOptions {
+        level: OptimizationLevel = OptimizationLevel::Basic,
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum OptimizationLevel {
+    /// emit the bytecode exactly as generated by codegen
+    None,
+
+    /// peephole rewrites, jump threading and unreachable code removal
+    #[default]
+    Basic,
+
+    /// everything in [`OptimizationLevel::Basic`] plus dead store removal and local slot compaction
+    Full,
+}
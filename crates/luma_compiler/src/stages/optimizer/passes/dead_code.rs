@@ -0,0 +1,36 @@
+use crate::{
+    bytecode::BytecodeValue,
+    stages::optimizer::{DecodedFunction, OptimizerPass, flow},
+};
+
+/// Removes instructions that can never be reached from the start of the chunk,
+/// such as code following a `Return` or an unconditional jump.
+pub struct DeadCodeElimination;
+
+impl OptimizerPass for DeadCodeElimination {
+    fn name(&self) -> String {
+        String::from("dead_code_elimination")
+    }
+
+    fn optimize(&self, _constants: &[BytecodeValue], func: &mut DecodedFunction) -> bool {
+        let code = &mut func.instructions;
+
+        if code.is_empty() {
+            return false;
+        }
+
+        let mut reachable = vec![false; code.len()];
+        let mut worklist = vec![0];
+
+        while let Some(index) = worklist.pop() {
+            if index >= code.len() || reachable[index] {
+                continue;
+            }
+
+            reachable[index] = true;
+            worklist.extend(flow::successors(code, index));
+        }
+
+        flow::retain_instructions(func, &reachable)
+    }
+}
@@ -0,0 +1,52 @@
+use crate::{
+    bytecode::{BytecodeValue, Opcode},
+    stages::optimizer::{DecodedFunction, OptimizerPass},
+};
+
+/// Redirects jumps that land on an unconditional jump straight to its final destination
+/// and replaces jumps to a `Return` with the return itself.
+pub struct JumpThreading;
+
+impl OptimizerPass for JumpThreading {
+    fn name(&self) -> String {
+        String::from("jump_threading")
+    }
+
+    fn optimize(&self, _constants: &[BytecodeValue], func: &mut DecodedFunction) -> bool {
+        let code = &mut func.instructions;
+        let mut changed = false;
+
+        for index in 0..code.len() {
+            let Some(original_target) = code[index].jump_target() else {
+                continue;
+            };
+
+            let mut target = original_target;
+            let mut hops = 0;
+
+            // follow the chain, bounded by the chunk length to not get stuck in jump cycles
+            while hops < code.len()
+                && let Some(next) = code.get(target as usize)
+                && next.is_unconditional_jump()
+                && let Some(next_target) = next.jump_target()
+            {
+                target = next_target;
+                hops += 1;
+            }
+
+            if target != original_target {
+                code[index].set_jump_target(target);
+                changed = true;
+            }
+
+            if code[index].is_unconditional_jump()
+                && let Some(Opcode::Return) = code.get(target as usize)
+            {
+                code[index] = Opcode::Return;
+                changed = true;
+            }
+        }
+
+        changed
+    }
+}
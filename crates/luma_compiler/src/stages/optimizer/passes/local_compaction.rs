@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+
+use crate::{
+    bytecode::{BytecodeValue, Opcode},
+    stages::{
+        codegen::chunk::LocalSlot,
+        optimizer::{DecodedFunction, OptimizerPass},
+    },
+};
+
+/// Turns stores to locals that are never read into plain pops,
+/// then renumbers the remaining locals so they occupy a dense range of slots.
+///
+/// Parameter slots are never touched as the caller places the arguments there.
+pub struct LocalCompaction;
+
+impl OptimizerPass for LocalCompaction {
+    fn name(&self) -> String {
+        String::from("local_compaction")
+    }
+
+    fn optimize(&self, _constants: &[BytecodeValue], func: &mut DecodedFunction) -> bool {
+        let param_slots = func.arity as LocalSlot;
+        let code = &mut func.instructions;
+        let mut changed = false;
+
+        // dead stores, a referenced local may still be read through the reference
+        let read_slots = code
+            .iter()
+            .filter_map(|opcode| opcode.local_read().or(opcode.local_ref()))
+            .collect::<Vec<_>>();
+
+        for opcode in code.iter_mut() {
+            if let Some(slot) = opcode.local_write()
+                && slot >= param_slots
+                && !read_slots.contains(&slot)
+            {
+                *opcode = Opcode::Pop;
+                changed = true;
+            }
+        }
+
+        // compaction, slots are renumbered in order of first appearance
+        let mut remapped = HashMap::<LocalSlot, LocalSlot>::new();
+
+        for opcode in code.iter_mut() {
+            let Some(slot) = opcode.local_slot() else {
+                continue;
+            };
+
+            if slot < param_slots {
+                continue;
+            }
+
+            let next_slot = param_slots + remapped.len() as LocalSlot;
+            let new_slot = *remapped.entry(slot).or_insert(next_slot);
+
+            if new_slot != slot {
+                opcode.set_local_slot(new_slot);
+                changed = true;
+            }
+        }
+
+        // locals that are never stored anymore have no slot left to show
+        func.debug.locals.retain_mut(|local| {
+            if local.slot < param_slots {
+                return true;
+            }
+
+            match remapped.get(&local.slot) {
+                Some(&slot) => {
+                    local.slot = slot;
+                    true
+                }
+                None => false,
+            }
+        });
+
+        let max_locals = param_slots as usize + remapped.len();
+
+        if max_locals < func.max_locals {
+            func.max_locals = max_locals;
+            changed = true;
+        }
+
+        changed
+    }
+}
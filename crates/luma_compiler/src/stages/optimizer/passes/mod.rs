@@ -0,0 +1,28 @@
+mod dead_code;
+mod jump_threading;
+mod local_compaction;
+mod peephole;
+
+pub use dead_code::DeadCodeElimination;
+pub use jump_threading::JumpThreading;
+pub use local_compaction::LocalCompaction;
+pub use peephole::Peephole;
+
+use crate::stages::optimizer::{OptimizationLevel, OptimizerPass};
+
+pub fn passes_for_level(level: OptimizationLevel) -> Vec<Box<dyn OptimizerPass>> {
+    match level {
+        OptimizationLevel::None => Vec::new(),
+        OptimizationLevel::Basic => vec![
+            Box::new(Peephole),
+            Box::new(JumpThreading),
+            Box::new(DeadCodeElimination),
+        ],
+        OptimizationLevel::Full => vec![
+            Box::new(Peephole),
+            Box::new(JumpThreading),
+            Box::new(DeadCodeElimination),
+            Box::new(LocalCompaction),
+        ],
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{
+    bytecode::{BytecodeValue, Opcode},
+    stages::optimizer::{DecodedFunction, OptimizerPass, flow},
+};
+
+/// Rewrites short instruction sequences into cheaper equivalents.
+///
+/// A pattern is only applied if no jump lands in the middle of it.
+pub struct Peephole;
+
+impl OptimizerPass for Peephole {
+    fn name(&self) -> String {
+        String::from("peephole")
+    }
+
+    fn optimize(&self, constants: &[BytecodeValue], func: &mut DecodedFunction) -> bool {
+        let code = &mut func.instructions;
+        let targets = flow::jump_targets(code);
+
+        let mut keep = vec![true; code.len()];
+        let mut changed = false;
+        let mut index = 0;
+
+        while index < code.len() {
+            // jump to the very next instruction
+            if code[index].is_unconditional_jump()
+                && code[index].jump_target() == Some(index as u32 + 1)
+            {
+                keep[index] = false;
+                index += 1;
+                continue;
+            }
+
+            if index + 1 >= code.len() || targets[index + 1] {
+                index += 1;
+                continue;
+            }
+
+            let (first, second) = (code[index], code[index + 1]);
+
+            // a copy that is stored and then discarded is never needed
+            if first == Opcode::Dup
+                && second.local_write().is_some()
+                && code.get(index + 2) == Some(&Opcode::Pop)
+                && !targets[index + 2]
+            {
+                keep[index] = false;
+                keep[index + 2] = false;
+                index += 3;
+                continue;
+            }
+
+            // storing and immediately loading a local keeps the value around instead
+            if let (Some(set_slot), Some(get_slot)) = (first.local_write(), second.local_read())
+                && set_slot == get_slot
+            {
+                code[index] = Opcode::Dup;
+                code[index + 1] = first;
+                changed = true;
+            }
+            // pushing a value without side effects only to discard it
+            else if second == Opcode::Pop
+                && (matches!(first, Opcode::PushUnit | Opcode::PushNone | Opcode::Dup)
+                    || first.const_slot().is_some()
+                    || first.local_read().is_some())
+            {
+                keep[index] = false;
+                keep[index + 1] = false;
+            }
+            // negated conditions flip the jump instead
+            else if first == Opcode::Not
+                && let Some((jumps_on, target)) = conditional_jump(second)
+            {
+                keep[index] = false;
+                code[index + 1] = if jumps_on {
+                    Opcode::jump_if_false(target)
+                } else {
+                    Opcode::jump_if_true(target)
+                };
+            }
+            // conditions known at compile time
+            else if let Some(slot) = first.const_slot()
+                && let Some(BytecodeValue::Bool(value)) = constants.get(slot as usize)
+                && let Some((jumps_on, target)) = conditional_jump(second)
+            {
+                keep[index] = false;
+
+                if *value == jumps_on {
+                    code[index + 1] = Opcode::jump(target);
+                } else {
+                    keep[index + 1] = false;
+                }
+            } else {
+                index += 1;
+                continue;
+            }
+
+            index += 2;
+        }
+
+        flow::retain_instructions(func, &keep) || changed
+    }
+}
+
+/// Returns the condition value the jump is taken on and its target, if the opcode is a conditional jump
+fn conditional_jump(opcode: Opcode) -> Option<(bool, u32)> {
+    match opcode {
+        Opcode::JumpIfTrue(_) | Opcode::JumpIfTrueWide(_) => Some((true, opcode.jump_target()?)),
+        Opcode::JumpIfFalse(_) | Opcode::JumpIfFalseWide(_) => Some((false, opcode.jump_target()?)),
+        _ => None,
+    }
+}
@@ -0,0 +1,92 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    bytecode::Opcode,
+    stages::optimizer::{OptimizationLevel, tests::optimize_chunk},
+};
+
+#[test]
+fn code_after_return_is_removed() {
+    let disassembly = optimize_chunk(
+        OptimizationLevel::Basic,
+        0,
+        vec![
+            Opcode::LoadConst(0),
+            Opcode::Return,
+            Opcode::Jump(4),
+            Opcode::PushUnit,
+            Opcode::Return,
+        ],
+    );
+
+    assert_eq!(
+        disassembly,
+        "\
+arity: 0, locals: 0
+0000  LoadConst 0
+0003  Return
+"
+    );
+}
+
+#[test]
+fn jump_offsets_survive_removal() {
+    // the block at 0003..0005 is unreachable, the jumps after it must be shifted back
+    let disassembly = optimize_chunk(
+        OptimizationLevel::Basic,
+        1,
+        vec![
+            Opcode::GetLocal(0),
+            Opcode::JumpIfFalse(6),
+            Opcode::Jump(8),
+            Opcode::LoadConst(0),
+            Opcode::LoadConst(1),
+            Opcode::Add,
+            Opcode::LoadConst(2),
+            Opcode::Return,
+            Opcode::LoadConst(3),
+            Opcode::Return,
+        ],
+    );
+
+    assert_eq!(
+        disassembly,
+        "\
+arity: 1, locals: 1
+0000  GetLocal 0
+0003  JumpIfFalse -> 0009
+0006  Jump -> 0013
+0009  LoadConst 2
+0012  Return
+0013  LoadConst 3
+0016  Return
+"
+    );
+}
+
+#[test]
+fn none_level_keeps_code_untouched() {
+    let instructions = vec![
+        Opcode::PushUnit,
+        Opcode::Pop,
+        Opcode::Jump(3),
+        Opcode::PushUnit,
+        Opcode::Return,
+        Opcode::Return,
+    ];
+
+    let disassembly = optimize_chunk(OptimizationLevel::None, 0, instructions);
+
+    assert_eq!(
+        disassembly,
+        "\
+arity: 0, locals: 0
+0000  PushUnit
+0001  Pop
+0002  Jump -> 0005
+0005  PushUnit
+0006  Return
+0007  Return
+"
+    );
+}
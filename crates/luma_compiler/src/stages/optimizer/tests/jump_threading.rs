@@ -0,0 +1,95 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    bytecode::Opcode,
+    stages::optimizer::{
+        OptimizationLevel,
+        tests::{compile_module, optimize_chunk},
+    },
+};
+
+#[test]
+fn nested_if_jumps_to_outer_end() {
+    let module = compile_module(
+        r#"
+        var t = true;
+        var b = if t {
+            if t { 1 } else { 2 }
+        } else {
+            3
+        };
+    "#,
+        OptimizationLevel::Basic,
+    );
+
+    assert_eq!(
+        module.disassemble(),
+        "\
+fn#0 arity: 0, locals: 0
+0000  LoadConst 0  ; Bool(true)
+0003  SetGlobal 0
+0006  GetGlobal 0
+0009  JumpIfFalse -> 0030
+0012  GetGlobal 0
+0015  JumpIfFalse -> 0024
+0018  LoadConst 1  ; Int32(1)
+0021  Jump -> 0033
+0024  LoadConst 2  ; Int32(2)
+0027  Jump -> 0033
+0030  LoadConst 3  ; Int32(3)
+0033  SetGlobal 1
+0036  PushUnit
+0037  Return
+"
+    );
+}
+
+#[test]
+fn jump_to_return_becomes_return() {
+    let disassembly = optimize_chunk(
+        OptimizationLevel::Basic,
+        1,
+        vec![
+            Opcode::GetLocal(0),
+            Opcode::JumpIfFalse(4),
+            Opcode::LoadConst(0),
+            Opcode::Jump(5),
+            Opcode::LoadConst(1),
+            Opcode::Return,
+        ],
+    );
+
+    assert_eq!(
+        disassembly,
+        "\
+arity: 1, locals: 1
+0000  GetLocal 0
+0003  JumpIfFalse -> 0010
+0006  LoadConst 0
+0009  Return
+0010  LoadConst 1
+0013  Return
+"
+    );
+}
+
+#[test]
+fn jump_cycle_terminates() {
+    let disassembly = optimize_chunk(
+        OptimizationLevel::Basic,
+        0,
+        vec![
+            Opcode::Jump(1),
+            Opcode::Jump(2),
+            Opcode::Jump(1),
+        ],
+    );
+
+    assert_eq!(
+        disassembly,
+        "\
+arity: 0, locals: 0
+0000  Jump -> 0000
+"
+    );
+}
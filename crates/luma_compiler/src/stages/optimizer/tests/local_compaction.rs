@@ -0,0 +1,98 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    bytecode::Opcode,
+    stages::optimizer::{
+        OptimizationLevel,
+        tests::{compile_module, optimize_chunk},
+    },
+};
+
+#[test]
+fn dead_store_is_removed() {
+    let module = compile_module(
+        r#"
+        func f(): i32 {
+            var unused = 1;
+            var x = 4;
+            x
+        };
+    "#,
+        OptimizationLevel::Full,
+    );
+
+    assert_eq!(
+        module.disassemble(),
+        "\
+fn#0 arity: 0, locals: 0
+0000  PushUnit
+0001  Return
+
+fn#1 arity: 0, locals: 0
+0000  LoadConst 1  ; Int32(4)
+0003  Return
+"
+    );
+}
+
+#[test]
+fn slots_are_compacted_after_params() {
+    let disassembly = optimize_chunk(
+        OptimizationLevel::Full,
+        2,
+        vec![
+            Opcode::GetLocal(0),
+            Opcode::SetLocal(7),
+            Opcode::GetLocal(1),
+            Opcode::SetLocal(4),
+            Opcode::GetLocal(4),
+            Opcode::GetLocal(7),
+            Opcode::Add,
+            Opcode::Return,
+        ],
+    );
+
+    assert_eq!(
+        disassembly,
+        "\
+arity: 2, locals: 3
+0000  GetLocal 0
+0003  SetLocal 2
+0006  GetLocal 1
+0009  GetLocal 2
+0012  Add
+0013  Return
+"
+    );
+}
+
+#[test]
+fn basic_level_keeps_slots() {
+    let module = compile_module(
+        r#"
+        func f(): i32 {
+            var unused = 1;
+            var x = 4;
+            x
+        };
+    "#,
+        OptimizationLevel::Basic,
+    );
+
+    assert_eq!(
+        module.disassemble(),
+        "\
+fn#0 arity: 0, locals: 0
+0000  PushUnit
+0001  Return
+
+fn#1 arity: 0, locals: 2
+0000  LoadConst 0  ; Int32(1)
+0003  SetLocal 0
+0006  LoadConst 1  ; Int32(4)
+0009  Dup
+0010  SetLocal 1
+0013  Return
+"
+    );
+}
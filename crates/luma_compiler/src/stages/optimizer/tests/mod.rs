@@ -0,0 +1,52 @@
+use luma_core::CodeSource;
+
+use crate::{
+    CompilerOptions, LumaCompiler,
+    bytecode::{ModuleBytecode, Opcode},
+    stages::{
+        codegen::chunk::{CodeChunk, FunctionChunk},
+        lexer::LexerOptions,
+        optimizer::{OptimizationLevel, OptimizerOptions, optimize_function, passes},
+    },
+};
+
+pub mod dead_code;
+pub mod jump_threading;
+pub mod local_compaction;
+pub mod peephole;
+
+pub fn compile_module(src: &str, level: OptimizationLevel) -> ModuleBytecode {
+    let compiler = LumaCompiler::configure(CompilerOptions {
+        lexer: LexerOptions {
+            zeroed_spans: true,
+            ..Default::default()
+        },
+        optimizer: OptimizerOptions {
+            level,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+
+    let result = compiler.compile([CodeSource::from(src)]);
+
+    assert!(!result.has_errors(), "compilation failed: {:#?}", result.diagnostics);
+
+    result
+        .result
+        .and_then(|modules| modules.into_iter().next())
+        .expect("expected at least one module")
+}
+
+/// Optimizes a hand written chunk and returns its disassembly
+pub fn optimize_chunk(level: OptimizationLevel, arity: usize, instructions: Vec<Opcode>) -> String {
+    let mut func = FunctionChunk {
+        name: String::from("test"),
+        code: CodeChunk::from(instructions),
+        arity,
+    };
+
+    optimize_function(&passes::passes_for_level(level), &[], &mut func).expect("optimization failed");
+
+    func.disassemble(&[])
+}
@@ -0,0 +1,147 @@
+use pretty_assertions::assert_eq;
+
+use crate::{
+    bytecode::Opcode,
+    stages::optimizer::{
+        OptimizationLevel,
+        tests::{compile_module, optimize_chunk},
+    },
+};
+
+#[test]
+fn store_then_load_becomes_dup() {
+    let module = compile_module(
+        r#"
+        {
+            var a = 1;
+            a = 2;
+            var b = a;
+        };
+    "#,
+        OptimizationLevel::Basic,
+    );
+
+    assert_eq!(
+        module.disassemble(),
+        "\
+fn#0 arity: 0, locals: 2
+0000  LoadConst 0  ; Int32(1)
+0003  SetLocal 0
+0006  LoadConst 1  ; Int32(2)
+0009  Dup
+0010  SetLocal 0
+0013  SetLocal 1
+0016  PushUnit
+0017  Return
+"
+    );
+}
+
+#[test]
+fn discarded_values_are_removed() {
+    let module = compile_module(
+        r#"
+        {
+            var a = 1;
+            {
+                var c = 5;
+                c;
+            };
+            a;
+        };
+    "#,
+        OptimizationLevel::Basic,
+    );
+
+    assert_eq!(
+        module.disassemble(),
+        "\
+fn#0 arity: 0, locals: 2
+0000  LoadConst 0  ; Int32(1)
+0003  SetLocal 0
+0006  LoadConst 1  ; Int32(5)
+0009  SetLocal 1
+0012  PushUnit
+0013  Return
+"
+    );
+}
+
+#[test]
+fn negated_condition_flips_jump() {
+    let module = compile_module(
+        r#"
+        var t = true;
+        var b = if !t { 2 } else { 3 };
+    "#,
+        OptimizationLevel::Basic,
+    );
+
+    assert_eq!(
+        module.disassemble(),
+        "\
+fn#0 arity: 0, locals: 0
+0000  LoadConst 0  ; Bool(true)
+0003  SetGlobal 0
+0006  GetGlobal 0
+0009  JumpIfTrue -> 0018
+0012  LoadConst 1  ; Int32(2)
+0015  Jump -> 0021
+0018  LoadConst 2  ; Int32(3)
+0021  SetGlobal 1
+0024  PushUnit
+0025  Return
+"
+    );
+}
+
+#[test]
+fn constant_condition_is_folded() {
+    let module = compile_module(
+        r#"
+        var b = if true { 2 } else { 3 };
+    "#,
+        OptimizationLevel::Basic,
+    );
+
+    assert_eq!(
+        module.disassemble(),
+        "\
+fn#0 arity: 0, locals: 0
+0000  LoadConst 1  ; Int32(2)
+0003  SetGlobal 0
+0006  PushUnit
+0007  Return
+"
+    );
+}
+
+#[test]
+fn pattern_split_by_jump_target_is_kept() {
+    // the `Pop` is a jump target, so the `GetLocal` before it cannot be removed together with it
+    let disassembly = optimize_chunk(
+        OptimizationLevel::Basic,
+        1,
+        vec![
+            Opcode::GetLocal(0),
+            Opcode::JumpIfFalse(3),
+            Opcode::GetLocal(0),
+            Opcode::Pop,
+            Opcode::PushUnit,
+            Opcode::Return,
+        ],
+    );
+
+    assert_eq!(
+        disassembly,
+        "\
+arity: 1, locals: 1
+0000  GetLocal 0
+0003  JumpIfFalse -> 0009
+0006  GetLocal 0
+0009  Pop
+0010  PushUnit
+0011  Return
+"
+    );
+}
@@ -1,56 +1,132 @@
-use crate::ast::ExprKind;
+use crate::{TypeKind, ast::ExprKind};
 use luma_diagnostic::define_diagnostics;
 
 use crate::stages::lexer::TokenKind;
 
 define_diagnostics! {
     pub enum ParserError {
-        #[Error("unexpected token", "expected '{expected}', found '{found}'")]
+        #[Error(E0101, "unexpected token", "expected '{expected}', found '{found}'")]
+        /// The grammar requires a specific token at this position, most often a missing `;` or closing bracket.
+        ///
+        /// ```luma
+        /// var x = 1   // missing ';'
+        /// var y = 2;
+        /// ```
         ExpectedToken {
             expected: TokenKind,
             found: TokenKind,
         },
-        #[Error("unexpected token", "found '{found}'")]
+        #[Error(E0102, "unexpected token", "found '{found}'")]
+        /// The token can't start or continue the expression or statement being parsed.
+        ///
+        /// ```luma
+        /// var x = * ;
+        /// ```
         UnexpectedToken {
             found: TokenKind,
         },
-        #[Error("unexpected end of input")]
+        #[Error(E0103, "unexpected end of input")]
+        /// The source ended in the middle of a statement, usually because a block or string was never closed.
+        ///
+        /// ```luma
+        /// func f() {
+        /// ```
         UnexpectedEndOfInput,
-        #[Error("invalid char literal", "found '{lexeme}'")]
+        #[Error(E0104, "invalid char literal", "found '{lexeme}'")]
+        /// A char literal must hold exactly one character or escape sequence.
+        ///
+        /// ```luma
+        /// var c = 'ab';
+        /// ```
         InvalidCharLiteral {
             lexeme: String
         },
-        #[Error("invalid float literal", "found '{lexeme}': {source}")]
+        #[Error(E0105, "invalid float literal", "found '{lexeme}': {source}")]
+        /// The literal looks like a float but can't be read as one.
         InvalidFloatLiteral {
             lexeme: String,
             source: String,
         },
-        #[Error("invalid integer literal", "found '{lexeme}': {source}")]
+        #[Error(E0106, "invalid integer literal", "found '{lexeme}': {source}")]
+        /// The literal looks like an integer but can't be read as one, for example because it doesn't fit in 64 bits.
+        ///
+        /// ```luma
+        /// var x = 99999999999999999999999;
+        /// ```
         InvalidIntegerLiteral {
             lexeme: String,
             source: String,
         },
-        #[Error("invalid boolean literal", "found '{lexeme}'")]
+        #[Error(E0107, "invalid boolean literal", "found '{lexeme}'")]
+        /// Booleans are written `true` or `false`.
         InvalidBooleanLiteral {
             lexeme: String,
         },
-        #[Error("invalid suffix for literal", "found '{suffix}'")]
+        #[Error(E0108, "invalid suffix for literal", "found '{suffix}'")]
+        /// A numeric literal may only be suffixed with a number type like `u8`, `i64` or `f32`.
+        ///
+        /// ```luma
+        /// var x = 1abc;
+        /// ```
         InvalidLiteralSuffix {
             suffix: String,
         },
-        #[Error("invalid struct literal target", "expected identifier found '{found}'")]
+        #[Error(E0109, "invalid struct literal target", "expected identifier found '{found}'")]
+        /// A struct literal starts with the name of the struct.
+        ///
+        /// ```luma
+        /// var p = (1 + 2) { x: 1 };
+        /// ```
         InvalidStructLiteralTarget {
             found: ExprKind,
         },
-        #[Error("invalid visibility specifier", "found '{ident}'")]
+        #[Error(E0110, "invalid visibility specifier", "found '{ident}'")]
+        /// Visibility is written `pub`, `pub(this)` or `pub(module)`.
+        ///
+        /// ```luma
+        /// pub(everyone) func f() { };
+        /// ```
         InvalidVisibility {
             ident: String,
         },
-        #[Error("missing body for function declaration")]
+        #[Error(E0111, "unknown attribute", "found '@{name}', only '@allow' is supported")]
+        /// Statements only accept the `@allow(..)` attribute, which silences the named lints.
+        ///
+        /// ```luma
+        /// @deny(unused) func f() { };
+        /// ```
+        UnknownAttribute {
+            name: String,
+        },
+        #[Error(E0112, "missing body for function declaration")]
+        /// Every function declaration needs a body in braces.
+        ///
+        /// ```luma
+        /// func f(): i32;
+        /// ```
         MissingFunctionBody,
-        #[Error("invalid type", "found '{type_name}'")]
+        #[Error(E0113, "invalid type", "found '{type_name}'")]
+        /// The type annotation isn't a type that can be written in source.
         InvalidType {
             type_name: String,
         },
+        #[Error(E0114, "nested optional type", "'{ty}' is already optional")]
+        /// An optional can't itself be optional, a single `?` already covers the missing value.
+        ///
+        /// ```luma
+        /// var x: i32?? = none;
+        /// ```
+        NestedOptional {
+            ty: TypeKind,
+        },
+        #[Error(E0115, "nested result type", "'{ty}' already has an error type")]
+        /// A result has a single error type, combine the errors into one type instead.
+        ///
+        /// ```luma
+        /// var x: i32!str!bool = ok(1);
+        /// ```
+        NestedResult {
+            ty: TypeKind,
+        },
     }
-}
\ No newline at end of file
+}
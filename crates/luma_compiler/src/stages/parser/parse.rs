@@ -38,7 +38,7 @@ impl TokenParser<'_> {
         let mut statements: Vec<Stmt> = Vec::new();
 
         while !self.is_at_end() {
-            match self.parse_statement(None) {
+            match self.parse_top_level_statement() {
                 Ok(stmt) => statements.push(stmt),
                 Err(err) => {
                     diagnostics.push(err);
@@ -18,9 +18,9 @@ impl TokenParser<'_> {
     // MARK: Assign
     /// Parses assignment expressions
     ///
-    /// Ascends to [`Parser::expr_or`]
+    /// Ascends to [`Parser::expr_catch`]
     pub(super) fn expr_assign(&mut self) -> CompilerResult<Expr> {
-        let mut expr = self.expr_or()?;
+        let mut expr = self.expr_catch()?;
 
         loop {
             // check for assignment operator
@@ -52,6 +52,40 @@ impl TokenParser<'_> {
         Ok(expr)
     }
 
+    // MARK: Catch
+    /// Parses `try value` and `value catch e { ... }` handlers of results,
+    /// `try` covers the whole value so that `try a / b` catches the division
+    ///
+    /// Ascends to [`Parser::expr_or`]
+    pub(super) fn expr_catch(&mut self) -> CompilerResult<Expr> {
+        let mut expr = if let Ok(try_token) = self.consume(TokenKind::Try) {
+            let value = self.expr_or()?;
+
+            Expr::new(try_token.span.merged(&value.span), ExprKind::Try(Box::new(value)))
+        } else {
+            self.expr_or()?
+        };
+
+        while self.consume(TokenKind::Catch).is_ok() {
+            let ident_token = self.consume(TokenKind::Ident)?;
+            let handler = self.expr_block()?;
+
+            expr = Expr::new(
+                expr.span.merged(&handler.span),
+                ExprKind::Catch(CatchExpr {
+                    value: Box::new(expr),
+                    binding: Binding {
+                        symbol: ident_token.as_symbol(),
+                        scope_id: None,
+                    },
+                    handler: Box::new(handler),
+                }),
+            );
+        }
+
+        Ok(expr)
+    }
+
     // MARK: Or
     /// Parses or expression
     ///
@@ -272,7 +306,7 @@ impl TokenParser<'_> {
     }
 
     // MARK: Unary
-    /// Parses unary expressions (!, -)
+    /// Parses unary expressions (!, -, &, *), prefix `&` and `*` take and follow references
     ///
     /// Ascends to [`Parser::expr_postfix`]
     pub(super) fn expr_unary(&mut self) -> CompilerResult<Expr> {
@@ -295,6 +329,20 @@ impl TokenParser<'_> {
                 ))
             }
 
+            TokenKind::Ampersand | TokenKind::Asterisk => {
+                self.advance();
+                let value = Box::new(self.expr_unary()?);
+
+                Ok(Expr::new(
+                    current.span.merged(&value.span),
+                    if current.kind == TokenKind::Ampersand {
+                        ExprKind::AddressOf(value)
+                    } else {
+                        ExprKind::Deref(value)
+                    },
+                ))
+            }
+
             TokenKind::Plus => {
                 self.advance(); // unary plus = no-op
                 self.expr_unary()
@@ -317,6 +365,7 @@ impl TokenParser<'_> {
             expr = match &current.kind {
                 TokenKind::LeftParen => self.expr_finish_call(expr)?,
                 TokenKind::Dot => self.expr_get(expr)?,
+                TokenKind::Question => self.expr_propagate(expr)?,
                 TokenKind::LeftBrace if self.ctx.allow_struct_literal => {
                     self.expr_finish_struct(expr)?
                 }
@@ -379,6 +428,17 @@ impl TokenParser<'_> {
         ))
     }
 
+    // MARK: Propagate
+    /// Parses the `?` that unwraps an optional or a result, or returns `none` or the error from the enclosing function
+    pub(super) fn expr_propagate(&mut self, value: Expr) -> CompilerResult<Expr> {
+        let question = self.consume(TokenKind::Question)?;
+
+        Ok(Expr::new(
+            value.span.merged(&question.span),
+            ExprKind::Propagate(Box::new(value)),
+        ))
+    }
+
     // MARK: Struct
     /// Parses struct literal expressions
     pub(super) fn expr_finish_struct(&mut self, expr: Expr) -> CompilerResult<Expr> {
@@ -433,10 +493,18 @@ impl TokenParser<'_> {
             | TokenKind::FloatLiteral
             | TokenKind::IntLiteral
             | TokenKind::BoolLiteral
+            | TokenKind::NoneLiteral
             | TokenKind::StringLiteral => self.expr_literal(),
+            TokenKind::InterpolationStart => self.expr_interpolation(),
             TokenKind::LeftParen => self.expr_tuple_group(),
             TokenKind::LeftBrace => self.expr_block(),
             TokenKind::If => self.expr_if(),
+            // `ok` and `err` aren't keywords, they only construct results when called
+            TokenKind::Ident
+                if matches!(self.current().lexeme.as_str(), "ok" | "err") && self.check_next(TokenKind::LeftParen) =>
+            {
+                self.expr_result()
+            }
             TokenKind::Ident => self.expr_ident(),
 
             _ => Err(error!(
@@ -448,6 +516,62 @@ impl TokenParser<'_> {
         }
     }
 
+    // MARK: Result
+    /// Parses the `ok(value)` and `err(value)` constructors of results
+    pub(super) fn expr_result(&mut self) -> CompilerResult<Expr> {
+        let constructor = self.current();
+        self.advance();
+
+        self.consume(TokenKind::LeftParen)?;
+        let value = Box::new(self.parse_expression()?);
+        let right_paren = self.consume(TokenKind::RightParen)?;
+
+        Ok(Expr::new(
+            constructor.span.merged(&right_paren.span),
+            match constructor.lexeme.as_str() {
+                "ok" => ExprKind::Ok(value),
+                _ => ExprKind::Err(value),
+            },
+        ))
+    }
+
+    // MARK: Interpolation
+    /// Parses interpolated strings `"... {expr} ..."`
+    pub(super) fn expr_interpolation(&mut self) -> CompilerResult<Expr> {
+        let start = self.consume(TokenKind::InterpolationStart)?;
+
+        let mut parts = Vec::new();
+        push_segment(&mut parts, start.lexeme);
+
+        // the braces delimit the expression, so struct literals are unambiguous
+        let original_allow_struct_literal = self.ctx.allow_struct_literal;
+        self.ctx.allow_struct_literal = true;
+
+        let result = loop {
+            match self.parse_expression() {
+                Ok(expr) => parts.push(InterpolationPart::Expr(expr)),
+                Err(err) => break Err(err),
+            }
+
+            if let Ok(middle) = self.consume(TokenKind::InterpolationMiddle) {
+                push_segment(&mut parts, middle.lexeme);
+                continue;
+            }
+
+            break self.consume(TokenKind::InterpolationEnd);
+        };
+
+        self.ctx.allow_struct_literal = original_allow_struct_literal;
+        let end = result?;
+
+        push_segment(&mut parts, end.lexeme);
+
+        Ok(Expr::new(
+            start.span.merged(&end.span),
+            ExprKind::Interpolation(InterpolationExpr { parts }),
+        ))
+    }
+
     // MARK: Tuple/Group
     /// Parses tuples and grouped expressions `(...)`
     pub(super) fn expr_tuple_group(&mut self) -> CompilerResult<Expr> {
@@ -557,11 +681,23 @@ impl TokenParser<'_> {
     }
 
     // MARK: If
-    /// Parses an if expression
+    /// Parses an if expression, `if var x = maybe { ... }` unwraps an optional into `x`
     pub(super) fn expr_if(&mut self) -> CompilerResult<Expr> {
         // consume main branch
         let if_token = self.consume(TokenKind::If)?;
 
+        let binding = if self.consume(TokenKind::Var).is_ok() {
+            let ident_token = self.consume(TokenKind::Ident)?;
+            self.consume(TokenKind::Equal)?;
+
+            Some(Binding {
+                symbol: ident_token.as_symbol(),
+                scope_id: None,
+            })
+        } else {
+            None
+        };
+
         let original_allow_struct_literal = self.ctx.allow_struct_literal;
         self.ctx.allow_struct_literal = false;
 
@@ -591,6 +727,7 @@ impl TokenParser<'_> {
         Ok(Expr::new(
             span,
             ExprKind::If(IfExpr {
+                binding,
                 condition: Box::new(condition),
                 then_branch: Box::new(then_branch),
                 else_branch,
@@ -664,6 +801,8 @@ impl TokenParser<'_> {
                 ExprKind::Literal(LiteralExpr::String(current.lexeme.clone()))
             }
 
+            TokenKind::NoneLiteral => ExprKind::Literal(LiteralExpr::None),
+
             _ => unreachable!("expr_literal called on non-literal token {:#?}", current),
         };
 
@@ -686,3 +825,10 @@ impl TokenParser<'_> {
         ))
     }
 }
+
+/// Adds a literal segment of an interpolated string, empty segments are dropped
+fn push_segment(parts: &mut Vec<InterpolationPart>, segment: String) {
+    if !segment.is_empty() {
+        parts.push(InterpolationPart::Literal(segment));
+    }
+}
@@ -8,7 +8,9 @@ use crate::stages::{
 
 impl TokenParser<'_> {
     pub fn parse_statement(&mut self, semi: Option<bool>) -> CompilerResult<Stmt> {
-        let stmt = self.stmt_declaration()?;
+        let attributes = self.parse_attributes()?;
+        let mut stmt = self.stmt_declaration()?;
+        stmt.attributes = attributes;
 
         if semi.unwrap_or(true) {
             self.consume(TokenKind::Semicolon)?;
@@ -17,6 +19,61 @@ impl TokenParser<'_> {
         Ok(stmt)
     }
 
+    /// Parses a statement at the top level of a module, the only place imports may appear
+    pub fn parse_top_level_statement(&mut self) -> CompilerResult<Stmt> {
+        let attributes = self.parse_attributes()?;
+
+        if !self.check(TokenKind::Import) {
+            let mut stmt = self.parse_statement(None)?;
+            stmt.attributes = attributes;
+            return Ok(stmt);
+        }
+
+        let mut stmt = self.stmt_import()?;
+        stmt.attributes = attributes;
+        self.consume(TokenKind::Semicolon)?;
+
+        Ok(stmt)
+    }
+
+    // MARK: Attribute
+    /// Parses the attributes placed before a statement
+    ///
+    /// ```ignore
+    /// @allow(unused)
+    /// ```
+    pub(super) fn parse_attributes(&mut self) -> CompilerResult<Vec<Attribute>> {
+        let mut attributes = Vec::new();
+
+        while self.check(TokenKind::At) {
+            let at_token = self.consume(TokenKind::At)?;
+            let name = self.consume(TokenKind::Ident)?;
+
+            if name.lexeme != "allow" {
+                return Err(error!(ParserError::UnknownAttribute { name: name.lexeme.clone() }, name.span));
+            }
+
+            self.consume(TokenKind::LeftParen)?;
+
+            let mut arguments = Vec::new();
+
+            loop {
+                arguments.push(self.consume(TokenKind::Ident)?.as_symbol());
+
+                if self.consume(TokenKind::Comma).is_err() {
+                    break;
+                }
+            }
+
+            let mut span = at_token.span;
+            span.merge(&self.consume(TokenKind::RightParen)?.span);
+
+            attributes.push(Attribute { name: name.as_symbol(), arguments, span });
+        }
+
+        Ok(attributes)
+    }
+
     // MARK: Declaration
     /// Parses a declaration statement
     pub(super) fn stmt_declaration(&mut self) -> CompilerResult<Stmt> {
@@ -68,6 +125,31 @@ impl TokenParser<'_> {
         ))
     }
 
+    // MARK: Import
+    /// Parses an import statement
+    ///
+    /// ```ignore
+    /// import std.math;
+    /// ```
+    pub(super) fn stmt_import(&mut self) -> CompilerResult<Stmt> {
+        let import_token = self.consume(TokenKind::Import)?;
+        let mut span = import_token.span;
+
+        let mut path = Vec::new();
+
+        loop {
+            let segment = self.consume(TokenKind::Ident)?;
+            span.merge(&segment.span);
+            path.push(segment.as_symbol());
+
+            if self.consume(TokenKind::Dot).is_err() {
+                break;
+            }
+        }
+
+        Ok(Stmt::new(span, StmtKind::Import(ImportStmt { path })))
+    }
+
     // MARK: Function
     /// Parses a function declaration statement
     ///
@@ -10,6 +10,50 @@ impl TokenParser<'_> {
     // MARK: Type
     /// Parses a type annotation (does not consume anything other than the type itself)
     pub(super) fn parse_type(&mut self) -> CompilerResult<Type> {
+        let ty = self.parse_result_type()?;
+
+        let Ok(question) = self.consume(TokenKind::Question) else {
+            return Ok(ty);
+        };
+
+        // `T??` would be indistinguishable from `T?` at runtime
+        if let Ok(nested) = self.consume(TokenKind::Question) {
+            return Err(error!(
+                ParserError::NestedOptional {
+                    ty: TypeKind::Optional(Box::new(ty.clone())),
+                },
+                question.span.merged(&nested.span),
+            ));
+        }
+
+        Ok(Type::spanned(
+            question.span.maybe_merged(ty.span.as_ref()),
+            TypeKind::Optional(Box::new(ty)),
+        ))
+    }
+
+    /// Parses a type with an optional `!E` error type suffix, but without the optional `?` suffix
+    fn parse_result_type(&mut self) -> CompilerResult<Type> {
+        let ty = self.parse_non_optional_type()?;
+
+        if self.consume(TokenKind::Bang).is_err() {
+            return Ok(ty);
+        }
+
+        let error_type = self.parse_non_optional_type()?;
+        let span = ty.span.map(|span| span.maybe_merged(error_type.span.as_ref()));
+        let result_type = TypeKind::Result(Box::new(ty), Box::new(error_type));
+
+        // an error of `T!E!F` couldn't be told apart from the error of the inner result at runtime
+        if let Ok(bang) = self.consume(TokenKind::Bang) {
+            return Err(error!(ParserError::NestedResult { ty: result_type.clone() }, bang.span));
+        }
+
+        Ok(Type::new(span, result_type))
+    }
+
+    /// Parses a type without the optional `?` suffix
+    fn parse_non_optional_type(&mut self) -> CompilerResult<Type> {
         let current = self.current();
 
         match current.kind {
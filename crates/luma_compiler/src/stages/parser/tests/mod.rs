@@ -3,6 +3,7 @@ use crate::{
 };
 
 pub mod parse_func;
+pub mod parse_interpolation;
 pub mod parse_var;
 
 pub fn parse_ast(src: &str) -> Ast {
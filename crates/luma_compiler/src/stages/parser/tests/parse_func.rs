@@ -149,3 +149,13 @@ fn var_with_type_and_value() {
         )
     );
 }
+
+#[test]
+fn attributes_precede_statements() {
+    let ast = parse_ast("@allow(unused) @allow(shadowing, naming) func f() { }; var x = 1;");
+
+    assert_eq!(ast.statements[0].attributes.len(), 2);
+    assert!(ast.statements[0].allows("unused"));
+    assert!(ast.statements[0].allows("naming"));
+    assert!(!ast.statements[1].allows("unused"));
+}
@@ -0,0 +1,66 @@
+use crate::{ast::*, stages::parser::tests::parse_ast};
+use luma_core::Span;
+use pretty_assertions::assert_eq;
+
+fn initializer(src: &str) -> ExprKind {
+    let ast = parse_ast(src);
+
+    let StmtKind::Var(var) = &ast.statements[0].item else {
+        panic!("expected a variable declaration");
+    };
+
+    var.initializer.item.clone()
+}
+
+fn ident(name: &str) -> Expr {
+    Expr::new(
+        Span::ZERO,
+        ExprKind::Ident(IdentExpr {
+            symbol: SymbolKind::named(name.to_string()),
+        }),
+    )
+}
+
+#[test]
+fn interpolation_splits_segments_and_expressions() {
+    assert_eq!(
+        initializer(r#"var s = "hello {name}, you are {age} years old";"#),
+        ExprKind::Interpolation(InterpolationExpr {
+            parts: vec![
+                InterpolationPart::Literal("hello ".to_string()),
+                InterpolationPart::Expr(ident("name")),
+                InterpolationPart::Literal(", you are ".to_string()),
+                InterpolationPart::Expr(ident("age")),
+                InterpolationPart::Literal(" years old".to_string()),
+            ],
+        })
+    );
+}
+
+#[test]
+fn interpolation_nests_blocks_and_strings() {
+    assert_eq!(
+        initializer(r#"var s = "{a}{ { "x{b}" } }\{}";"#),
+        ExprKind::Interpolation(InterpolationExpr {
+            parts: vec![
+                InterpolationPart::Expr(ident("a")),
+                InterpolationPart::Expr(Expr::new(
+                    Span::ZERO,
+                    ExprKind::Block(BlockExpr {
+                        statements: vec![],
+                        tail_expr: Some(Box::new(Expr::new(
+                            Span::ZERO,
+                            ExprKind::Interpolation(InterpolationExpr {
+                                parts: vec![
+                                    InterpolationPart::Literal("x".to_string()),
+                                    InterpolationPart::Expr(ident("b")),
+                                ],
+                            }),
+                        ))),
+                    }),
+                )),
+                InterpolationPart::Literal("{}".to_string()),
+            ],
+        })
+    );
+}
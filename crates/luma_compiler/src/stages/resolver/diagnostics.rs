@@ -0,0 +1,15 @@
+use luma_diagnostic::define_diagnostics;
+
+define_diagnostics! {
+    pub enum ResolverError {
+        #[Error(E0201, "unknown module", "no library named '{name}' was registered with the compiler")]
+        /// Only libraries registered with `LumaCompiler::with_libraries` can be imported.
+        ///
+        /// ```luma
+        /// import does.not.exist;
+        /// ```
+        UnknownModule {
+            name: String,
+        },
+    }
+}
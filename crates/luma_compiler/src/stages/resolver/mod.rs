@@ -0,0 +1,98 @@
+use std::collections::{HashMap, HashSet};
+
+use luma_diagnostic::error;
+
+use crate::{CompilerContext, CompilerStage, LexerStage, ParserStage, ast::*};
+
+mod diagnostics;
+
+pub use diagnostics::*;
+
+#[cfg(test)]
+mod tests;
+
+/// Resolves the imports of every module, placing the declarations of the imported libraries before the import.
+///
+/// A library is brought into a module at most once, no matter how many of its imports lead to it
+pub struct ModuleResolutionStage;
+
+impl CompilerStage<'_> for ModuleResolutionStage {
+    type Input = Vec<Ast>;
+    type Output = Vec<Ast>;
+
+    fn name() -> &'static str {
+        "resolver"
+    }
+
+    fn process(self, ctx: &CompilerContext, input: Self::Input) -> Self::Output {
+        let mut resolver = ModuleResolver {
+            ctx,
+            libraries: HashMap::new(),
+        };
+
+        input
+            .into_iter()
+            .map(|mut ast| {
+                let mut imported = HashSet::new();
+                ast.statements = resolver.resolve(std::mem::take(&mut ast.statements), &mut imported);
+                ast
+            })
+            .collect()
+    }
+}
+
+struct ModuleResolver<'ctx> {
+    ctx: &'ctx CompilerContext,
+
+    /// statements of every library parsed so far, `None` if it failed to parse
+    libraries: HashMap<String, Option<Vec<Stmt>>>,
+}
+
+impl ModuleResolver<'_> {
+    /// Returns the statements with the declarations of every library they import, `imported` holds the libraries already brought in
+    fn resolve(&mut self, statements: Vec<Stmt>, imported: &mut HashSet<String>) -> Vec<Stmt> {
+        let mut resolved = Vec::with_capacity(statements.len());
+
+        for stmt in statements {
+            if let StmtKind::Import(import) = &stmt.item {
+                let name = import.module_name();
+
+                if imported.insert(name.clone())
+                    && let Some(library) = self.library(&name, &stmt)
+                {
+                    resolved.extend(self.resolve(library, imported));
+                }
+            }
+
+            resolved.push(stmt);
+        }
+
+        resolved
+    }
+
+    /// Parses the library on its first import
+    fn library(&mut self, name: &str, import: &Stmt) -> Option<Vec<Stmt>> {
+        if let Some(statements) = self.libraries.get(name) {
+            return statements.clone();
+        }
+
+        let Some(&source_id) = self.ctx.libraries.get(name) else {
+            let diagnostic = error!(ResolverError::UnknownModule { name: name.to_string() }, import.span);
+            self.ctx.add_diag(diagnostic);
+            return None;
+        };
+
+        let diagnostics = self.ctx.get_diagnostics().len();
+
+        let tokens = LexerStage.process(self.ctx, vec![source_id]);
+        let statements = ParserStage
+            .process(self.ctx, &tokens)
+            .pop()
+            .filter(|_| self.ctx.get_diagnostics().len() == diagnostics)
+            .map(|ast| ast.statements);
+
+        self.libraries.insert(name.to_string(), statements.clone());
+
+        statements
+    }
+}
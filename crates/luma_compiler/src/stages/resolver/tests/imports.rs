@@ -0,0 +1,49 @@
+use pretty_assertions::assert_eq;
+
+use crate::stages::resolver::tests::{outline, resolve};
+
+const MATH: (&str, &str) = ("lib.math", "pub func square(x: i32): i32 { x * x };");
+const GEOMETRY: (&str, &str) = ("lib.geometry", "import lib.math; pub func area(side: i32): i32 { square(side) };");
+
+#[test]
+fn imported_declarations_come_before_the_import() {
+    let ast = resolve("import lib.math; var x = square(3);", &[MATH]).unwrap();
+
+    assert_eq!(outline(&ast), ["square", "import lib.math", "x"]);
+}
+
+#[test]
+fn libraries_resolve_their_own_imports() {
+    let ast = resolve("import lib.geometry; var x = area(3);", &[MATH, GEOMETRY]).unwrap();
+
+    assert_eq!(outline(&ast), ["square", "import lib.math", "area", "import lib.geometry", "x"]);
+}
+
+#[test]
+fn libraries_are_brought_in_once() {
+    let ast = resolve("import lib.math; import lib.geometry; import lib.math;", &[MATH, GEOMETRY]).unwrap();
+
+    assert_eq!(
+        outline(&ast),
+        ["square", "import lib.math", "import lib.math", "area", "import lib.geometry", "import lib.math"]
+    );
+}
+
+#[test]
+fn imported_declarations_keep_their_source() {
+    let ast = resolve("import lib.math;", &[MATH]).unwrap();
+
+    assert_ne!(ast.statements[0].span.source_id, ast.span.source_id);
+}
+
+#[test]
+fn unknown_modules_are_reported() {
+    let diagnostics = resolve("import lib.missing;", &[MATH]).unwrap_err();
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].title, "unknown module");
+    assert_eq!(
+        diagnostics[0].annotation.as_deref(),
+        Some("no library named 'lib.missing' was registered with the compiler")
+    );
+}
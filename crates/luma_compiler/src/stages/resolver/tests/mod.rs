@@ -0,0 +1,41 @@
+use luma_diagnostic::Diagnostic;
+
+use crate::{
+    CompilerContext, LexerStage, ModuleResolutionStage, ParserStage, ast::*, compiler::run_stage,
+};
+
+pub mod imports;
+
+/// Parses the source and resolves its imports against the `(name, source)` libraries
+pub fn resolve(src: &str, libraries: &[(&str, &str)]) -> Result<Ast, Vec<Diagnostic>> {
+    let mut ctx = CompilerContext::default();
+    let source_id = ctx.sources.add_source(src.into());
+
+    for (name, source) in libraries {
+        let library_id = ctx.sources.add_source((*source).into());
+        ctx.libraries.insert(name.to_string(), library_id);
+    }
+
+    let result = run_stage(&ctx, LexerStage, vec![source_id])
+        .and_then(|tokens| run_stage(&ctx, ParserStage, &tokens))
+        .and_then(|asts| run_stage(&ctx, ModuleResolutionStage, asts));
+
+    match result {
+        Ok(asts) => Ok(asts.into_iter().next().expect("expected one AST")),
+        Err(()) => Err(ctx.get_diagnostics().clone()),
+    }
+}
+
+/// Names of the top-level declarations, `import path` for imports
+pub fn outline(ast: &Ast) -> Vec<String> {
+    ast.statements
+        .iter()
+        .map(|stmt| match &stmt.item {
+            StmtKind::Func(func) => func.symbol.name().to_string(),
+            StmtKind::Struct(decl) => decl.symbol.name().to_string(),
+            StmtKind::Var(var) => var.symbol.name().to_string(),
+            StmtKind::Import(import) => format!("import {}", import.module_name()),
+            kind => kind.to_string(),
+        })
+        .collect()
+}
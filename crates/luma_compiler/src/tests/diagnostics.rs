@@ -0,0 +1,84 @@
+use std::collections::HashSet;
+
+use luma_core::CodeSource;
+use luma_diagnostic::{JsonPrinter, SarifPrinter};
+use pretty_assertions::assert_eq;
+
+use crate::{
+    LumaCompiler,
+    diagnostics::{EXPLANATIONS, explain},
+};
+
+#[test]
+fn codes_are_unique() {
+    let mut codes = HashSet::new();
+
+    for explanation in EXPLANATIONS.iter().flat_map(|explanations| explanations.iter()) {
+        assert!(codes.insert(explanation.code), "'{}' is used twice", explanation.code);
+        assert!(!explanation.lines.is_empty(), "'{}' has no explanation", explanation.code);
+    }
+}
+
+#[test]
+fn diagnostics_carry_their_code() {
+    let code = |src: &str| LumaCompiler::new().compile([CodeSource::from(src)]).diagnostics[0].code;
+
+    assert_eq!(code("var x = ;"), Some("E0102"));
+    assert_eq!(code("var x = none;"), Some("E0313"));
+    assert_eq!(code("pub func f(a: i32) { };"), Some("W0302"));
+}
+
+#[test]
+fn codes_are_explained() {
+    let explanation = explain("e0313").expect("expected an explanation");
+
+    assert_eq!(explanation.code, "E0313");
+    assert!(explanation.to_string().starts_with("E0313: untyped none\n\n`none` fits every optional type"));
+    assert_eq!(explain("E9999"), None);
+}
+
+/// A syntax error on the second line, after a two byte char
+fn syntax_error() -> crate::compiler::CompileResult {
+    LumaCompiler::new().compile([CodeSource::new(
+        String::from("var a = 1;\nvar s = \"é\"; var x = ;"),
+        Some(String::from("main.luma")),
+    )])
+}
+
+#[test]
+fn diagnostics_render_as_json() {
+    let result = syntax_error();
+
+    assert_eq!(
+        JsonPrinter::print(&result.sources, &result.diagnostics),
+        concat!(
+            r#"[{"level":"error","code":"E0102","title":"unexpected token","annotation":"found ';'","lint":null,"#,
+            r#""span":{"file":"main.luma","start":33,"end":34,"line":2,"column":22,"end_line":2,"end_column":23},"#,
+            r#""contexts":[]}]"#
+        )
+    );
+
+    let result = LumaCompiler::new().compile([CodeSource::from("pub func f(a: i32) { };")]);
+    let rendered = JsonPrinter::print(&result.sources, &result.diagnostics);
+
+    assert!(rendered.contains(r#""level":"warning","code":"W0302""#));
+    assert!(rendered.contains(r#""lint":"unused_parameters","span":{"file":null,"start":11"#));
+    assert_eq!(JsonPrinter::print(&result.sources, &[]), "[]");
+}
+
+#[test]
+fn diagnostics_render_as_sarif() {
+    let result = syntax_error();
+
+    assert_eq!(
+        SarifPrinter::print(&result.sources, &result.diagnostics),
+        concat!(
+            r#"{"$schema":"https://json.schemastore.org/sarif-2.1.0.json","version":"2.1.0","runs":[{"#,
+            r#""tool":{"driver":{"name":"luma","rules":[{"id":"E0102","shortDescription":{"text":"unexpected token"}}]}},"#,
+            r#""columnKind":"unicodeCodePoints","results":[{"ruleId":"E0102","level":"error","#,
+            r#""message":{"text":"unexpected token: found ';'"},"locations":[{"physicalLocation":{"#,
+            r#""artifactLocation":{"uri":"main.luma"},"#,
+            r#""region":{"startLine":2,"startColumn":22,"endLine":2,"endColumn":23,"byteOffset":33,"byteLength":1}}}]}]}]}"#
+        )
+    );
+}
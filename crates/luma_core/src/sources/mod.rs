@@ -55,6 +55,23 @@ impl CodeSource {
     pub const fn is_file(&self) -> bool {
         self.file_path.is_some()
     }
+
+    /// The 1-based line and column of a byte offset, columns count chars rather than bytes
+    #[must_use]
+    pub fn line_column(&self, offset: usize) -> (usize, usize) {
+        let mut offset = offset.min(self.content.len());
+        while !self.content.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        let before = &self.content[..offset];
+        let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+
+        let line = before.matches('\n').count() + 1;
+        let column = before[line_start..].chars().count() + 1;
+
+        (line, column)
+    }
 }
 
 impl From<String> for CodeSource {
@@ -0,0 +1,78 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, parse_macro_input};
+
+/// Maps a struct with named fields to the Luma struct of the same name, field by field.
+///
+/// Implements `IntoLuma`, `FromLuma` and `LumaStruct`, every field type has to implement both conversions
+#[proc_macro_derive(LumaStruct)]
+pub fn derive_luma_struct(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(syn::Error::new_spanned(&data.fields, "LumaStruct requires named fields")),
+        },
+        _ => return Err(syn::Error::new_spanned(&input.ident, "LumaStruct can only be derived for structs")),
+    };
+
+    let ident = &input.ident;
+    let name = ident.to_string();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let idents = fields.iter().map(|field| field.ident.as_ref().expect("fields are named")).collect::<Vec<_>>();
+    let names = idents.iter().map(|ident| ident.to_string()).collect::<Vec<_>>();
+    let types = fields.iter().map(|field| &field.ty);
+
+    let luma_type = quote! {
+        fn luma_type() -> ::luma_vm::TypeKind {
+            ::luma_vm::TypeKind::Named {
+                name: ::std::string::String::from(#name),
+                def_id: ::std::option::Option::None,
+            }
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::luma_vm::IntoLuma for #ident #ty_generics #where_clause {
+            #luma_type
+
+            fn into_luma(self) -> ::luma_vm::BytecodeValue {
+                ::luma_vm::BytecodeValue::Struct {
+                    name: ::std::string::String::from(#name),
+                    fields: ::std::vec![#(
+                        (::std::string::String::from(#names), ::luma_vm::IntoLuma::into_luma(self.#idents))
+                    ),*],
+                }
+            }
+        }
+
+        impl #impl_generics ::luma_vm::FromLuma for #ident #ty_generics #where_clause {
+            #luma_type
+
+            fn from_luma(value: ::luma_vm::BytecodeValue) -> ::std::result::Result<Self, ::luma_vm::RuntimeErrorKind> {
+                let mut fields = ::luma_vm::StructFields::new(#name, value)?;
+
+                ::std::result::Result::Ok(Self {
+                    #(#idents: fields.take(#names)?),*
+                })
+            }
+        }
+
+        impl #impl_generics ::luma_vm::LumaStruct for #ident #ty_generics #where_clause {
+            const NAME: &'static str = #name;
+
+            fn fields() -> ::std::vec::Vec<(&'static str, ::luma_vm::TypeKind)> {
+                ::std::vec![#(
+                    (#names, <#types as ::luma_vm::FromLuma>::luma_type())
+                ),*]
+            }
+        }
+    })
+}
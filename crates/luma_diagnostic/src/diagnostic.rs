@@ -1,13 +1,20 @@
+use std::fmt::{self, Display, Formatter};
+
 use luma_core::Span;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Diagnostic {
     pub level: DiagnosticLevel,
+    /// stable code of the diagnostic like `E0101`, see [`Explanation`]
+    pub code: Option<&'static str>,
     pub title: String,
     pub annotation: Option<String>,
     pub span: Option<Span>,
     pub additional_contexts: Vec<DiagnosticContext>,
 
+    /// name of the lint reporting the diagnostic, its level can be configured
+    pub lint: Option<String>,
+
     #[cfg(debug_assertions)]
     pub thrower: CallerInfo,
 }
@@ -27,6 +34,11 @@ impl Diagnostic {
         self.additional_contexts.push(context);
         self
     }
+
+    pub fn lint(mut self, lint: impl Into<String>) -> Self {
+        self.lint = Some(lint.into());
+        self
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -58,6 +70,7 @@ pub struct CallerInfo {
 }
 
 pub trait AsDiagnostic {
+    fn code(&self) -> &'static str;
     fn level(&self) -> DiagnosticLevel;
     fn title(&self) -> String;
     fn annotation(&self) -> Option<String>;
@@ -66,4 +79,42 @@ pub trait AsDiagnostic {
 pub trait AsDiagnosticContext {
     fn kind(&self) -> DiagnosticContextKind;
     fn annotation(&self) -> Option<String>;
-}
\ No newline at end of file
+}
+
+/// Long-form explanation of a diagnostic code, written as the doc comment of its variant in [`crate::define_diagnostics`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Explanation {
+    pub code: &'static str,
+    pub title: &'static str,
+    /// the lines of the doc comment
+    pub lines: &'static [&'static str],
+}
+
+impl Explanation {
+    /// Finds the explanation of `code` among the explanations of several diagnostic enums
+    #[must_use]
+    pub fn find(catalogs: &[&'static [Explanation]], code: &str) -> Option<Explanation> {
+        catalogs
+            .iter()
+            .flat_map(|explanations| explanations.iter())
+            .find(|explanation| explanation.code.eq_ignore_ascii_case(code))
+            .copied()
+    }
+}
+
+impl Display for Explanation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}: {}", self.code, self.title)?;
+
+        if !self.lines.is_empty() {
+            writeln!(f)?;
+        }
+
+        for line in self.lines {
+            // doc comments keep the space after `///`
+            writeln!(f, "{}", line.strip_prefix(' ').unwrap_or(line))?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+use std::fmt::{self, Display, Formatter, Write};
+
+use luma_core::{SourceManager, Span};
+
+use crate::DiagnosticLevel;
+
+/// A JSON document, serialized compactly through [`Display`]
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum JsonValue {
+    #[cfg_attr(not(feature = "json"), allow(dead_code))]
+    Null,
+    Number(usize),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(&'static str, JsonValue)>),
+}
+
+impl JsonValue {
+    pub fn string(value: impl Into<String>) -> Self {
+        JsonValue::String(value.into())
+    }
+
+    #[cfg_attr(not(feature = "json"), allow(dead_code))]
+    pub fn optional<T>(value: Option<T>, f: impl FnOnce(T) -> JsonValue) -> Self {
+        value.map_or(JsonValue::Null, f)
+    }
+}
+
+impl Display for JsonValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonValue::Null => f.write_str("null"),
+            JsonValue::Number(number) => write!(f, "{number}"),
+            JsonValue::String(string) => {
+                f.write_char('"')?;
+
+                for char in string.chars() {
+                    match char {
+                        '"' => f.write_str("\\\"")?,
+                        '\\' => f.write_str("\\\\")?,
+                        '\n' => f.write_str("\\n")?,
+                        '\r' => f.write_str("\\r")?,
+                        '\t' => f.write_str("\\t")?,
+                        char if char.is_control() => write!(f, "\\u{:04x}", char as u32)?,
+                        char => f.write_char(char)?,
+                    }
+                }
+
+                f.write_char('"')
+            }
+            JsonValue::Array(values) => {
+                f.write_char('[')?;
+
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{value}")?;
+                }
+
+                f.write_char(']')
+            }
+            JsonValue::Object(fields) => {
+                f.write_char('{')?;
+
+                for (index, (key, value)) in fields.iter().enumerate() {
+                    if index > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{}:{value}", JsonValue::string(*key))?;
+                }
+
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+/// Where a span lies in its source, `None` when the source isn't known to the manager
+pub(crate) struct Location<'a> {
+    pub file: Option<&'a str>,
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+impl<'a> Location<'a> {
+    pub fn of(sources: &'a SourceManager, span: Span) -> Option<Self> {
+        let source = sources.get_source(span.source_id)?;
+
+        let (start, end) = (span.start as usize, span.end as usize);
+        let (line, column) = source.line_column(start);
+        let (end_line, end_column) = source.line_column(end);
+
+        Some(Self {
+            file: source.file_path.as_deref(),
+            start,
+            end,
+            line,
+            column,
+            end_line,
+            end_column,
+        })
+    }
+}
+
+pub(crate) fn level_name(level: DiagnosticLevel) -> &'static str {
+    match level {
+        DiagnosticLevel::Error => "error",
+        DiagnosticLevel::Warning => "warning",
+        DiagnosticLevel::Note => "note",
+    }
+}
@@ -0,0 +1,66 @@
+use luma_core::{SourceManager, Span};
+
+use crate::{
+    Diagnostic, DiagnosticContext, DiagnosticContextKind,
+    json::{JsonValue, Location, level_name},
+};
+
+/// Renders diagnostics as a JSON array, for tools consuming the compiler's output.
+///
+/// Lines and columns are 1-based, columns count chars, `start` and `end` are byte offsets into the source
+pub struct JsonPrinter<'a> {
+    sources: &'a SourceManager,
+}
+
+impl<'a> JsonPrinter<'a> {
+    pub fn print(sources: &'a SourceManager, diagnostics: &[Diagnostic]) -> String {
+        let printer = Self { sources };
+
+        JsonValue::Array(diagnostics.iter().map(|diagnostic| printer.diagnostic(diagnostic)).collect()).to_string()
+    }
+
+    fn diagnostic(&self, diagnostic: &Diagnostic) -> JsonValue {
+        JsonValue::Object(vec![
+            ("level", JsonValue::string(level_name(diagnostic.level))),
+            ("code", JsonValue::optional(diagnostic.code, JsonValue::string)),
+            ("title", JsonValue::string(&diagnostic.title)),
+            ("annotation", JsonValue::optional(diagnostic.annotation.as_deref(), JsonValue::string)),
+            ("lint", JsonValue::optional(diagnostic.lint.as_deref(), JsonValue::string)),
+            ("span", self.span(diagnostic.span)),
+            (
+                "contexts",
+                JsonValue::Array(diagnostic.additional_contexts.iter().map(|ctx| self.context(ctx)).collect()),
+            ),
+        ])
+    }
+
+    fn context(&self, ctx: &DiagnosticContext) -> JsonValue {
+        JsonValue::Object(vec![
+            ("kind", JsonValue::string(context_kind_name(ctx.kind))),
+            ("annotation", JsonValue::optional(ctx.annotation.as_deref(), JsonValue::string)),
+            ("span", self.span(ctx.span)),
+        ])
+    }
+
+    fn span(&self, span: Option<Span>) -> JsonValue {
+        JsonValue::optional(span.and_then(|span| Location::of(self.sources, span)), |location| {
+            JsonValue::Object(vec![
+                ("file", JsonValue::optional(location.file, JsonValue::string)),
+                ("start", JsonValue::Number(location.start)),
+                ("end", JsonValue::Number(location.end)),
+                ("line", JsonValue::Number(location.line)),
+                ("column", JsonValue::Number(location.column)),
+                ("end_line", JsonValue::Number(location.end_line)),
+                ("end_column", JsonValue::Number(location.end_column)),
+            ])
+        })
+    }
+}
+
+fn context_kind_name(kind: DiagnosticContextKind) -> &'static str {
+    match kind {
+        DiagnosticContextKind::Primary => "primary",
+        DiagnosticContextKind::Context => "context",
+        DiagnosticContextKind::Unannotated => "unannotated",
+    }
+}
@@ -6,6 +6,30 @@ cfg_select! {
         mod pretty_print;
         pub use pretty_print::Printer;
     }
+    _ => {}
+}
+
+cfg_select! {
+    any(feature = "json", feature = "sarif") => {
+        mod json;
+    }
+    _ => {}
+}
+
+cfg_select! {
+    feature = "json" => {
+        mod json_print;
+        pub use json_print::JsonPrinter;
+    }
+    _ => {}
+}
+
+cfg_select! {
+    feature = "sarif" => {
+        mod sarif_print;
+        pub use sarif_print::SarifPrinter;
+    }
+    _ => {}
 }
 
 mod macros;
@@ -4,7 +4,8 @@ macro_rules! define_diagnostics {
     (
         $enum_vis:vis enum $enum_name:ident {
             $(
-                #[$level:tt($title:expr $(, $annotation:expr)?)]
+                #[$level:tt($code:ident, $title:expr $(, $annotation:expr)?)]
+                $(#[doc = $explanation:literal])*
                 $err_name:ident $( { $($field:ident : $typ:ty),* $(,)? } )?
             ),* $(,)?
         }
@@ -12,11 +13,33 @@ macro_rules! define_diagnostics {
         #[derive(Debug, Clone)]
         $enum_vis enum $enum_name {
             $(
+                $(#[doc = $explanation])*
                 $err_name $( { $($field : $typ),* } )?,
             )*
         }
 
+        impl $enum_name {
+            /// The explanations of every code of the enum, in declaration order
+            pub const EXPLANATIONS: &'static [$crate::Explanation] = &[
+                $(
+                    $crate::Explanation {
+                        code: stringify!($code),
+                        title: $title,
+                        lines: &[$($explanation),*],
+                    },
+                )*
+            ];
+        }
+
         impl $crate::AsDiagnostic for $enum_name {
+            fn code(&self) -> &'static str {
+                match self {
+                    $(
+                        $enum_name::$err_name { .. } => stringify!($code),
+                    )*
+                }
+            }
+
             fn level(&self) -> $crate::DiagnosticLevel {
                 match self {
                     $(
@@ -55,6 +78,7 @@ macro_rules! diagnostic {
     ($level:expr, $error:expr, [$($contexts:expr,)*$(,)?], $span:expr$(,)?) => {
         $crate::Diagnostic {
             level: $level,
+            code: Some($crate::AsDiagnostic::code(&$error)),
             title: $crate::AsDiagnostic::title(&$error),
             annotation: $crate::AsDiagnostic::annotation(&$error),
             span: $span,
@@ -63,6 +87,7 @@ macro_rules! diagnostic {
                     $contexts,
                 )*
             ],
+            lint: None,
 
             #[cfg(debug_assertions)]
             thrower: $crate::CallerInfo {
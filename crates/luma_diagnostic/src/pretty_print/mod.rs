@@ -1,4 +1,4 @@
-use annotate_snippets::{AnnotationKind, Group, Level, Origin, Renderer, Snippet};
+use annotate_snippets::{AnnotationKind, Group, Level, Origin, Renderer, Snippet, Title};
 use luma_core::SourceManager;
 
 use crate::{Diagnostic, DiagnosticContextKind, DiagnosticLevel};
@@ -49,11 +49,9 @@ impl<'a> Printer<'a> {
                 }
             }
 
-            Level::from(diagnostic.level)
-                .primary_title(diagnostic.title.clone())
-                .element(snippet)
+            self.title(diagnostic).element(snippet)
         } else {
-            let title = Level::from(diagnostic.level).primary_title(diagnostic.title.clone());
+            let title = self.title(diagnostic);
 
             if let Some(annotation) = &diagnostic.annotation {
                 title.element(Level::NOTE.message(annotation.clone()))
@@ -62,6 +60,10 @@ impl<'a> Printer<'a> {
             }
         };
 
+        if let Some(lint) = &diagnostic.lint {
+            notes.push(Level::NOTE.message(format!("reported by the '{lint}' lint")));
+        }
+
         let primary_group = primary_group.elements(notes);
 
         report.push(primary_group);
@@ -82,6 +84,15 @@ impl<'a> Printer<'a> {
 
         report
     }
+
+    fn title<'report>(&self, diagnostic: &Diagnostic) -> Title<'report> {
+        let title = Level::from(diagnostic.level).primary_title(diagnostic.title.clone());
+
+        match diagnostic.code {
+            Some(code) => title.id(code),
+            None => title,
+        }
+    }
 }
 
 impl<'a> From<DiagnosticLevel> for Level<'a> {
@@ -0,0 +1,121 @@
+use luma_core::{SourceManager, Span};
+
+use crate::{
+    Diagnostic,
+    json::{JsonValue, Location, level_name},
+};
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Renders diagnostics as a SARIF 2.1.0 log with a single run, for code scanning and review tools.
+///
+/// Every diagnostic code becomes a rule, columns count chars
+pub struct SarifPrinter<'a> {
+    sources: &'a SourceManager,
+}
+
+impl<'a> SarifPrinter<'a> {
+    pub fn print(sources: &'a SourceManager, diagnostics: &[Diagnostic]) -> String {
+        let printer = Self { sources };
+
+        let mut rules = Vec::new();
+        let mut codes = Vec::new();
+
+        for diagnostic in diagnostics {
+            if let Some(code) = diagnostic.code
+                && !codes.contains(&code)
+            {
+                codes.push(code);
+                rules.push(JsonValue::Object(vec![
+                    ("id", JsonValue::string(code)),
+                    ("shortDescription", Self::message(&diagnostic.title)),
+                ]));
+            }
+        }
+
+        let driver = JsonValue::Object(vec![("name", JsonValue::string("luma")), ("rules", JsonValue::Array(rules))]);
+
+        let run = JsonValue::Object(vec![
+            ("tool", JsonValue::Object(vec![("driver", driver)])),
+            ("columnKind", JsonValue::string("unicodeCodePoints")),
+            (
+                "results",
+                JsonValue::Array(diagnostics.iter().map(|diagnostic| printer.result(diagnostic)).collect()),
+            ),
+        ]);
+
+        JsonValue::Object(vec![
+            ("$schema", JsonValue::string(SCHEMA)),
+            ("version", JsonValue::string("2.1.0")),
+            ("runs", JsonValue::Array(vec![run])),
+        ])
+        .to_string()
+    }
+
+    fn result(&self, diagnostic: &Diagnostic) -> JsonValue {
+        let text = match &diagnostic.annotation {
+            Some(annotation) => format!("{}: {annotation}", diagnostic.title),
+            None => diagnostic.title.clone(),
+        };
+
+        let mut result = Vec::new();
+
+        if let Some(code) = diagnostic.code {
+            result.push(("ruleId", JsonValue::string(code)));
+        }
+
+        result.push(("level", JsonValue::string(level_name(diagnostic.level))));
+        result.push(("message", Self::message(&text)));
+        result.push(("locations", JsonValue::Array(self.location(diagnostic.span, None).into_iter().collect())));
+
+        let related = diagnostic
+            .additional_contexts
+            .iter()
+            .filter_map(|ctx| self.location(ctx.span, ctx.annotation.as_deref()))
+            .collect::<Vec<_>>();
+
+        if !related.is_empty() {
+            result.push(("relatedLocations", JsonValue::Array(related)));
+        }
+
+        if let Some(lint) = &diagnostic.lint {
+            result.push(("properties", JsonValue::Object(vec![("lint", JsonValue::string(lint))])));
+        }
+
+        JsonValue::Object(result)
+    }
+
+    fn location(&self, span: Option<Span>, message: Option<&str>) -> Option<JsonValue> {
+        let location = Location::of(self.sources, span?)?;
+
+        let region = JsonValue::Object(vec![
+            ("startLine", JsonValue::Number(location.line)),
+            ("startColumn", JsonValue::Number(location.column)),
+            ("endLine", JsonValue::Number(location.end_line)),
+            ("endColumn", JsonValue::Number(location.end_column)),
+            ("byteOffset", JsonValue::Number(location.start)),
+            ("byteLength", JsonValue::Number(location.end - location.start)),
+        ]);
+
+        let mut physical = Vec::new();
+
+        // sources without a path have no artifact to point at
+        if let Some(file) = location.file {
+            physical.push(("artifactLocation", JsonValue::Object(vec![("uri", JsonValue::string(file))])));
+        }
+
+        physical.push(("region", region));
+
+        let mut fields = vec![("physicalLocation", JsonValue::Object(physical))];
+
+        if let Some(message) = message {
+            fields.push(("message", Self::message(message)));
+        }
+
+        Some(JsonValue::Object(fields))
+    }
+
+    fn message(text: &str) -> JsonValue {
+        JsonValue::Object(vec![("text", JsonValue::string(text))])
+    }
+}
@@ -0,0 +1,64 @@
+//! The Luma standard library, Luma sources importable as `std` or one of its modules plus the host functions they call.
+//!
+//! `std.fs`, `std.env` and `std.time` call the natives of the VM's [`Capabilities`](luma_vm::Capabilities), they
+//! only compile for VMs granted the capability and aren't part of `import std;`.
+//!
+//! The natives have to be registered with the VM and declared to the compiler alongside the libraries:
+//!
+//! ```ignore
+//! let mut vm = LumaVM::new();
+//! luma_std::register(&mut vm);
+//!
+//! let result = LumaCompiler::new()
+//!     .with_natives(vm.native_signatures())
+//!     .with_libraries(luma_std::libraries())
+//!     .compile(sources);
+//! ```
+
+use std::io::Write;
+
+use luma_compiler::Library;
+use luma_core::CodeSource;
+use luma_vm::LumaVM;
+
+mod natives;
+
+#[cfg(test)]
+mod tests;
+
+/// `(module name, file, source)` of every module of the standard library
+const MODULES: &[(&str, &str, &str)] = &[
+    ("std", "std.luma", include_str!("../lib/std.luma")),
+    ("std.io", "io.luma", include_str!("../lib/io.luma")),
+    ("std.math", "math.luma", include_str!("../lib/math.luma")),
+    ("std.string", "string.luma", include_str!("../lib/string.luma")),
+    ("std.convert", "convert.luma", include_str!("../lib/convert.luma")),
+    ("std.assert", "assert.luma", include_str!("../lib/assert.luma")),
+    ("std.fs", "fs.luma", include_str!("../lib/fs.luma")),
+    ("std.env", "env.luma", include_str!("../lib/env.luma")),
+    ("std.time", "time.luma", include_str!("../lib/time.luma")),
+];
+
+/// The modules of the standard library, to be registered with the compiler
+pub fn libraries() -> Vec<Library> {
+    MODULES
+        .iter()
+        .map(|(name, file, source)| {
+            Library::new(*name, CodeSource::new(source.to_string(), Some(format!("<std>/{file}"))))
+        })
+        .collect()
+}
+
+/// Registers the natives of the standard library, printing to the process' stdout and stderr
+pub fn register(vm: &mut LumaVM) {
+    register_with_output(vm, std::io::stdout(), std::io::stderr());
+}
+
+/// Registers the natives of the standard library, printing to the given writers
+pub fn register_with_output(vm: &mut LumaVM, out: impl Write + 'static, err: impl Write + 'static) {
+    natives::io::register(vm, out, err);
+    natives::math::register(vm);
+    natives::string::register(vm);
+    natives::convert::register(vm);
+    natives::assert::register(vm);
+}
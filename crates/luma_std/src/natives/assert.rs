@@ -0,0 +1,7 @@
+use luma_vm::LumaVM;
+
+pub fn register(vm: &mut LumaVM) {
+    vm.register_fn("std_assert", |condition: bool, message: String| {
+        if condition { Ok(()) } else { Err(format!("assertion failed: {message}")) }
+    });
+}
@@ -0,0 +1,20 @@
+use luma_vm::LumaVM;
+
+pub fn register(vm: &mut LumaVM) {
+    // the same parsing as the `parse_int` and `parse_float` intrinsics
+    vm.register_fn("std_is_int", |text: String| text.trim().parse::<i64>().is_ok());
+    vm.register_fn("std_is_float", |text: String| text.trim().parse::<f64>().is_ok());
+    vm.register_fn("std_to_float", |x: i64| x as f64);
+    vm.register_fn("std_to_int", to_int);
+}
+
+/// Truncates towards zero, NaN and floats beyond the range of `i64` are an error instead of saturating
+fn to_int(x: f64) -> Result<i64, String> {
+    let truncated = x.trunc();
+
+    if truncated.is_nan() || truncated < i64::MIN as f64 || truncated >= i64::MAX as f64 {
+        return Err(format!("{x} doesn't fit in an i64"));
+    }
+
+    Ok(truncated as i64)
+}
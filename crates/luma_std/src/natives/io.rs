@@ -0,0 +1,22 @@
+use std::{cell::RefCell, io::Write, rc::Rc};
+
+use luma_vm::LumaVM;
+
+pub fn register(vm: &mut LumaVM, out: impl Write + 'static, err: impl Write + 'static) {
+    let (out, err) = (Rc::new(RefCell::new(out)), Rc::new(RefCell::new(err)));
+
+    vm.register_fn("std_print", writer(out.clone(), ""));
+    vm.register_fn("std_println", writer(out, "\n"));
+    vm.register_fn("std_eprint", writer(err.clone(), ""));
+    vm.register_fn("std_eprintln", writer(err, "\n"));
+}
+
+/// A native writing its argument followed by `end`, flushing so output interleaves with the host's
+fn writer(stream: Rc<RefCell<impl Write>>, end: &'static str) -> impl Fn(String) -> std::io::Result<()> {
+    move |text| {
+        let mut stream = stream.borrow_mut();
+
+        write!(stream, "{text}{end}")?;
+        stream.flush()
+    }
+}
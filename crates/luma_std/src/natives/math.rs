@@ -0,0 +1,15 @@
+use luma_vm::LumaVM;
+
+pub fn register(vm: &mut LumaVM) {
+    vm.register_fn("std_sqrt", f64::sqrt);
+    vm.register_fn("std_pow", f64::powf);
+    vm.register_fn("std_exp", f64::exp);
+    vm.register_fn("std_ln", f64::ln);
+    vm.register_fn("std_floor", f64::floor);
+    vm.register_fn("std_ceil", f64::ceil);
+    vm.register_fn("std_round", f64::round);
+    vm.register_fn("std_sin", f64::sin);
+    vm.register_fn("std_cos", f64::cos);
+    vm.register_fn("std_tan", f64::tan);
+    vm.register_fn("std_atan2", f64::atan2);
+}
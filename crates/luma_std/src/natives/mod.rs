@@ -0,0 +1,7 @@
+//! Host functions called by the Luma sources of the standard library, prefixed with `std_` so they don't clash with the functions wrapping them
+
+pub mod assert;
+pub mod convert;
+pub mod io;
+pub mod math;
+pub mod string;
@@ -0,0 +1,15 @@
+use luma_vm::LumaVM;
+
+pub fn register(vm: &mut LumaVM) {
+    vm.register_fn("std_index_of", index_of);
+    vm.register_fn("std_replace", |text: String, from: String, to: String| text.replace(&from, &to));
+    vm.register_fn("std_trim", |text: String| text.trim().to_string());
+    vm.register_fn("std_to_upper", |text: String| text.to_uppercase());
+    vm.register_fn("std_to_lower", |text: String| text.to_lowercase());
+}
+
+/// Char index of the first occurrence of `pattern`, `-1` if there is none
+fn index_of(text: String, pattern: String) -> i32 {
+    text.find(&pattern)
+        .map_or(-1, |byte_index| text[..byte_index].chars().count() as i32)
+}
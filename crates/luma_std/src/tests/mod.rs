@@ -0,0 +1,74 @@
+use std::{cell::RefCell, io::Write, rc::Rc};
+
+use luma_compiler::{LumaCompiler, bytecode::ModuleBytecode};
+use luma_core::CodeSource;
+use luma_diagnostic::Printer;
+use luma_vm::{Capabilities, LumaVM, RuntimeError};
+
+pub mod natives;
+pub mod programs;
+
+/// A writer whose output stays readable after it was moved into the VM
+#[derive(Clone, Default)]
+pub struct Output(Rc<RefCell<Vec<u8>>>);
+
+impl Output {
+    pub fn text(&self) -> String {
+        String::from_utf8(self.0.borrow().clone()).expect("output should be utf-8")
+    }
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.borrow_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// The result of running a program against the standard library
+pub struct Run {
+    pub stdout: String,
+    pub stderr: String,
+    pub result: Result<(), RuntimeError>,
+}
+
+/// Compiles the source against the standard library, the rendered diagnostics are the error
+pub fn compile(vm: &LumaVM, source: CodeSource) -> Result<ModuleBytecode, String> {
+    let result = LumaCompiler::new()
+        .with_natives(vm.native_signatures())
+        .with_libraries(crate::libraries())
+        .compile([source]);
+
+    let failed = result.has_errors();
+
+    match result.result.and_then(|modules| modules.into_iter().next()) {
+        Some(module) if !failed => Ok(module),
+        _ => Err(Printer::print(&result.sources, &result.diagnostics)),
+    }
+}
+
+/// Compiles and loads the source, capturing what it prints
+pub fn run(source: impl Into<CodeSource>) -> Result<Run, String> {
+    run_with(Capabilities::NONE, source)
+}
+
+/// Compiles and loads the source on a VM granted the capabilities, capturing what it prints
+pub fn run_with(capabilities: Capabilities, source: impl Into<CodeSource>) -> Result<Run, String> {
+    let (stdout, stderr) = (Output::default(), Output::default());
+
+    let mut vm = LumaVM::new();
+    vm.grant(capabilities);
+    crate::register_with_output(&mut vm, stdout.clone(), stderr.clone());
+
+    let module = compile(&vm, source.into())?;
+    let result = vm.load(module);
+
+    Ok(Run {
+        stdout: stdout.text(),
+        stderr: stderr.text(),
+        result,
+    })
+}
@@ -0,0 +1,119 @@
+use luma_compiler::LumaCompiler;
+use luma_vm::{Capabilities, LumaVM, RuntimeErrorKind};
+use pretty_assertions::assert_eq;
+
+use crate::tests::{compile, run, run_with};
+
+#[test]
+fn errors_print_to_stderr() {
+    let run = run("import std.io; eprint(\"oh \"); eprintln(\"no\"); print(\"fine\");").unwrap();
+
+    assert_eq!(run.stdout, "fine");
+    assert_eq!(run.stderr, "oh no\n");
+}
+
+#[test]
+fn failed_assertions_abort_with_their_message() {
+    let run = run("import std.assert; func check() = assert_eq_int(1 + 1, 3); check();").unwrap();
+    let err = run.result.unwrap_err();
+
+    assert!(matches!(
+        err.kind,
+        RuntimeErrorKind::HostError { ref message, .. } if message == "assertion failed: expected 3, found 2"
+    ));
+    assert_eq!(
+        err.trace.iter().map(|frame| frame.function.as_str()).collect::<Vec<_>>(),
+        ["assert", "assert_eq_int", "check", "<init>"]
+    );
+}
+
+#[test]
+fn out_of_range_floats_do_not_convert_to_ints() {
+    let run = run("import std.convert; var x = to_int(100000000000000000000.0);").unwrap();
+
+    assert!(matches!(
+        run.result.unwrap_err().kind,
+        RuntimeErrorKind::HostError { ref message, .. } if message.ends_with("doesn't fit in an i64")
+    ));
+}
+
+#[test]
+fn modules_must_be_imported() {
+    let err = run("println(\"hello\");").err().unwrap();
+
+    assert!(err.contains("unresolved identifier"), "{err}");
+}
+
+#[test]
+fn unknown_modules_fail_to_compile() {
+    let err = run("import std.missing;").err().unwrap();
+
+    assert!(err.contains("unknown module"), "{err}");
+}
+
+#[test]
+fn imported_functions_are_not_exported() {
+    let mut vm = LumaVM::new();
+    crate::register(&mut vm);
+
+    let module = compile(&vm, "import std.math; pub func twice(x: f64): f64 = max(x, x) * 2.0;".into()).unwrap();
+
+    assert_eq!(module.exports.get_functions().keys().collect::<Vec<_>>(), ["twice"]);
+}
+
+#[test]
+fn every_module_gets_its_own_copy_of_a_library() {
+    let mut vm = LumaVM::new();
+    crate::register(&mut vm);
+
+    let result = LumaCompiler::new()
+        .with_natives(vm.native_signatures())
+        .with_libraries(crate::libraries())
+        .compile(["import std.math; var a = sqrt(4.0);".into(), "import std; var b = max(1.0, 2.0);".into()]);
+
+    assert!(!result.has_errors(), "compilation failed: {:#?}", result.diagnostics);
+    assert!(result.result.unwrap().iter().all(|module| module.get_init_chunk().is_some()));
+}
+
+#[test]
+fn capability_modules_need_the_capability() {
+    let err = run("import std.time; var now = unix_millis();").err().unwrap();
+
+    assert!(err.contains("unresolved identifier"), "{err}");
+}
+
+#[test]
+fn scripts_round_trip_files() {
+    let dir = std::env::temp_dir().join(format!("luma-std-fs-{}", std::process::id()));
+    let path = dir.join("note.txt").to_string_lossy().replace('\\', "/");
+
+    let source = format!(
+        "
+        import std.io;
+        import std.fs;
+        import std.assert;
+
+        func round_trip(): ()!str {{
+            create_dir(\"{dir}\")?;
+            write_file(\"{path}\", \"first\")?;
+            append_file(\"{path}\", \" second\")?;
+            println(read_file(\"{path}\")?);
+            println(list_dir(\"{dir}\")?);
+            remove_file(\"{path}\")
+        }};
+
+        round_trip() catch e {{ fail(e) }};
+        assert(!exists(\"{path}\"), \"the file was removed\");
+
+        var missing = read_file(\"{path}\") catch e {{ \"failed\" }};
+        assert_eq_str(missing, \"failed\");
+        ",
+        dir = dir.to_string_lossy().replace('\\', "/"),
+    );
+
+    let run = run_with(Capabilities { fs: true, ..Capabilities::NONE }, source).unwrap();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    run.result.unwrap();
+    assert_eq!(run.stdout, "first second\nnote.txt\n");
+}
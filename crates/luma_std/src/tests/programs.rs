@@ -0,0 +1,55 @@
+//! Runs every `.luma` program in `tests/`, a program passes when it runs without an error and prints what its
+//! `.stdout` file holds (nothing if there is none)
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use luma_core::CodeSource;
+
+use crate::tests::run;
+
+fn programs() -> Vec<PathBuf> {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests");
+
+    let mut programs = fs::read_dir(&dir)
+        .expect("the tests directory should exist")
+        .map(|entry| entry.expect("the tests directory should be readable").path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "luma"))
+        .collect::<Vec<_>>();
+
+    programs.sort();
+    programs
+}
+
+/// Runs the program, describing how it failed
+fn check(path: &Path) -> Result<(), String> {
+    let source = CodeSource::try_from(path.to_path_buf()).map_err(|err| err.to_string())?;
+    let run = run(source)?;
+
+    if let Err(err) = run.result {
+        return Err(format!("{err}"));
+    }
+
+    let expected = fs::read_to_string(path.with_extension("stdout")).unwrap_or_default();
+
+    if run.stdout != expected {
+        return Err(format!("expected output:\n{expected}\nfound:\n{}", run.stdout));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn luma_programs() {
+    let programs = programs();
+    assert!(!programs.is_empty(), "expected at least one program");
+
+    let failures = programs
+        .iter()
+        .filter_map(|path| check(path).err().map(|err| format!("{}: {err}", path.display())))
+        .collect::<Vec<_>>();
+
+    assert!(failures.is_empty(), "{} program(s) failed:\n\n{}", failures.len(), failures.join("\n\n"));
+}
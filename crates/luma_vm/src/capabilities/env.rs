@@ -0,0 +1,13 @@
+use crate::{LumaVM, capabilities::result};
+
+pub fn register(vm: &mut LumaVM) {
+    vm.register_fn("std_env_var", |name: String| {
+        result(std::env::var(&name).map_err(|err| format!("'{name}': {err}")))
+    });
+
+    vm.register_fn("std_env_has_var", |name: String| std::env::var_os(name).is_some());
+
+    vm.register_fn("std_env_current_dir", || {
+        result(std::env::current_dir().map(|dir| dir.to_string_lossy().into_owned()))
+    });
+}
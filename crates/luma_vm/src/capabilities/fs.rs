@@ -0,0 +1,35 @@
+use std::fs;
+
+use crate::{LumaVM, capabilities::result};
+
+pub fn register(vm: &mut LumaVM) {
+    vm.register_fn("std_fs_read", |path: String| result(fs::read_to_string(path)));
+    vm.register_fn("std_fs_write", |path: String, contents: String| result(fs::write(path, contents)));
+    vm.register_fn("std_fs_append", |path: String, contents: String| result(append(&path, &contents)));
+    vm.register_fn("std_fs_remove", |path: String| result(fs::remove_file(path)));
+    vm.register_fn("std_fs_create_dir", |path: String| result(fs::create_dir_all(path)));
+    vm.register_fn("std_fs_list_dir", |path: String| result(list_dir(&path)));
+    vm.register_fn("std_fs_exists", |path: String| fs::exists(path).unwrap_or(false));
+}
+
+fn append(path: &str, contents: &str) -> std::io::Result<()> {
+    use std::io::Write;
+
+    fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(contents.as_bytes())
+}
+
+/// Names of the directory's entries on separate lines, sorted as the order of `read_dir` depends on the platform.
+///
+/// Luma has no way to spell an array type yet, so a string is what a script can pass around
+fn list_dir(path: &str) -> std::io::Result<String> {
+    let mut names = fs::read_dir(path)?
+        .map(|entry| entry.map(|entry| entry.file_name().to_string_lossy().into_owned()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+
+    names.sort();
+    Ok(names.join("\n"))
+}
@@ -0,0 +1,54 @@
+use crate::{LumaResult, LumaVM};
+
+mod env;
+mod fs;
+mod time;
+
+/// Host resources a script may access, every capability is disabled by default.
+///
+/// Granting a capability registers its natives with the VM, see [`LumaVM::grant`]. Failing calls don't abort
+/// the script, they return a `T!str` holding the message
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    /// reading, writing and removing files and listing directories, the `std_fs_*` natives
+    pub fs: bool,
+
+    /// reading environment variables, the `std_env_*` natives
+    pub env: bool,
+
+    /// reading the clocks, the `std_time_*` natives
+    pub time: bool,
+}
+
+impl Capabilities {
+    pub const NONE: Capabilities = Capabilities {
+        fs: false,
+        env: false,
+        time: false,
+    };
+
+    pub const ALL: Capabilities = Capabilities {
+        fs: true,
+        env: true,
+        time: true,
+    };
+}
+
+/// Hands the message of a failed call to the script as the error of a `T!str`
+fn result<T, E: ToString>(result: Result<T, E>) -> LumaResult<T, String> {
+    result.map_err(|err| err.to_string()).into()
+}
+
+pub(crate) fn register(vm: &mut LumaVM, capabilities: Capabilities) {
+    if capabilities.fs {
+        fs::register(vm);
+    }
+
+    if capabilities.env {
+        env::register(vm);
+    }
+
+    if capabilities.time {
+        time::register(vm);
+    }
+}
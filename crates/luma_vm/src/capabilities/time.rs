@@ -0,0 +1,18 @@
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use crate::LumaVM;
+
+pub fn register(vm: &mut LumaVM) {
+    let start = Instant::now();
+
+    vm.register_fn("std_time_unix_millis", || {
+        // a clock set before 1970 reads as negative
+        match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(elapsed) => elapsed.as_millis() as i64,
+            Err(err) => -(err.duration().as_millis() as i64),
+        }
+    });
+
+    // monotonic, unlike the wall clock
+    vm.register_fn("std_time_elapsed_millis", move || start.elapsed().as_millis() as i64);
+}
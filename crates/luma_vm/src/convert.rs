@@ -0,0 +1,352 @@
+use luma_compiler::{
+    Type, TypeKind,
+    bytecode::{BytecodeValue, StructLayout},
+};
+
+use crate::RuntimeErrorKind;
+
+/// A Rust type that can be passed to Luma
+pub trait IntoLuma {
+    /// The Luma type of the converted values
+    fn luma_type() -> TypeKind;
+
+    fn into_luma(self) -> BytecodeValue;
+}
+
+/// A Rust type that can be created from a Luma value
+pub trait FromLuma: Sized {
+    /// The Luma type of the accepted values
+    fn luma_type() -> TypeKind;
+
+    fn from_luma(value: BytecodeValue) -> Result<Self, RuntimeErrorKind>;
+}
+
+/// A Rust struct mapped to a Luma struct by field name, usually implemented with `#[derive(LumaStruct)]`
+pub trait LumaStruct: IntoLuma + FromLuma {
+    /// Name of the Luma struct
+    const NAME: &'static str;
+
+    /// Names and Luma types of the fields, in declaration order
+    fn fields() -> Vec<(&'static str, TypeKind)>;
+
+    /// Checks that the compiled struct has exactly the same fields with the same types
+    fn check_layout(layout: &StructLayout) -> Result<(), RuntimeErrorKind> {
+        let fields = Self::fields();
+        let mismatch = |field: &str| RuntimeErrorKind::StructLayoutMismatch {
+            name: Self::NAME.to_string(),
+            field: field.to_string(),
+        };
+
+        for (name, ty) in &fields {
+            match layout.get_field(name) {
+                Some(field) if same_type(&field.ty, ty) => {}
+                _ => return Err(mismatch(name)),
+            }
+        }
+
+        match layout.fields.iter().find(|field| !fields.iter().any(|(name, _)| *name == field.name)) {
+            Some(extra) => Err(mismatch(&extra.name)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Compares types structurally, ignoring spans and resolved definitions
+fn same_type(a: &TypeKind, b: &TypeKind) -> bool {
+    match (a, b) {
+        (TypeKind::Tuple(a), TypeKind::Tuple(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| same_type(&a.kind, &b.kind))
+        }
+        (TypeKind::Array(a), TypeKind::Array(b))
+        | (TypeKind::Ptr(a), TypeKind::Ptr(b))
+        | (TypeKind::Optional(a), TypeKind::Optional(b)) => same_type(&a.kind, &b.kind),
+        (TypeKind::Result(a, a_error), TypeKind::Result(b, b_error)) => {
+            same_type(&a.kind, &b.kind) && same_type(&a_error.kind, &b_error.kind)
+        }
+        (TypeKind::Named { name: a, .. }, TypeKind::Named { name: b, .. }) => a == b,
+        _ => a == b,
+    }
+}
+
+fn mismatch(expected: TypeKind, found: &BytecodeValue) -> RuntimeErrorKind {
+    RuntimeErrorKind::ConversionFailed {
+        expected: expected.to_string(),
+        found: found.type_kind().to_string(),
+    }
+}
+
+/// Fields of a Luma struct value being converted to a Rust struct
+pub struct StructFields {
+    name: String,
+    fields: Vec<(String, BytecodeValue)>,
+}
+
+impl StructFields {
+    /// Fails unless the value is a struct called `name`
+    pub fn new(name: &str, value: BytecodeValue) -> Result<Self, RuntimeErrorKind> {
+        match value {
+            BytecodeValue::Struct { name: found, fields } if found == name => Ok(Self { name: found, fields }),
+            other => Err(mismatch(named(name), &other)),
+        }
+    }
+
+    /// Removes the field and converts its value
+    pub fn take<T: FromLuma>(&mut self, field: &str) -> Result<T, RuntimeErrorKind> {
+        let Some(index) = self.fields.iter().position(|(name, _)| name == field) else {
+            return Err(RuntimeErrorKind::MissingField {
+                name: self.name.clone(),
+                field: field.to_string(),
+            });
+        };
+
+        T::from_luma(self.fields.swap_remove(index).1)
+    }
+}
+
+/// The type of a Luma struct called `name`
+fn named(name: &str) -> TypeKind {
+    TypeKind::Named {
+        name: name.to_string(),
+        def_id: None,
+    }
+}
+
+macro_rules! primitives {
+    ($($ty:ty => $kind:ident),* $(,)?) => {$(
+        impl IntoLuma for $ty {
+            fn luma_type() -> TypeKind {
+                TypeKind::$kind
+            }
+
+            fn into_luma(self) -> BytecodeValue {
+                BytecodeValue::$kind(self)
+            }
+        }
+
+        impl FromLuma for $ty {
+            fn luma_type() -> TypeKind {
+                TypeKind::$kind
+            }
+
+            fn from_luma(value: BytecodeValue) -> Result<Self, RuntimeErrorKind> {
+                match value {
+                    BytecodeValue::$kind(value) => Ok(value),
+                    other => Err(mismatch(TypeKind::$kind, &other)),
+                }
+            }
+        }
+    )*};
+}
+
+primitives! {
+    u8 => UInt8,
+    u16 => UInt16,
+    u32 => UInt32,
+    u64 => UInt64,
+    i8 => Int8,
+    i16 => Int16,
+    i32 => Int32,
+    i64 => Int64,
+    f32 => Float32,
+    f64 => Float64,
+    bool => Bool,
+    char => Char,
+}
+
+impl IntoLuma for String {
+    fn luma_type() -> TypeKind {
+        TypeKind::String
+    }
+
+    fn into_luma(self) -> BytecodeValue {
+        BytecodeValue::String(self.into())
+    }
+}
+
+impl FromLuma for String {
+    fn luma_type() -> TypeKind {
+        TypeKind::String
+    }
+
+    fn from_luma(value: BytecodeValue) -> Result<Self, RuntimeErrorKind> {
+        match value {
+            BytecodeValue::String(value) => Ok(value.to_string()),
+            other => Err(mismatch(TypeKind::String, &other)),
+        }
+    }
+}
+
+impl IntoLuma for &str {
+    fn luma_type() -> TypeKind {
+        TypeKind::String
+    }
+
+    fn into_luma(self) -> BytecodeValue {
+        BytecodeValue::String(self.into())
+    }
+}
+
+impl IntoLuma for () {
+    fn luma_type() -> TypeKind {
+        TypeKind::Unit
+    }
+
+    fn into_luma(self) -> BytecodeValue {
+        BytecodeValue::Unit
+    }
+}
+
+impl FromLuma for () {
+    fn luma_type() -> TypeKind {
+        TypeKind::Unit
+    }
+
+    fn from_luma(value: BytecodeValue) -> Result<Self, RuntimeErrorKind> {
+        match value {
+            BytecodeValue::Unit => Ok(()),
+            other => Err(mismatch(TypeKind::Unit, &other)),
+        }
+    }
+}
+
+impl<T: IntoLuma> IntoLuma for Vec<T> {
+    fn luma_type() -> TypeKind {
+        TypeKind::Array(Box::new(Type::unspanned(T::luma_type())))
+    }
+
+    fn into_luma(self) -> BytecodeValue {
+        BytecodeValue::Array(self.into_iter().map(T::into_luma).collect())
+    }
+}
+
+impl<T: FromLuma> FromLuma for Vec<T> {
+    fn luma_type() -> TypeKind {
+        TypeKind::Array(Box::new(Type::unspanned(T::luma_type())))
+    }
+
+    fn from_luma(value: BytecodeValue) -> Result<Self, RuntimeErrorKind> {
+        match value {
+            BytecodeValue::Array(elements) => elements.into_iter().map(T::from_luma).collect(),
+            other => Err(mismatch(Self::luma_type(), &other)),
+        }
+    }
+}
+
+impl<T: IntoLuma> IntoLuma for Option<T> {
+    fn luma_type() -> TypeKind {
+        TypeKind::Optional(Box::new(Type::unspanned(T::luma_type())))
+    }
+
+    fn into_luma(self) -> BytecodeValue {
+        self.map_or(BytecodeValue::None, T::into_luma)
+    }
+}
+
+impl<T: FromLuma> FromLuma for Option<T> {
+    fn luma_type() -> TypeKind {
+        TypeKind::Optional(Box::new(Type::unspanned(T::luma_type())))
+    }
+
+    fn from_luma(value: BytecodeValue) -> Result<Self, RuntimeErrorKind> {
+        match value {
+            BytecodeValue::None => Ok(None),
+            // present values aren't wrapped
+            value => T::from_luma(value).map(Some),
+        }
+    }
+}
+
+/// A Luma result `T!E`, returning it from a host function hands the error to the script instead of aborting
+#[derive(Debug, Clone, PartialEq)]
+pub enum LumaResult<T, E> {
+    Ok(T),
+    Err(E),
+}
+
+impl<T, E> From<Result<T, E>> for LumaResult<T, E> {
+    fn from(result: Result<T, E>) -> Self {
+        match result {
+            Ok(value) => LumaResult::Ok(value),
+            Err(error) => LumaResult::Err(error),
+        }
+    }
+}
+
+impl<T, E> From<LumaResult<T, E>> for Result<T, E> {
+    fn from(result: LumaResult<T, E>) -> Self {
+        match result {
+            LumaResult::Ok(value) => Ok(value),
+            LumaResult::Err(error) => Err(error),
+        }
+    }
+}
+
+impl<T: IntoLuma, E: IntoLuma> IntoLuma for LumaResult<T, E> {
+    fn luma_type() -> TypeKind {
+        TypeKind::Result(Box::new(Type::unspanned(T::luma_type())), Box::new(Type::unspanned(E::luma_type())))
+    }
+
+    fn into_luma(self) -> BytecodeValue {
+        match self {
+            LumaResult::Ok(value) => value.into_luma(),
+            LumaResult::Err(error) => BytecodeValue::Err(Box::new(error.into_luma())),
+        }
+    }
+}
+
+impl<T: FromLuma, E: FromLuma> FromLuma for LumaResult<T, E> {
+    fn luma_type() -> TypeKind {
+        TypeKind::Result(Box::new(Type::unspanned(T::luma_type())), Box::new(Type::unspanned(E::luma_type())))
+    }
+
+    fn from_luma(value: BytecodeValue) -> Result<Self, RuntimeErrorKind> {
+        match value {
+            BytecodeValue::Err(error) => E::from_luma(*error).map(LumaResult::Err),
+            // values aren't wrapped
+            value => T::from_luma(value).map(LumaResult::Ok),
+        }
+    }
+}
+
+macro_rules! tuples {
+    ($(($($name:ident),+)),* $(,)?) => {$(
+        impl<$($name: IntoLuma),+> IntoLuma for ($($name,)+) {
+            fn luma_type() -> TypeKind {
+                TypeKind::Tuple(vec![$(Type::unspanned($name::luma_type())),+])
+            }
+
+            #[allow(non_snake_case)]
+            fn into_luma(self) -> BytecodeValue {
+                let ($($name,)+) = self;
+                BytecodeValue::Tuple(vec![$($name.into_luma()),+])
+            }
+        }
+
+        impl<$($name: FromLuma),+> FromLuma for ($($name,)+) {
+            fn luma_type() -> TypeKind {
+                TypeKind::Tuple(vec![$(Type::unspanned($name::luma_type())),+])
+            }
+
+            fn from_luma(value: BytecodeValue) -> Result<Self, RuntimeErrorKind> {
+                const LEN: usize = [$(stringify!($name)),+].len();
+
+                match value {
+                    BytecodeValue::Tuple(elements) if elements.len() == LEN => {
+                        let mut elements = elements.into_iter();
+                        Ok(($($name::from_luma(elements.next().expect("length was checked"))?,)+))
+                    }
+                    other => Err(mismatch(Self::luma_type(), &other)),
+                }
+            }
+        }
+    )*};
+}
+
+tuples! {
+    (A),
+    (A, B),
+    (A, B, C),
+    (A, B, C, D),
+    (A, B, C, D, E),
+    (A, B, C, D, E, F),
+}
@@ -0,0 +1,327 @@
+use std::fmt::Display;
+
+use luma_core::Span;
+use luma_diagnostic::{AsDiagnostic, Diagnostic, Explanation, context, define_contexts, define_diagnostics, error};
+
+define_diagnostics! {
+    pub enum RuntimeErrorKind {
+        #[Error(E0901, "invalid instruction", "the bytes at offset {offset} don't form a valid instruction")]
+        /// The bytecode is corrupt, it was not produced by the compiler or was modified afterwards.
+        InvalidInstruction {
+            offset: usize,
+        },
+        #[Error(E0902, "stack underflow", "the instruction at offset {offset} popped from an empty stack")]
+        /// The bytecode popped more values than it pushed, it was not produced by the compiler or was modified afterwards.
+        StackUnderflow {
+            offset: usize,
+        },
+        #[Error(E0903, "stack overflow", "the stack grew beyond {limit} values")]
+        /// The script needed more stack space than `VmLimits::max_stack_size` allows, usually because of deep recursion.
+        StackOverflow {
+            limit: usize,
+        },
+        #[Error(E0904, "invalid local", "local slot {slot} is outside of the function's locals")]
+        /// The bytecode reads a local the function doesn't have, it was not produced by the compiler or was modified afterwards.
+        InvalidLocal {
+            slot: usize,
+        },
+        #[Error(E0905, "invalid global", "global slot {slot} is outside of the module's globals")]
+        /// The bytecode reads a global the module doesn't have, it was not produced by the compiler or was modified afterwards.
+        InvalidGlobal {
+            slot: usize,
+        },
+        #[Error(E0906, "invalid reference", "the reference points at stack slot {index}, past the end of the stack")]
+        /// A reference points past the stack, it outlived the variable it refers to.
+        InvalidReference {
+            index: usize,
+        },
+        #[Error(E0907, "invalid constant", "constant slot {slot} is outside of the constant pool")]
+        /// The bytecode reads a constant the module doesn't have, it was not produced by the compiler or was modified afterwards.
+        InvalidConstant {
+            slot: usize,
+        },
+        #[Error(E0908, "type mismatch", "mismatched operand types for {operation}")]
+        /// An instruction received operands of types it can't combine, the bytecode doesn't match the types the compiler checked.
+        TypeMismatch {
+            operation: &'static str,
+        },
+        #[Error(E0909, "division by zero", "attempted to divide by zero")]
+        /// An integer was divided by zero, scripts can catch this with `try`.
+        ///
+        /// ```luma
+        /// func f(a: i32): i32 { try 10 / a catch e { 0 } };
+        /// ```
+        DivisionByZero,
+        #[Error(E0910, "integer overflow", "the result does not fit in its integer type")]
+        /// An arithmetic result doesn't fit in its integer type, scripts can catch this with `try`.
+        IntegerOverflow,
+        #[Error(E0911, "function not found", "the module has no function with index {index}")]
+        /// The bytecode calls a function the module doesn't have, it was not produced by the compiler or was modified afterwards.
+        FunctionNotFound {
+            index: usize,
+        },
+        #[Error(E0912, "arity mismatch", "expected {expected} arguments, found {found}")]
+        /// The host called a function with more or fewer arguments than it declares.
+        ArityMismatch {
+            expected: usize,
+            found: usize,
+        },
+        #[Error(E0913, "fuel exhausted", "the script ran for more than {limit} instructions")]
+        /// The script executed more instructions than `VmLimits::fuel` allows, every call starts with a fresh budget.
+        FuelExhausted {
+            limit: u64,
+        },
+        #[Error(E0914, "time limit exceeded", "the script ran for longer than {millis}ms")]
+        /// The call ran for longer than `VmLimits::timeout` allows.
+        TimeLimitExceeded {
+            millis: u128,
+        },
+        #[Error(E0915, "heap limit exceeded", "the script allocated more than {limit} bytes")]
+        /// The script allocated more memory than `VmLimits::max_heap_size` allows.
+        HeapLimitExceeded {
+            limit: usize,
+        },
+        #[Error(E0916, "index out of bounds", "index {index} is outside of a string of {len} chars")]
+        /// The index is negative or past the end of the string, scripts can catch this with `try`.
+        IndexOutOfBounds {
+            index: i64,
+            len: usize,
+        },
+        #[Error(E0917, "invalid number", "'{text}' is not a valid number")]
+        /// The text can't be parsed as a number, scripts can catch this with `try`.
+        InvalidNumber {
+            text: String,
+        },
+        #[Error(E0918, "call depth exceeded", "more than {limit} calls were active at once")]
+        /// More calls were active at once than `VmLimits::max_call_depth` allows, usually because of unbounded recursion.
+        CallDepthExceeded {
+            limit: usize,
+        },
+        #[Error(E0919, "no module loaded", "load a module before calling its functions")]
+        /// A function was called before `LumaVM::load` was given a module.
+        NoModuleLoaded,
+        #[Error(E0920, "export not found", "the module exports no function named '{name}'")]
+        /// The host called a function the module doesn't declare as `pub`.
+        ExportNotFound {
+            name: String,
+        },
+        #[Error(E0921, "global not found", "the module exports no global named '{name}'")]
+        /// The host accessed a global the module doesn't declare as `pub`.
+        GlobalNotFound {
+            name: String,
+        },
+        #[Error(E0922, "unknown host function", "the module calls '{name}' which isn't registered with the VM")]
+        /// The module calls a host function that wasn't registered with the VM before loading it.
+        UnknownNative {
+            name: String,
+        },
+        #[Error(E0923, "host function failed", "'{function}' failed: {message}")]
+        /// A host function reported a failure, scripts can catch this with `try`.
+        HostError {
+            function: String,
+            message: String,
+        },
+        #[Error(E0924, "host function returned the wrong type", "'{function}' is declared to return '{expected}' but returned '{found}'")]
+        /// A host function returned a value of a different type than its signature declares.
+        HostReturnMismatch {
+            function: String,
+            expected: String,
+            found: String,
+        },
+        #[Error(E0925, "conversion failed", "expected a value of type '{expected}', found '{found}'")]
+        /// A value passed between the host and the script doesn't have the expected type.
+        ConversionFailed {
+            expected: String,
+            found: String,
+        },
+        #[Error(E0926, "missing field", "struct '{name}' has no field '{field}'")]
+        /// The host converted a struct that lacks a field the script's struct declares.
+        MissingField {
+            name: String,
+            field: String,
+        },
+        #[Error(E0927, "struct not found", "the module declares no struct named '{name}'")]
+        /// The host referred to a struct the module doesn't declare.
+        StructNotFound {
+            name: String,
+        },
+        #[Error(E0928, "struct layout mismatch", "field '{field}' of struct '{name}' doesn't match the host type")]
+        /// The fields of the host type don't line up with the struct declared by the script.
+        StructLayoutMismatch {
+            name: String,
+            field: String,
+        },
+        #[Error(E0929, "invalid range", "the range starts at {start} but ends before it at {end}")]
+        /// The start of a slice lies past its end, scripts can catch this with `try`.
+        InvalidRange {
+            start: usize,
+            end: usize,
+        },
+    }
+}
+
+impl RuntimeErrorKind {
+    /// Whether a script can catch the error with `try`, exceeded limits and broken bytecode always abort
+    pub fn is_recoverable(&self) -> bool {
+        matches!(
+            self,
+            RuntimeErrorKind::DivisionByZero
+                | RuntimeErrorKind::IntegerOverflow
+                | RuntimeErrorKind::IndexOutOfBounds { .. }
+                | RuntimeErrorKind::InvalidRange { .. }
+                | RuntimeErrorKind::InvalidNumber { .. }
+                | RuntimeErrorKind::HostError { .. }
+        )
+    }
+
+    /// The error a script catching it receives, host functions report their own message
+    pub fn message(&self) -> String {
+        match self {
+            RuntimeErrorKind::HostError { message, .. } => message.clone(),
+            kind => kind.annotation().unwrap_or_else(|| kind.title()),
+        }
+    }
+}
+
+/// The explanation of a diagnostic code reported by the compiler or at runtime, like `E0101` or `E0909`
+#[must_use]
+pub fn explain(code: &str) -> Option<Explanation> {
+    Explanation::find(&[luma_compiler::diagnostics::EXPLANATIONS, &[RuntimeErrorKind::EXPLANATIONS]].concat(), code)
+}
+
+define_contexts! {
+    pub enum RuntimeErrorContext {
+        #[Unannotated("in function `{function}`")]
+        InFunction {
+            function: String,
+        },
+        #[Context("called from `{function}`")]
+        CalledFrom {
+            function: String,
+        },
+        #[Context("... {count} more calls to `{function}`")]
+        Repeated {
+            function: String,
+            count: usize,
+        },
+        #[Context("... {count} more frames")]
+        Omitted {
+            count: usize,
+        },
+    }
+}
+
+/// Frames kept at either end of a stack trace, the ones in between are only counted
+pub const TRACE_ENDS: usize = 16;
+
+/// An error raised while executing bytecode, along with the Luma functions that were running
+#[derive(Debug, Clone)]
+pub struct RuntimeError {
+    pub kind: RuntimeErrorKind,
+
+    /// the active functions, innermost first
+    pub trace: Vec<StackFrame>,
+
+    /// frames left out between the innermost and outermost [`TRACE_ENDS`] frames of the trace
+    pub omitted: usize,
+}
+
+impl RuntimeError {
+    /// Collapses repeated calls into one frame and keeps only the ends of deep traces
+    pub(crate) fn new(kind: RuntimeErrorKind, frames: impl IntoIterator<Item = StackFrame>) -> Self {
+        let mut trace: Vec<StackFrame> = Vec::new();
+
+        for frame in frames {
+            match trace.last_mut() {
+                Some(last) if last.function == frame.function && last.span == frame.span => last.repeated += 1,
+                _ => trace.push(frame),
+            }
+        }
+
+        let omitted = trace.len().saturating_sub(2 * TRACE_ENDS);
+        if omitted > 0 {
+            trace.drain(TRACE_ENDS..TRACE_ENDS + omitted);
+        }
+
+        RuntimeError { kind, trace, omitted }
+    }
+}
+
+/// A function that was running when a [`RuntimeError`] was raised
+#[derive(Debug, Clone, PartialEq)]
+pub struct StackFrame {
+    pub function: String,
+
+    /// span of the instruction the function was executing, `None` if the chunk has no debug info for it
+    pub span: Option<Span>,
+
+    /// further calls directly below this one at the same instruction, like in deep recursion
+    pub repeated: usize,
+}
+
+impl Display for RuntimeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.kind.title())?;
+
+        if let Some(annotation) = self.kind.annotation() {
+            write!(f, ": {annotation}")?;
+        }
+
+        for (index, frame) in self.trace.iter().enumerate() {
+            if index == TRACE_ENDS && self.omitted > 0 {
+                write!(f, "\n    ... {} more frames", self.omitted)?;
+            }
+
+            write!(f, "\n    at {}", frame.function)?;
+
+            if let Some(span) = frame.span {
+                write!(f, " ({}..{})", span.start, span.end)?;
+            }
+
+            if frame.repeated > 0 {
+                write!(f, "\n    ... {} more calls to `{}`", frame.repeated, frame.function)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for RuntimeError {}
+
+impl From<RuntimeError> for Diagnostic {
+    /// Points at the failing instruction of the innermost function, the callers are added as contexts
+    fn from(err: RuntimeError) -> Self {
+        let mut diagnostic = error!(err.kind);
+
+        for (index, frame) in err.trace.into_iter().enumerate() {
+            if index == TRACE_ENDS && err.omitted > 0 {
+                diagnostic = diagnostic.context(context!(RuntimeErrorContext::Omitted { count: err.omitted }));
+            }
+
+            let repeated = RuntimeErrorContext::Repeated {
+                function: frame.function.clone(),
+                count: frame.repeated,
+            };
+
+            if index == 0 {
+                let function = RuntimeErrorContext::InFunction { function: frame.function };
+
+                diagnostic = diagnostic.maybe_span(frame.span).context(context!(function));
+            } else {
+                let caller = RuntimeErrorContext::CalledFrom { function: frame.function };
+
+                diagnostic = diagnostic.context(match frame.span {
+                    Some(span) => context!(caller, span),
+                    None => context!(caller),
+                });
+            }
+
+            if frame.repeated > 0 {
+                diagnostic = diagnostic.context(context!(repeated));
+            }
+        }
+
+        diagnostic
+    }
+}
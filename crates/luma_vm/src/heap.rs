@@ -0,0 +1,423 @@
+use std::{cmp::Ordering, collections::HashMap, rc::Rc};
+
+use luma_compiler::bytecode::BytecodeValue;
+
+use crate::Value;
+
+/// Handle to an object on the [`Heap`], it dangles once the object was collected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ObjectRef(u32);
+
+impl ObjectRef {
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// A value too large or too dynamic for the stack, values referring to the same object alias it
+#[derive(Debug, Clone, PartialEq)]
+pub enum HeapObject {
+    String(Rc<str>),
+    Tuple(Vec<Value>),
+    Array(Vec<Value>),
+    Struct(StructObject),
+
+    /// the error of a result, its values aren't wrapped
+    Err(Value),
+}
+
+/// Field values of a struct, in the order they were given
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructObject {
+    pub name: String,
+    pub fields: Vec<(String, Value)>,
+}
+
+impl HeapObject {
+    /// Bytes the object occupies, including the buffers it owns
+    fn size(&self) -> usize {
+        size_of::<HeapObject>()
+            + match self {
+                HeapObject::String(string) => string.len(),
+                HeapObject::Tuple(elements) | HeapObject::Array(elements) => elements.len() * size_of::<Value>(),
+                HeapObject::Struct(object) => {
+                    object.name.len()
+                        + object
+                            .fields
+                            .iter()
+                            .map(|(name, _)| name.len() + size_of::<(String, Value)>())
+                            .sum::<usize>()
+                }
+                HeapObject::Err(_) => 0,
+            }
+    }
+
+    /// Values the object refers to
+    fn children(&self) -> impl Iterator<Item = &Value> {
+        let (elements, fields): (&[Value], &[(String, Value)]) = match self {
+            HeapObject::String(_) => (&[], &[]),
+            HeapObject::Tuple(elements) | HeapObject::Array(elements) => (elements, &[]),
+            HeapObject::Struct(object) => (&[], &object.fields),
+            HeapObject::Err(value) => (std::slice::from_ref(value), &[]),
+        };
+
+        elements.iter().chain(fields.iter().map(|(_, value)| value))
+    }
+}
+
+/// Counters describing the work of the garbage collector
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GcStats {
+    pub collections: u64,
+    pub allocated_objects: u64,
+    pub freed_objects: u64,
+    pub live_objects: usize,
+    pub live_bytes: usize,
+}
+
+struct Slot {
+    object: HeapObject,
+    marked: bool,
+}
+
+/// Objects referenced by the VM's values, reclaimed by a mark-and-sweep collector.
+///
+/// Allocating never collects, the VM collects at points where every value it uses is rooted
+pub struct Heap {
+    slots: Vec<Option<Slot>>,
+    free: Vec<u32>,
+    bytes: usize,
+
+    /// string objects by their contents, entries are dropped once the string is collected
+    interned: HashMap<Rc<str>, ObjectRef>,
+
+    /// live bytes at which the next collection is due
+    threshold: usize,
+
+    /// collect at every safepoint, to shake out missing roots in tests
+    stress: bool,
+    stats: GcStats,
+}
+
+impl Heap {
+    const INITIAL_THRESHOLD: usize = 1 << 20;
+
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            free: Vec::new(),
+            bytes: 0,
+            interned: HashMap::new(),
+            threshold: Self::INITIAL_THRESHOLD,
+            stress: false,
+            stats: GcStats::default(),
+        }
+    }
+
+    pub fn allocate(&mut self, object: HeapObject) -> ObjectRef {
+        self.bytes += object.size();
+        self.stats.allocated_objects += 1;
+
+        let slot = Slot { object, marked: false };
+
+        match self.free.pop() {
+            Some(index) => {
+                self.slots[index as usize] = Some(slot);
+                ObjectRef(index)
+            }
+            None => {
+                self.slots.push(Some(slot));
+                ObjectRef((self.slots.len() - 1) as u32)
+            }
+        }
+    }
+
+    /// Returns the string object with the contents, allocating it only if there is none yet
+    pub fn intern(&mut self, string: &Rc<str>) -> ObjectRef {
+        if let Some(&object) = self.interned.get(string) {
+            return object;
+        }
+
+        let object = self.allocate(HeapObject::String(string.clone()));
+        self.interned.insert(string.clone(), object);
+
+        object
+    }
+
+    /// Allocates a string without interning it, for strings built at runtime
+    pub fn allocate_string(&mut self, string: impl Into<Rc<str>>) -> Value {
+        Value::Object(self.allocate(HeapObject::String(string.into())))
+    }
+
+    /// The contents of the value if it's a string
+    pub fn string(&self, value: &Value) -> Option<&str> {
+        match value {
+            Value::Object(object) => match self.get(*object) {
+                HeapObject::String(string) => Some(string),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Wraps the value into the error of a result
+    pub fn allocate_err(&mut self, value: Value) -> Value {
+        Value::Object(self.allocate(HeapObject::Err(value)))
+    }
+
+    /// The wrapped value if the value is the error of a result
+    pub fn err(&self, value: &Value) -> Option<&Value> {
+        match value {
+            Value::Object(object) => match self.get(*object) {
+                HeapObject::Err(value) => Some(value),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Renders the value the way `to_string` does, strings are not quoted
+    pub fn display(&self, value: &Value) -> String {
+        let Value::Object(object) = value else {
+            return value.to_string();
+        };
+
+        let join = |values: &[Value]| values.iter().map(|value| self.display(value)).collect::<Vec<_>>().join(", ");
+
+        match self.get(*object) {
+            HeapObject::String(string) => string.to_string(),
+            HeapObject::Tuple(elements) => format!("({})", join(elements)),
+            HeapObject::Array(elements) => format!("[{}]", join(elements)),
+            HeapObject::Struct(object) => {
+                let fields = object.fields.iter().map(|(name, value)| format!("{name}: {}", self.display(value)));
+                format!("{} {{ {} }}", object.name, fields.collect::<Vec<_>>().join(", "))
+            }
+            HeapObject::Err(value) => format!("err({})", self.display(value)),
+        }
+    }
+
+    /// # Panics
+    /// If the object was collected
+    pub fn get(&self, object: ObjectRef) -> &HeapObject {
+        match self.slots.get(object.index()) {
+            Some(Some(slot)) => &slot.object,
+            _ => panic!("dangling object reference #{}", object.index()),
+        }
+    }
+
+    /// Bytes occupied by the objects allocated and not collected yet
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    pub fn stats(&self) -> GcStats {
+        GcStats {
+            live_objects: self.slots.len() - self.free.len(),
+            live_bytes: self.bytes,
+            ..self.stats
+        }
+    }
+
+    pub fn set_stress(&mut self, stress: bool) {
+        self.stress = stress;
+    }
+
+    pub(crate) fn should_collect(&self) -> bool {
+        self.stress || self.bytes >= self.threshold
+    }
+
+    /// Frees every object not reachable from the roots
+    pub fn collect<'a>(&mut self, roots: impl IntoIterator<Item = &'a Value>) {
+        let mut pending = roots
+            .into_iter()
+            .filter_map(|value| match value {
+                Value::Object(object) => Some(*object),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        while let Some(object) = pending.pop() {
+            let slot = self.slots[object.index()].as_mut().expect("roots and live objects don't dangle");
+
+            if slot.marked {
+                continue;
+            }
+
+            slot.marked = true;
+            pending.extend(slot.object.children().filter_map(|value| match value {
+                Value::Object(object) => Some(*object),
+                _ => None,
+            }));
+        }
+
+        for (index, entry) in self.slots.iter_mut().enumerate() {
+            match entry {
+                Some(slot) if slot.marked => slot.marked = false,
+                Some(slot) => {
+                    if let HeapObject::String(string) = &slot.object
+                        && self.interned.get(string) == Some(&ObjectRef(index as u32))
+                    {
+                        self.interned.remove(string);
+                    }
+
+                    self.bytes -= slot.object.size();
+                    self.stats.freed_objects += 1;
+                    self.free.push(index as u32);
+                    *entry = None;
+                }
+                None => {}
+            }
+        }
+
+        self.stats.collections += 1;
+        self.threshold = (self.bytes * 2).max(Self::INITIAL_THRESHOLD);
+    }
+
+    /// Copies a host value onto the heap, strings are interned
+    pub fn import(&mut self, value: &BytecodeValue) -> Value {
+        let object = match value {
+            BytecodeValue::UInt8(v) => return Value::UInt8(*v),
+            BytecodeValue::UInt16(v) => return Value::UInt16(*v),
+            BytecodeValue::UInt32(v) => return Value::UInt32(*v),
+            BytecodeValue::UInt64(v) => return Value::UInt64(*v),
+            BytecodeValue::Int8(v) => return Value::Int8(*v),
+            BytecodeValue::Int16(v) => return Value::Int16(*v),
+            BytecodeValue::Int32(v) => return Value::Int32(*v),
+            BytecodeValue::Int64(v) => return Value::Int64(*v),
+            BytecodeValue::Float32(v) => return Value::Float32(*v),
+            BytecodeValue::Float64(v) => return Value::Float64(*v),
+            BytecodeValue::Bool(v) => return Value::Bool(*v),
+            BytecodeValue::Char(v) => return Value::Char(*v),
+            BytecodeValue::Unit => return Value::Unit,
+            BytecodeValue::None => return Value::None,
+            BytecodeValue::String(v) => return Value::Object(self.intern(v)),
+            BytecodeValue::Tuple(elements) => HeapObject::Tuple(elements.iter().map(|value| self.import(value)).collect()),
+            BytecodeValue::Array(elements) => HeapObject::Array(elements.iter().map(|value| self.import(value)).collect()),
+            BytecodeValue::Struct { name, fields } => HeapObject::Struct(StructObject {
+                name: name.clone(),
+                fields: fields.iter().map(|(name, value)| (name.clone(), self.import(value))).collect(),
+            }),
+            BytecodeValue::Err(value) => HeapObject::Err(self.import(value)),
+        };
+
+        Value::Object(self.allocate(object))
+    }
+
+    /// Copies a value and the objects it refers to out of the heap
+    pub fn export(&self, value: &Value) -> BytecodeValue {
+        match *value {
+            Value::UInt8(v) => BytecodeValue::UInt8(v),
+            Value::UInt16(v) => BytecodeValue::UInt16(v),
+            Value::UInt32(v) => BytecodeValue::UInt32(v),
+            Value::UInt64(v) => BytecodeValue::UInt64(v),
+            Value::Int8(v) => BytecodeValue::Int8(v),
+            Value::Int16(v) => BytecodeValue::Int16(v),
+            Value::Int32(v) => BytecodeValue::Int32(v),
+            Value::Int64(v) => BytecodeValue::Int64(v),
+            Value::Float32(v) => BytecodeValue::Float32(v),
+            Value::Float64(v) => BytecodeValue::Float64(v),
+            Value::Bool(v) => BytecodeValue::Bool(v),
+            Value::Char(v) => BytecodeValue::Char(v),
+            Value::Unit => BytecodeValue::Unit,
+            Value::None => BytecodeValue::None,
+            // hosts can't pass references in, so the variables scripts return references to are gone by now
+            Value::Ref(_) | Value::GlobalRef(_) => BytecodeValue::Unit,
+            Value::Object(object) => match self.get(object) {
+                HeapObject::String(string) => BytecodeValue::String(string.clone()),
+                HeapObject::Tuple(elements) => BytecodeValue::Tuple(elements.iter().map(|value| self.export(value)).collect()),
+                HeapObject::Array(elements) => BytecodeValue::Array(elements.iter().map(|value| self.export(value)).collect()),
+                HeapObject::Struct(object) => BytecodeValue::Struct {
+                    name: object.name.clone(),
+                    fields: object
+                        .fields
+                        .iter()
+                        .map(|(name, value)| (name.clone(), self.export(value)))
+                        .collect(),
+                },
+                HeapObject::Err(value) => BytecodeValue::Err(Box::new(self.export(value))),
+            },
+        }
+    }
+
+    /// Orders two values of the same type by their contents, `None` for mismatched or unordered values
+    pub fn compare(&self, left: &Value, right: &Value) -> Option<Ordering> {
+        // like none, errors are ordered before any value
+        match (self.err(left), self.err(right)) {
+            (Some(l), Some(r)) => return self.compare(l, r),
+            (Some(_), None) => return Some(Ordering::Less),
+            (None, Some(_)) => return Some(Ordering::Greater),
+            (None, None) => {}
+        }
+
+        let (Value::Object(l), Value::Object(r)) = (left, right) else {
+            return left.compare(right);
+        };
+
+        if l == r {
+            return Some(Ordering::Equal);
+        }
+
+        match (self.get(*l), self.get(*r)) {
+            (HeapObject::String(l), HeapObject::String(r)) => l.partial_cmp(r),
+            (HeapObject::Tuple(l), HeapObject::Tuple(r)) | (HeapObject::Array(l), HeapObject::Array(r)) => {
+                // lexicographic, like tuples and slices in Rust
+                for (l, r) in l.iter().zip(r.iter()) {
+                    match self.compare(l, r)? {
+                        Ordering::Equal => continue,
+                        ordering => return Some(ordering),
+                    }
+                }
+
+                Some(l.len().cmp(&r.len()))
+            }
+            // structs are only equal or unordered, see `equal`
+            (HeapObject::Struct(_), HeapObject::Struct(_)) => self.equal(left, right)?.then_some(Ordering::Equal),
+            _ => None,
+        }
+    }
+
+    /// Whether two values of the same type or a value and `none` are equal, `None` for mismatched values.
+    ///
+    /// Unlike [`Heap::compare`], values without an order such as structs are still equal or not
+    pub fn equal(&self, left: &Value, right: &Value) -> Option<bool> {
+        match (self.err(left), self.err(right)) {
+            (Some(l), Some(r)) => return self.equal(l, r),
+            (Some(_), None) | (None, Some(_)) => return Some(false),
+            (None, None) => {}
+        }
+
+        let (Value::Object(l), Value::Object(r)) = (left, right) else {
+            return left.equal(right);
+        };
+
+        if l == r {
+            return Some(true);
+        }
+
+        match (self.get(*l), self.get(*r)) {
+            (HeapObject::String(l), HeapObject::String(r)) => Some(l == r),
+            (HeapObject::Tuple(l), HeapObject::Tuple(r)) | (HeapObject::Array(l), HeapObject::Array(r)) => {
+                let mut equal = l.len() == r.len();
+                for (l, r) in l.iter().zip(r.iter()) {
+                    equal &= self.equal(l, r)?;
+                }
+
+                Some(equal)
+            }
+            (HeapObject::Struct(l), HeapObject::Struct(r)) if l.name == r.name && l.fields.len() == r.fields.len() => {
+                let mut equal = true;
+                for ((ln, lv), (rn, rv)) in l.fields.iter().zip(&r.fields) {
+                    if ln != rn {
+                        return None;
+                    }
+
+                    equal &= self.equal(lv, rv)?;
+                }
+
+                Some(equal)
+            }
+            _ => None,
+        }
+    }
+}
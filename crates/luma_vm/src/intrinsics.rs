@@ -0,0 +1,64 @@
+use luma_compiler::Intrinsic;
+
+use crate::{Heap, HeapObject, RuntimeErrorKind, Value};
+
+/// Runs an intrinsic, the arguments were type-checked by the compiler
+pub(crate) fn call_intrinsic(heap: &mut Heap, intrinsic: Intrinsic, args: &[Value]) -> Result<Value, RuntimeErrorKind> {
+    let text = |index: usize| {
+        heap.string(&args[index])
+            .ok_or(RuntimeErrorKind::TypeMismatch { operation: intrinsic.name() })
+    };
+
+    Ok(match intrinsic {
+        Intrinsic::Len => Value::Int32(text(0)?.chars().count() as i32),
+        Intrinsic::Slice => {
+            let text = text(0)?;
+            let len = text.chars().count();
+            let (start, end) = (char_index(&args[1], len)?, char_index(&args[2], len)?);
+
+            // the start may not pass the end
+            if start > end {
+                return Err(RuntimeErrorKind::InvalidRange { start, end });
+            }
+
+            let slice = text.chars().skip(start).take(end - start).collect::<String>();
+            heap.allocate_string(slice)
+        }
+        Intrinsic::CharAt => {
+            let text = text(0)?;
+            let len = text.chars().count();
+            let index = char_index(&args[1], len)?;
+
+            match text.chars().nth(index) {
+                Some(char) => Value::Char(char),
+                None => return Err(RuntimeErrorKind::IndexOutOfBounds { index: index as i64, len }),
+            }
+        }
+        Intrinsic::Chars => {
+            let chars = text(0)?.chars().map(Value::Char).collect();
+            Value::Object(heap.allocate(HeapObject::Array(chars)))
+        }
+        Intrinsic::ToString => {
+            let string = heap.display(&args[0]);
+            heap.allocate_string(string)
+        }
+        Intrinsic::ParseInt => Value::Int64(parse(text(0)?)?),
+        Intrinsic::ParseFloat => Value::Float64(parse(text(0)?)?),
+    })
+}
+
+/// Checks that a char index lies within a string of `len` chars, `len` itself being the end
+fn char_index(value: &Value, len: usize) -> Result<usize, RuntimeErrorKind> {
+    let Value::Int32(index) = *value else {
+        return Err(RuntimeErrorKind::TypeMismatch { operation: "indexing" });
+    };
+
+    usize::try_from(index)
+        .ok()
+        .filter(|&index| index <= len)
+        .ok_or(RuntimeErrorKind::IndexOutOfBounds { index: index as i64, len })
+}
+
+fn parse<T: std::str::FromStr>(text: &str) -> Result<T, RuntimeErrorKind> {
+    text.trim().parse().map_err(|_| RuntimeErrorKind::InvalidNumber { text: text.to_string() })
+}
@@ -1,2 +1,25 @@
+extern crate self as luma_vm;
+
+mod capabilities;
+mod convert;
+mod error;
+mod heap;
+mod intrinsics;
+mod limits;
+mod native;
+mod value;
 mod vm;
-pub use vm::LumaVM;
\ No newline at end of file
+
+pub use capabilities::Capabilities;
+pub use convert::{FromLuma, IntoLuma, LumaResult, LumaStruct, StructFields};
+pub use error::*;
+pub use luma_compiler::{NativeSignature, TypeKind, bytecode::BytecodeValue};
+pub use limits::VmLimits;
+pub use luma_derive::LumaStruct;
+pub use native::{HostFunction, HostReturn, IntoHostFunction, NativeFn};
+pub use heap::{GcStats, Heap, HeapObject, ObjectRef, StructObject};
+pub use value::Value;
+pub use vm::LumaVM;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,76 @@
+use std::time::{Duration, Instant};
+
+use crate::RuntimeErrorKind;
+
+/// Resources a single call into the VM may use, each of [`LumaVM::load`](crate::LumaVM::load),
+/// [`LumaVM::call`](crate::LumaVM::call) and [`LumaVM::call_function`](crate::LumaVM::call_function) starts with a fresh budget
+#[derive(Debug, Clone, PartialEq)]
+pub struct VmLimits {
+    /// instructions that may be executed, `None` for no limit
+    pub fuel: Option<u64>,
+
+    /// calls that may be active at once
+    pub max_call_depth: usize,
+
+    /// values the stack may hold
+    pub max_stack_size: usize,
+
+    /// bytes the live objects on the heap may occupy, `None` for no limit
+    pub max_heap_size: Option<usize>,
+
+    /// wall-clock time a call may take, `None` for no limit
+    pub timeout: Option<Duration>,
+}
+
+impl Default for VmLimits {
+    fn default() -> Self {
+        Self {
+            fuel: None,
+            max_call_depth: 1 << 16,
+            max_stack_size: 1 << 20,
+            max_heap_size: None,
+            timeout: None,
+        }
+    }
+}
+
+/// Resources left to the running call
+#[derive(Debug, Default)]
+pub(crate) struct Budget {
+    pub executed: u64,
+    deadline: Option<Instant>,
+}
+
+impl Budget {
+    /// Instructions executed between two deadline checks, reading the clock every instruction is too slow
+    const DEADLINE_INTERVAL: u64 = 1024;
+
+    pub fn new(limits: &VmLimits) -> Self {
+        Self {
+            executed: 0,
+            deadline: limits.timeout.map(|timeout| Instant::now() + timeout),
+        }
+    }
+
+    /// Accounts for the next instruction
+    #[inline]
+    pub fn tick(&mut self, limits: &VmLimits) -> Result<(), RuntimeErrorKind> {
+        self.executed += 1;
+
+        if let Some(limit) = limits.fuel
+            && self.executed > limit
+        {
+            return Err(RuntimeErrorKind::FuelExhausted { limit });
+        }
+
+        if let Some(deadline) = self.deadline
+            && self.executed.is_multiple_of(Self::DEADLINE_INTERVAL)
+            && Instant::now() >= deadline
+        {
+            let millis = limits.timeout.unwrap_or_default().as_millis();
+            return Err(RuntimeErrorKind::TimeLimitExceeded { millis });
+        }
+
+        Ok(())
+    }
+}
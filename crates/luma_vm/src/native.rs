@@ -0,0 +1,107 @@
+use std::fmt::Display;
+
+use luma_compiler::{NativeSignature, TypeKind, bytecode::BytecodeValue};
+use luma_diagnostic::AsDiagnostic;
+
+use crate::{FromLuma, IntoLuma};
+
+/// Implementation of a host function, an `Err` aborts execution with the message
+pub type NativeFn = dyn Fn(&[BytecodeValue]) -> Result<BytecodeValue, String>;
+
+/// A Rust function callable from Luma
+pub struct HostFunction {
+    pub signature: NativeSignature,
+    pub(crate) func: Box<NativeFn>,
+}
+
+impl HostFunction {
+    pub fn new(
+        signature: NativeSignature,
+        func: impl Fn(&[BytecodeValue]) -> Result<BytecodeValue, String> + 'static,
+    ) -> Self {
+        Self {
+            signature,
+            func: Box::new(func),
+        }
+    }
+
+    /// Wraps a Rust closure, the signature is derived from its argument and return types
+    pub fn from_fn<Args>(name: impl Into<String>, func: impl IntoHostFunction<Args>) -> Self {
+        func.into_host_function(name.into())
+    }
+}
+
+/// A Rust closure whose arguments implement [`FromLuma`] and whose return type implements [`HostReturn`]
+pub trait IntoHostFunction<Args> {
+    fn into_host_function(self, name: String) -> HostFunction;
+}
+
+/// The return type of a host closure, an `Err` aborts execution with its message.
+///
+/// Return a [`LumaResult`](crate::LumaResult) instead to hand the error to the script
+pub trait HostReturn {
+    fn luma_type() -> TypeKind;
+
+    fn into_result(self) -> Result<BytecodeValue, String>;
+}
+
+impl<T: IntoLuma> HostReturn for T {
+    fn luma_type() -> TypeKind {
+        T::luma_type()
+    }
+
+    fn into_result(self) -> Result<BytecodeValue, String> {
+        Ok(self.into_luma())
+    }
+}
+
+impl<T: IntoLuma, E: Display> HostReturn for Result<T, E> {
+    fn luma_type() -> TypeKind {
+        T::luma_type()
+    }
+
+    fn into_result(self) -> Result<BytecodeValue, String> {
+        self.map(T::into_luma).map_err(|err| err.to_string())
+    }
+}
+
+/// Converts the next argument of a host closure, the message is reported as a host error
+fn argument<T: FromLuma>(args: &mut impl Iterator<Item = BytecodeValue>) -> Result<T, String> {
+    let arg = args.next().ok_or("missing argument")?;
+
+    T::from_luma(arg).map_err(|kind| kind.annotation().unwrap_or_else(|| kind.title()))
+}
+
+macro_rules! host_functions {
+    ($(($($arg:ident),*)),* $(,)?) => {$(
+        impl<Func, Ret, $($arg),*> IntoHostFunction<($($arg,)*)> for Func
+        where
+            Func: Fn($($arg),*) -> Ret + 'static,
+            Ret: HostReturn,
+            $($arg: FromLuma,)*
+        {
+            #[allow(non_snake_case, unused_mut, unused_variables)]
+            fn into_host_function(self, name: String) -> HostFunction {
+                let signature = NativeSignature::new(name, vec![$($arg::luma_type()),*], Ret::luma_type());
+
+                HostFunction::new(signature, move |args| {
+                    // the VM passes exactly as many arguments as the signature declares
+                    let mut args = args.iter().cloned();
+                    $(let $arg = argument::<$arg>(&mut args)?;)*
+
+                    self($($arg),*).into_result()
+                })
+            }
+        }
+    )*};
+}
+
+host_functions! {
+    (),
+    (A),
+    (A, B),
+    (A, B, C),
+    (A, B, C, D),
+    (A, B, C, D, E),
+    (A, B, C, D, E, F),
+}
@@ -0,0 +1,165 @@
+use std::{fs, path::PathBuf};
+
+use luma_compiler::bytecode::BytecodeValue;
+use pretty_assertions::assert_eq;
+
+use crate::{Capabilities, LumaVM, RuntimeErrorKind, tests::embedding::compile_for};
+
+const SOURCE: &str = "
+    pub func read(path: str): str!str { std_fs_read(path) };
+    pub func write(path: str, contents: str): ()!str { std_fs_write(path, contents) };
+    pub func append(path: str, contents: str): ()!str { std_fs_append(path, contents) };
+    pub func remove(path: str): ()!str { std_fs_remove(path) };
+    pub func create_dir(path: str): ()!str { std_fs_create_dir(path) };
+    pub func list(path: str): str!str { std_fs_list_dir(path) };
+    pub func exists(path: str): bool { std_fs_exists(path) };
+";
+
+/// A fresh directory under the system's temp directory, removed when dropped
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("luma-{name}-{}", std::process::id()));
+
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+
+        Self(path)
+    }
+
+    fn path(&self, name: &str) -> BytecodeValue {
+        string(&self.0.join(name).to_string_lossy())
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+fn string(text: &str) -> BytecodeValue {
+    BytecodeValue::String(text.into())
+}
+
+/// Asserts that a native returned an error with a message
+fn assert_failed(value: BytecodeValue) {
+    let BytecodeValue::Err(error) = value else {
+        panic!("expected an error, found {value:?}");
+    };
+    assert!(matches!(*error, BytecodeValue::String(ref message) if !message.is_empty()));
+}
+
+fn fs_vm() -> LumaVM {
+    let mut vm = LumaVM::new();
+    vm.grant(Capabilities { fs: true, ..Capabilities::NONE });
+
+    let module = compile_for(&vm, SOURCE);
+    vm.load(module).unwrap();
+    vm
+}
+
+#[test]
+fn capabilities_are_disabled_by_default() {
+    let vm = LumaVM::new();
+
+    assert_eq!(vm.capabilities(), Capabilities::NONE);
+    assert!(vm.native_signatures().is_empty());
+}
+
+#[test]
+fn scripts_without_a_capability_fail_to_load() {
+    let mut granted = LumaVM::new();
+    granted.grant(Capabilities::ALL);
+
+    let module = compile_for(&granted, "var now = std_time_unix_millis();");
+
+    assert!(matches!(
+        LumaVM::new().load(module).unwrap_err().kind,
+        RuntimeErrorKind::UnknownNative { ref name } if name == "std_time_unix_millis"
+    ));
+}
+
+#[test]
+fn granting_registers_only_the_granted_natives() {
+    let mut vm = LumaVM::new();
+    vm.grant(Capabilities { time: true, ..Capabilities::NONE });
+
+    let names = vm.native_signatures().into_iter().map(|native| native.name).collect::<Vec<_>>();
+    assert_eq!(names, ["std_time_unix_millis", "std_time_elapsed_millis"]);
+
+    // granting again doesn't register duplicates
+    vm.grant(Capabilities::ALL);
+    vm.grant(Capabilities::ALL);
+
+    assert_eq!(vm.capabilities(), Capabilities::ALL);
+    assert_eq!(vm.native_signatures().len(), 12);
+}
+
+#[test]
+fn files_are_written_read_and_removed() {
+    let dir = TempDir::new("files");
+    let mut vm = fs_vm();
+
+    assert_eq!(vm.call("write", vec![dir.path("a.txt"), string("hello")]).unwrap(), BytecodeValue::Unit);
+    assert_eq!(vm.call("append", vec![dir.path("a.txt"), string(" world")]).unwrap(), BytecodeValue::Unit);
+    assert_eq!(vm.call("read", vec![dir.path("a.txt")]).unwrap(), string("hello world"));
+    assert_eq!(fs::read_to_string(dir.0.join("a.txt")).unwrap(), "hello world");
+
+    assert_eq!(vm.call("exists", vec![dir.path("a.txt")]).unwrap(), BytecodeValue::Bool(true));
+    assert_eq!(vm.call("remove", vec![dir.path("a.txt")]).unwrap(), BytecodeValue::Unit);
+    assert_eq!(vm.call("exists", vec![dir.path("a.txt")]).unwrap(), BytecodeValue::Bool(false));
+}
+
+#[test]
+fn directories_are_created_and_listed_in_order() {
+    let dir = TempDir::new("dirs");
+    let mut vm = fs_vm();
+
+    assert_eq!(vm.call("create_dir", vec![dir.path("nested/inner")]).unwrap(), BytecodeValue::Unit);
+    fs::write(dir.0.join("b.txt"), "").unwrap();
+
+    assert_eq!(
+        vm.call("list", vec![string(&dir.0.to_string_lossy())]).unwrap(),
+        string("b.txt\nnested")
+    );
+}
+
+#[test]
+fn failures_are_errors_with_a_message() {
+    let dir = TempDir::new("failures");
+    let mut vm = fs_vm();
+
+    assert_failed(vm.call("read", vec![dir.path("missing.txt")]).unwrap());
+    assert_failed(vm.call("remove", vec![dir.path("missing.txt")]).unwrap());
+    assert_failed(vm.call("list", vec![dir.path("missing")]).unwrap());
+}
+
+#[test]
+fn environment_variables_and_clocks_are_read() {
+    let mut vm = LumaVM::new();
+    vm.grant(Capabilities { env: true, time: true, ..Capabilities::NONE });
+
+    let module = compile_for(
+        &vm,
+        "
+        pub func get(name: str): str!str { std_env_var(name) };
+        pub func has(name: str): bool { std_env_has_var(name) };
+        pub func now(): i64 { std_time_unix_millis() };
+        ",
+    );
+    vm.load(module).unwrap();
+
+    let path = std::env::var("PATH").unwrap_or_default();
+    assert_eq!(vm.call("get", vec![string("PATH")]).unwrap(), string(&path));
+
+    let missing = string("LUMA_SURELY_UNSET_VARIABLE");
+    assert_eq!(vm.call("has", vec![missing.clone()]).unwrap(), BytecodeValue::Bool(false));
+    assert_failed(vm.call("get", vec![missing]).unwrap());
+
+    let BytecodeValue::Int64(now) = vm.call("now", Vec::new()).unwrap() else {
+        panic!("expected milliseconds");
+    };
+    assert!(now > 1_600_000_000_000);
+}
@@ -0,0 +1,158 @@
+use luma_compiler::{Type, TypeKind, bytecode::BytecodeValue};
+use pretty_assertions::assert_eq;
+
+use crate::{FromLuma, IntoLuma, LumaStruct, LumaVM, RuntimeErrorKind, tests::compile_module};
+
+#[derive(Debug, Clone, PartialEq, LumaStruct)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, PartialEq, LumaStruct)]
+struct Person {
+    name: String,
+    age: u8,
+}
+
+#[test]
+fn primitives_round_trip() {
+    assert_eq!(42i64.into_luma(), BytecodeValue::Int64(42));
+    assert_eq!("hi".into_luma(), BytecodeValue::String("hi".into()));
+    assert_eq!(char::from_luma(BytecodeValue::Char('x')).unwrap(), 'x');
+    assert_eq!(<()>::from_luma(().into_luma()).unwrap(), ());
+}
+
+#[test]
+fn tuples_and_vecs_convert_element_wise() {
+    let value = (1u8, String::from("a"), vec![true, false]).into_luma();
+
+    assert_eq!(
+        <(u8, String, Vec<bool>) as IntoLuma>::luma_type(),
+        TypeKind::Tuple(vec![
+            Type::unspanned(TypeKind::UInt8),
+            Type::unspanned(TypeKind::String),
+            Type::unspanned(TypeKind::Array(Box::new(Type::unspanned(TypeKind::Bool)))),
+        ])
+    );
+    assert_eq!(
+        <(u8, String, Vec<bool>)>::from_luma(value).unwrap(),
+        (1, String::from("a"), vec![true, false])
+    );
+}
+
+#[test]
+fn mismatched_values_fail_to_convert() {
+    assert!(matches!(
+        i32::from_luma(BytecodeValue::Bool(true)).unwrap_err(),
+        RuntimeErrorKind::ConversionFailed { expected, found } if expected == "i32" && found == "bool"
+    ));
+    assert!(<(i32, i32)>::from_luma(BytecodeValue::Tuple(vec![BytecodeValue::Int32(1)])).is_err());
+}
+
+#[test]
+fn derived_structs_map_fields_by_name() {
+    let point = Point { x: 1, y: -2 };
+    let value = point.clone().into_luma();
+
+    assert_eq!(
+        value,
+        BytecodeValue::Struct {
+            name: String::from("Point"),
+            fields: vec![
+                (String::from("x"), BytecodeValue::Int32(1)),
+                (String::from("y"), BytecodeValue::Int32(-2)),
+            ],
+        }
+    );
+
+    let reordered = BytecodeValue::Struct {
+        name: String::from("Point"),
+        fields: vec![
+            (String::from("y"), BytecodeValue::Int32(-2)),
+            (String::from("x"), BytecodeValue::Int32(1)),
+        ],
+    };
+
+    assert_eq!(Point::from_luma(reordered).unwrap(), point);
+    assert!(matches!(
+        Point::from_luma(BytecodeValue::Struct { name: String::from("Point"), fields: Vec::new() }).unwrap_err(),
+        RuntimeErrorKind::MissingField { .. }
+    ));
+}
+
+#[test]
+fn derived_structs_are_checked_against_the_compiled_layout() {
+    let mut vm = LumaVM::new();
+    vm.load(compile_module(
+        "
+        struct Point { x: i32, y: i32 };
+        struct Person { name: str, age: i32 };
+        ",
+    ))
+    .unwrap();
+
+    assert!(vm.check_struct::<Point>().is_ok());
+    assert!(matches!(
+        vm.check_struct::<Person>().unwrap_err().kind,
+        RuntimeErrorKind::StructLayoutMismatch { name, field } if name == "Person" && field == "age"
+    ));
+}
+
+#[test]
+fn structs_are_compared_by_their_fields() {
+    let mut vm = LumaVM::new();
+    vm.load(compile_module(
+        "
+        struct Point { x: i32, y: i32 };
+        pub func differ(a: Point, b: Point): bool { a != b };
+        ",
+    ))
+    .unwrap();
+
+    let mut differ = |a: Point, b: Point| vm.call("differ", vec![a.into_luma(), b.into_luma()]).unwrap();
+
+    assert_eq!(differ(Point { x: 1, y: 2 }, Point { x: 1, y: 3 }), BytecodeValue::Bool(true));
+    assert_eq!(differ(Point { x: 1, y: 2 }, Point { x: 1, y: 2 }), BytecodeValue::Bool(false));
+}
+
+#[test]
+fn missing_structs_are_reported() {
+    let mut vm = LumaVM::new();
+    vm.load(compile_module("var x = 1;")).unwrap();
+
+    assert!(matches!(
+        vm.check_struct::<Point>().unwrap_err().kind,
+        RuntimeErrorKind::StructNotFound { .. }
+    ));
+}
+
+#[test]
+fn closures_are_registered_as_host_functions() {
+    let mut vm = LumaVM::new();
+    vm.register_fn("repeat", |text: String, times: u32| text.repeat(times as usize));
+    vm.register_fn("checked_half", |value: i32| {
+        if value % 2 == 0 { Ok(value / 2) } else { Err(format!("{value} is odd")) }
+    });
+
+    let signatures = vm.native_signatures();
+    assert_eq!(signatures[0].params, vec![TypeKind::String, TypeKind::UInt32]);
+    assert_eq!(signatures[0].return_type, TypeKind::String);
+    assert_eq!(signatures[1].return_type, TypeKind::Int32);
+
+    let module = super::embedding::compile_for(
+        &vm,
+        "
+        pub func twice(text: str): str { repeat(text, 2) };
+        pub func half(value: i32): i32 { checked_half(value) };
+        ",
+    );
+    vm.load(module).unwrap();
+
+    assert_eq!(vm.call("twice", vec!["ab".into_luma()]).unwrap(), "abab".into_luma());
+    assert_eq!(vm.call("half", vec![8.into_luma()]).unwrap(), BytecodeValue::Int32(4));
+    assert!(matches!(
+        vm.call("half", vec![3.into_luma()]).unwrap_err().kind,
+        RuntimeErrorKind::HostError { function, message } if function == "checked_half" && message == "3 is odd"
+    ));
+}
@@ -0,0 +1,135 @@
+use luma_compiler::{LumaCompiler, NativeSignature, TypeKind, bytecode::{BytecodeValue, ModuleBytecode}};
+use luma_core::CodeSource;
+use pretty_assertions::assert_eq;
+
+use crate::{HostFunction, LumaVM, RuntimeErrorKind, tests::compile_module};
+
+/// Compiles the source against the natives registered with the VM
+pub fn compile_for(vm: &LumaVM, src: &str) -> ModuleBytecode {
+    let result = LumaCompiler::new()
+        .with_natives(vm.native_signatures())
+        .compile([CodeSource::from(src)]);
+
+    assert!(!result.has_errors(), "compilation failed: {:#?}", result.diagnostics);
+
+    result.result.and_then(|modules| modules.into_iter().next()).expect("expected a module")
+}
+
+fn vm_with_clamp() -> LumaVM {
+    let mut vm = LumaVM::new();
+
+    vm.register_function(HostFunction::new(
+        NativeSignature::new("clamp", [TypeKind::Int32, TypeKind::Int32], TypeKind::Int32),
+        |args| match args {
+            [BytecodeValue::Int32(value), BytecodeValue::Int32(max)] => Ok(BytecodeValue::Int32(*value.min(max))),
+            _ => Err(String::from("expected two integers")),
+        },
+    ));
+
+    vm
+}
+
+#[test]
+fn exported_functions_are_called_by_name() {
+    let mut vm = LumaVM::new();
+    vm.load(compile_module("pub func add(a: i32, b: i32): i32 { a + b };")).unwrap();
+
+    assert_eq!(
+        vm.call("add", vec![BytecodeValue::Int32(2), BytecodeValue::Int32(3)]).unwrap(),
+        BytecodeValue::Int32(5)
+    );
+}
+
+#[test]
+fn private_functions_are_not_exported() {
+    let mut vm = LumaVM::new();
+    vm.load(compile_module("func hidden(): i32 { 1 };")).unwrap();
+
+    assert!(matches!(
+        vm.call("hidden", Vec::new()).unwrap_err().kind,
+        RuntimeErrorKind::ExportNotFound { .. }
+    ));
+}
+
+#[test]
+fn functions_call_each_other_and_themselves() {
+    let mut vm = LumaVM::new();
+    vm.load(compile_module(
+        "
+        func double(x: i64): i64 { x * 2 };
+
+        pub func factorial(n: i64): i64 {
+            if n <= 1 { 1 } else { n * factorial(n - 1) }
+        };
+
+        pub func run(n: i64): i64 { double(factorial(n)) };
+        ",
+    ))
+    .unwrap();
+
+    assert_eq!(vm.call("factorial", vec![BytecodeValue::Int64(10)]).unwrap(), BytecodeValue::Int64(3_628_800));
+    assert_eq!(vm.call("run", vec![BytecodeValue::Int64(5)]).unwrap(), BytecodeValue::Int64(240));
+}
+
+#[test]
+fn host_functions_are_called_from_luma() {
+    let mut vm = vm_with_clamp();
+    let module = compile_for(&vm, "pub func limit(x: i32): i32 { clamp(x + 1, 10) };");
+
+    assert_eq!(module.natives, vec![String::from("clamp")]);
+
+    vm.load(module).unwrap();
+
+    assert_eq!(vm.call("limit", vec![BytecodeValue::Int32(3)]).unwrap(), BytecodeValue::Int32(4));
+    assert_eq!(vm.call("limit", vec![BytecodeValue::Int32(30)]).unwrap(), BytecodeValue::Int32(10));
+}
+
+#[test]
+fn host_errors_abort_with_a_trace() {
+    let mut vm = LumaVM::new();
+    vm.register_function(HostFunction::new(
+        NativeSignature::new("fail", [], TypeKind::Unit),
+        |_| Err(String::from("nope")),
+    ));
+
+    let module = compile_for(&vm, "pub func run(): () { fail() };");
+    vm.load(module).unwrap();
+
+    let err = vm.call("run", Vec::new()).unwrap_err();
+
+    assert!(matches!(err.kind, RuntimeErrorKind::HostError { ref message, .. } if message == "nope"));
+    assert_eq!(err.trace[0].function, "run");
+}
+
+#[test]
+fn unregistered_natives_fail_to_load() {
+    let module = compile_for(&vm_with_clamp(), "var x = clamp(1, 2);");
+
+    assert!(matches!(
+        LumaVM::new().load(module).unwrap_err().kind,
+        RuntimeErrorKind::UnknownNative { ref name } if name == "clamp"
+    ));
+}
+
+#[test]
+fn runaway_recursion_is_an_error() {
+    let mut vm = LumaVM::new();
+    vm.load(compile_module("pub func forever(): i32 { forever() };")).unwrap();
+
+    assert!(matches!(
+        vm.call("forever", Vec::new()).unwrap_err().kind,
+        RuntimeErrorKind::CallDepthExceeded { .. }
+    ));
+}
+
+#[test]
+fn exports_survive_serialization() {
+    let module = compile_for(&vm_with_clamp(), "pub func f(): i32 { clamp(5, 3) };");
+    let restored = ModuleBytecode::from_bytes(&module.to_bytes()).unwrap();
+
+    assert_eq!(restored, module);
+
+    let mut vm = vm_with_clamp();
+    vm.load(restored).unwrap();
+    assert_eq!(vm.call("f", Vec::new()).unwrap(), BytecodeValue::Int32(3));
+}
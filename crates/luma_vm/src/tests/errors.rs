@@ -0,0 +1,114 @@
+use std::collections::HashSet;
+
+use luma_diagnostic::{Diagnostic, Printer};
+use pretty_assertions::assert_eq;
+
+use crate::{
+    LumaVM, RuntimeErrorKind, TRACE_ENDS, Value, explain,
+    tests::{call, compile_with_sources},
+};
+
+const DIV: &str = "func div(a: i32, b: i32): i32 { a / b };";
+
+#[test]
+fn arithmetic_errors() {
+    assert!(matches!(
+        call(DIV, 1, vec![Value::Int32(1), Value::Int32(0)]).unwrap_err().kind,
+        RuntimeErrorKind::DivisionByZero
+    ));
+    assert!(matches!(
+        call(DIV, 1, vec![Value::Int32(i32::MIN), Value::Int32(-1)]).unwrap_err().kind,
+        RuntimeErrorKind::IntegerOverflow
+    ));
+}
+
+#[test]
+fn errors_carry_a_stack_trace() {
+    let err = call(DIV, 1, vec![Value::Int32(1), Value::Int32(0)]).unwrap_err();
+
+    assert_eq!(err.trace.len(), 1);
+    assert_eq!(err.trace[0].function, "div");
+
+    let span = err.trace[0].span.expect("the division has a span");
+    assert_eq!(&DIV[span.start as usize..span.end as usize], "a / b");
+
+    assert_eq!(
+        err.to_string(),
+        "division by zero: attempted to divide by zero\n    at div (32..37)"
+    );
+}
+
+#[test]
+fn errors_outside_of_functions_have_no_trace() {
+    let err = call(DIV, 5, Vec::new()).unwrap_err();
+
+    assert!(matches!(err.kind, RuntimeErrorKind::FunctionNotFound { index: 5 }));
+    assert!(err.trace.is_empty());
+}
+
+#[test]
+fn errors_render_like_compile_diagnostics() {
+    let (sources, module) = compile_with_sources(DIV);
+    let err = LumaVM::new()
+        .call_function(&module, 1, vec![Value::Int32(1), Value::Int32(0)])
+        .unwrap_err();
+
+    let span = err.trace[0].span;
+    let diagnostic = Diagnostic::from(err);
+
+    assert_eq!(diagnostic.title, "division by zero");
+    assert_eq!(diagnostic.code, Some("E0909"));
+    assert_eq!(diagnostic.span, span);
+
+    let rendered = Printer::print(&sources, &[diagnostic]);
+
+    assert!(rendered.contains("division by zero"));
+    assert!(rendered.contains("[E0909]"));
+    assert!(rendered.contains("attempted to divide by zero"));
+    assert!(rendered.contains("in function `div`"));
+}
+
+#[test]
+fn deep_traces_stay_bounded() {
+    let src = r#"
+        func down(n: i32): i32 { if n == 0 { 1 / n } else { down(n - 1) } };
+        func even(n: i32): i32 { if n == 0 { 1 / n } else { odd(n - 1) } };
+        func odd(n: i32): i32 { even(n - 1) };
+    "#;
+
+    // direct recursion collapses into the frame that repeats
+    let err = call(src, 1, vec![Value::Int32(1000)]).unwrap_err();
+
+    assert_eq!(err.trace.len(), 2);
+    assert_eq!((err.trace[1].function.as_str(), err.trace[1].repeated), ("down", 999));
+    assert!(err.to_string().ends_with("... 999 more calls to `down`"));
+
+    // alternating calls don't repeat, only the ends of the trace are kept
+    let err = call(src, 2, vec![Value::Int32(1000)]).unwrap_err();
+
+    assert_eq!(err.trace.len(), 2 * TRACE_ENDS);
+    assert_eq!(err.omitted, 1001 - 2 * TRACE_ENDS);
+    assert!(err.to_string().contains(&format!("... {} more frames", err.omitted)));
+    assert!(Diagnostic::from(err).additional_contexts.len() <= 2 * TRACE_ENDS + 1);
+}
+
+#[test]
+fn codes_are_unique_across_the_compiler_and_the_vm() {
+    let catalogs = luma_compiler::diagnostics::EXPLANATIONS.iter().chain([&RuntimeErrorKind::EXPLANATIONS]);
+    let mut codes = HashSet::new();
+
+    for explanation in catalogs.flat_map(|explanations| explanations.iter()) {
+        assert!(codes.insert(explanation.code), "'{}' is used twice", explanation.code);
+        assert!(!explanation.lines.is_empty(), "'{}' has no explanation", explanation.code);
+    }
+}
+
+#[test]
+fn runtime_codes_are_explained() {
+    let explanation = explain("e0909").expect("expected an explanation");
+
+    assert_eq!(explanation.code, "E0909");
+    assert!(explanation.to_string().starts_with("E0909: division by zero\n\nAn integer was divided by zero"));
+    assert_eq!(explain("E0313").map(|explanation| explanation.code), Some("E0313"));
+    assert_eq!(explain("E9999"), None);
+}
@@ -0,0 +1,163 @@
+use luma_compiler::{
+    CompilerOptions, LumaCompiler,
+    bytecode::{BytecodeValue, ModuleBytecode, Opcode},
+    stages::{
+        codegen::{
+            chunk::{CodeChunk, FunctionChunk},
+            stores::ExportTable,
+        },
+        optimizer::OptimizationLevel,
+    },
+};
+use luma_core::{CodeSource, CodeSourceId};
+use pretty_assertions::assert_eq;
+
+use crate::{
+    LumaVM, RuntimeErrorKind, Value,
+    tests::{call, compile_module},
+};
+
+#[test]
+fn init_chunk_returns_unit() {
+    let module = compile_module("var x = 1 + 2;");
+
+    assert_eq!(LumaVM::new().run(&module).unwrap(), Value::Unit);
+}
+
+#[test]
+fn function_with_arguments() {
+    let src = r#"
+        func add(a: i32, b: i32): i32 {
+            var sum = a + b;
+            sum * 2
+        };
+    "#;
+
+    assert_eq!(call(src, 1, vec![Value::Int32(3), Value::Int32(4)]).unwrap(), Value::Int32(14));
+    assert!(matches!(
+        call(src, 1, vec![Value::Int32(3)]).unwrap_err().kind,
+        RuntimeErrorKind::ArityMismatch { expected: 2, found: 1 }
+    ));
+}
+
+#[test]
+fn if_else_takes_the_right_branch() {
+    let src = r#"
+        func max(a: i32, b: i32): i32 {
+            if a > b { a } else { b }
+        };
+    "#;
+
+    assert_eq!(call(src, 1, vec![Value::Int32(7), Value::Int32(2)]).unwrap(), Value::Int32(7));
+    assert_eq!(call(src, 1, vec![Value::Int32(-1), Value::Int32(2)]).unwrap(), Value::Int32(2));
+}
+
+#[test]
+fn short_circuit_skips_division_by_zero() {
+    let src = r#"
+        func check(x: i32): bool {
+            x != 0 && 10 / x > 1
+        };
+    "#;
+
+    assert_eq!(call(src, 1, vec![Value::Int32(0)]).unwrap(), Value::Bool(false));
+    assert_eq!(call(src, 1, vec![Value::Int32(2)]).unwrap(), Value::Bool(true));
+}
+
+#[test]
+fn nan_is_unequal_but_not_ordered() {
+    let src = r#"
+        func equal(a: f64, b: f64): bool { a == b };
+        func not_equal(a: f64, b: f64): bool { a != b };
+        func lesser(a: f64, b: f64): bool { a < b };
+    "#;
+    let nan = || vec![Value::Float64(f64::NAN), Value::Float64(f64::NAN)];
+
+    assert_eq!(call(src, 1, nan()).unwrap(), Value::Bool(false));
+    assert_eq!(call(src, 2, nan()).unwrap(), Value::Bool(true));
+    assert!(matches!(
+        call(src, 3, nan()).unwrap_err().kind,
+        RuntimeErrorKind::TypeMismatch { operation: "comparison" }
+    ));
+}
+
+#[test]
+fn wide_jumps_are_followed() {
+    // index based jumps, assembling widens the jump over the unit pushes
+    let mut instructions = vec![Opcode::LoadConst(0), Opcode::jump_if_true(70_002)];
+    instructions.extend(std::iter::repeat_n(Opcode::PushUnit, 70_000));
+    instructions.extend([Opcode::LoadConst(1), Opcode::Return]);
+
+    let code = CodeChunk::from(instructions);
+    assert!(code.iter().any(|(_, opcode)| opcode == Opcode::JumpIfTrueWide(70_008)));
+
+    let module = ModuleBytecode {
+        source_id: CodeSourceId::ZERO,
+        constants: vec![BytecodeValue::Bool(true), BytecodeValue::Int32(7)],
+        functions: vec![FunctionChunk {
+            name: FunctionChunk::INIT_NAME.to_string(),
+            code,
+            arity: 0,
+        }],
+        natives: Vec::new(),
+        structs: Vec::new(),
+        globals: Vec::new(),
+        exports: ExportTable::new(),
+    };
+
+    assert_eq!(LumaVM::new().run(&module).unwrap(), Value::Int32(7));
+}
+
+#[test]
+fn short_circuit_conditions_jump_over_wide_branches() {
+    // the branch is larger than 64 KiB, so the jumps into the else branch have to be widened one after the other
+    let branch = "x = 1;".repeat(25_000);
+
+    for level in [OptimizationLevel::None, OptimizationLevel::Basic] {
+        for (condition, expected) in [("a && b", [1, 2, 2, 2]), ("a || b", [1, 1, 1, 2])] {
+            let src = format!(
+                "pub func f(a: bool, b: bool): i32 {{ var x = 0; var r = if {condition} {{ {branch} 1 }} else {{ 2 }}; r }};"
+            );
+
+            let mut options = CompilerOptions::default();
+            options.optimizer.level = level;
+
+            let result = LumaCompiler::configure(options).compile([CodeSource::from(src)]);
+            assert!(!result.has_errors(), "compilation failed: {:#?}", result.diagnostics);
+
+            let mut vm = LumaVM::new();
+            vm.load(result.result.unwrap().remove(0)).unwrap();
+
+            let args = [(true, true), (true, false), (false, true), (false, false)];
+            for ((a, b), expected) in args.into_iter().zip(expected) {
+                assert_eq!(
+                    vm.call("f", vec![BytecodeValue::Bool(a), BytecodeValue::Bool(b)]).unwrap(),
+                    BytecodeValue::Int32(expected),
+                    "'{condition}' with a = {a}, b = {b} at {level:?}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn invalid_bytes_are_reported() {
+    let module = ModuleBytecode {
+        source_id: CodeSourceId::ZERO,
+        constants: Vec::new(),
+        functions: vec![FunctionChunk {
+            name: FunctionChunk::INIT_NAME.to_string(),
+            code: CodeChunk::from(vec![Opcode::PushUnit, Opcode::Pop, Opcode::Pop]),
+            arity: 0,
+        }],
+        natives: Vec::new(),
+        structs: Vec::new(),
+        globals: Vec::new(),
+        exports: ExportTable::new(),
+    };
+
+    assert!(matches!(
+        LumaVM::new().run(&module).unwrap_err().kind,
+        RuntimeErrorKind::StackUnderflow { offset: 2 }
+    ));
+}
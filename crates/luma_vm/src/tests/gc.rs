@@ -0,0 +1,79 @@
+use luma_compiler::bytecode::BytecodeValue;
+use pretty_assertions::assert_eq;
+
+use crate::{Heap, HeapObject, LumaVM, Value, VmLimits, tests::compile_module};
+
+const SOURCE: &str = "
+    pub func greet(): str { \"hello\" };
+    pub func same(a: str, b: str): bool { a == b };
+    pub func pick(first: bool, a: str, b: str): str { if first { a } else { b } };
+    pub func shout(text: str): str { text + \"!\" };
+";
+
+fn string(text: &str) -> BytecodeValue {
+    BytecodeValue::String(text.into())
+}
+
+#[test]
+fn unreachable_objects_are_collected() {
+    let mut heap = Heap::new();
+    let inner = heap.allocate(HeapObject::String("kept".into()));
+    let outer = heap.allocate(HeapObject::Tuple(vec![Value::Object(inner), Value::Int32(1)]));
+    heap.allocate(HeapObject::String("garbage".into()));
+
+    heap.collect(&[Value::Object(outer)]);
+
+    let stats = heap.stats();
+    assert_eq!((stats.collections, stats.freed_objects, stats.live_objects), (1, 1, 2));
+    assert_eq!(heap.get(inner), &HeapObject::String("kept".into()));
+
+    heap.collect(&[]);
+    assert_eq!((heap.stats().live_objects, heap.bytes()), (0, 0));
+}
+
+#[test]
+fn stress_mode_collects_on_every_allocation() {
+    let mut vm = LumaVM::new();
+    vm.set_gc_stress(true);
+    vm.load(compile_module(SOURCE)).unwrap();
+
+    assert_eq!(vm.call("greet", Vec::new()).unwrap(), string("hello"));
+    assert_eq!(
+        vm.call("pick", vec![BytecodeValue::Bool(false), string("a"), string("b")]).unwrap(),
+        string("b")
+    );
+    assert_eq!(vm.call("same", vec![string("x"), string("x")]).unwrap(), BytecodeValue::Bool(true));
+    assert!(vm.gc_stats().collections >= 3);
+}
+
+#[test]
+fn garbage_does_not_count_against_the_heap_limit() {
+    let mut vm = LumaVM::configure(VmLimits {
+        max_heap_size: Some(256),
+        ..VmLimits::default()
+    });
+    vm.load(compile_module(SOURCE)).unwrap();
+
+    for _ in 0..100 {
+        assert_eq!(vm.call("shout", vec![string("hey")]).unwrap(), string("hey!"));
+    }
+
+    assert!(vm.gc_stats().freed_objects > 0);
+    assert!(vm.gc_stats().live_bytes <= 256);
+}
+
+#[test]
+fn objects_returned_to_the_host_can_be_freed() {
+    let mut vm = LumaVM::new();
+    let module = compile_module(SOURCE);
+
+    let value = vm.call_function(&module, 1, Vec::new()).unwrap();
+    let Value::Object(object) = value else {
+        panic!("expected a string object, found {value}");
+    };
+
+    assert_eq!(vm.heap().get(object), &HeapObject::String("hello".into()));
+
+    vm.collect_garbage();
+    assert_eq!(vm.gc_stats().live_objects, 0);
+}
@@ -0,0 +1,43 @@
+use luma_compiler::bytecode::BytecodeValue;
+use pretty_assertions::assert_eq;
+
+use crate::{LumaVM, RuntimeErrorKind, tests::compile_module};
+
+const SOURCE: &str = "
+    func inc(p: *i32) { *p = *p + 1; };
+    pub var count = 0;
+    var base = count + 10;
+    pub func next(): i32 { count = count + 1; count };
+    pub func offset(): i32 { base + late };
+    pub func bumped(): i32 { inc(&count); count };
+    var late = 5;
+";
+
+fn vm() -> LumaVM {
+    let mut vm = LumaVM::new();
+    vm.load(compile_module(SOURCE)).unwrap();
+    vm
+}
+
+#[test]
+fn functions_share_globals() {
+    let mut vm = vm();
+
+    assert_eq!(vm.call("next", vec![]).unwrap(), BytecodeValue::Int32(1));
+    assert_eq!(vm.call("next", vec![]).unwrap(), BytecodeValue::Int32(2));
+    assert_eq!(vm.call("bumped", vec![]).unwrap(), BytecodeValue::Int32(3));
+    assert_eq!(vm.call("offset", vec![]).unwrap(), BytecodeValue::Int32(15));
+}
+
+#[test]
+fn exported_globals_are_reachable_from_the_host() {
+    let mut vm = vm();
+
+    assert_eq!(vm.global("count").unwrap(), BytecodeValue::Int32(0));
+
+    vm.set_global("count", BytecodeValue::Int32(41)).unwrap();
+    assert_eq!(vm.call("next", vec![]).unwrap(), BytecodeValue::Int32(42));
+
+    assert!(matches!(vm.global("base").map_err(|err| err.kind), Err(RuntimeErrorKind::GlobalNotFound { .. })));
+    assert!(matches!(vm.set_global("count", BytecodeValue::Bool(true)).map_err(|err| err.kind), Err(RuntimeErrorKind::ConversionFailed { .. })));
+}
@@ -0,0 +1,21 @@
+use luma_compiler::bytecode::BytecodeValue;
+use pretty_assertions::assert_eq;
+
+use crate::{LumaVM, tests::compile_module};
+
+const SOURCE: &str = "
+    pub var start = twice(21);
+    pub func is_even(n: i32): bool { if n == 0 { true } else { is_odd(n - 1) } };
+    func is_odd(n: i32): bool { if n == 0 { false } else { is_even(n - 1) } };
+    func twice(n: i32): i32 { n * 2 };
+";
+
+#[test]
+fn functions_are_called_before_their_declaration() {
+    let mut vm = LumaVM::new();
+    vm.load(compile_module(SOURCE)).unwrap();
+
+    assert_eq!(vm.global("start").unwrap(), BytecodeValue::Int32(42));
+    assert_eq!(vm.call("is_even", vec![BytecodeValue::Int32(10)]).unwrap(), BytecodeValue::Bool(true));
+    assert_eq!(vm.call("is_even", vec![BytecodeValue::Int32(7)]).unwrap(), BytecodeValue::Bool(false));
+}
@@ -0,0 +1,86 @@
+use std::time::Duration;
+
+use luma_compiler::bytecode::BytecodeValue;
+use pretty_assertions::assert_eq;
+
+use crate::{LumaVM, RuntimeErrorKind, VmLimits, tests::compile_module};
+
+const SOURCE: &str = "
+    pub func count(n: i64): i64 { if n == 0 { 0 } else { 1 + count(n - 1) } };
+    pub func greet(): str { \"hello\" };
+    pub func echo(text: str): str { text };
+";
+
+fn vm_with(limits: VmLimits) -> LumaVM {
+    let mut vm = LumaVM::configure(limits);
+    vm.load(compile_module(SOURCE)).unwrap();
+    vm
+}
+
+fn count(vm: &mut LumaVM, n: i64) -> Result<BytecodeValue, RuntimeErrorKind> {
+    vm.call("count", vec![BytecodeValue::Int64(n)]).map_err(|err| err.kind)
+}
+
+#[test]
+fn fuel_bounds_executed_instructions() {
+    let mut vm = vm_with(VmLimits {
+        fuel: Some(500),
+        ..VmLimits::default()
+    });
+
+    assert!(matches!(count(&mut vm, 1_000), Err(RuntimeErrorKind::FuelExhausted { limit: 500 })));
+    assert_eq!(vm.executed_instructions(), 501);
+
+    // every call starts with a fresh budget
+    assert_eq!(count(&mut vm, 3).unwrap(), BytecodeValue::Int64(3));
+}
+
+#[test]
+fn call_depth_and_stack_size_are_configurable() {
+    let mut vm = vm_with(VmLimits {
+        max_call_depth: 10,
+        ..VmLimits::default()
+    });
+
+    assert!(matches!(count(&mut vm, 20), Err(RuntimeErrorKind::CallDepthExceeded { limit: 10 })));
+    assert_eq!(count(&mut vm, 5).unwrap(), BytecodeValue::Int64(5));
+
+    let mut vm = vm_with(VmLimits {
+        max_stack_size: 8,
+        ..VmLimits::default()
+    });
+
+    assert!(matches!(count(&mut vm, 20), Err(RuntimeErrorKind::StackOverflow { limit: 8 })));
+}
+
+#[test]
+fn heap_allocations_are_bounded() {
+    let mut vm = vm_with(VmLimits {
+        max_heap_size: Some(3),
+        ..VmLimits::default()
+    });
+
+    assert!(matches!(
+        vm.call("greet", Vec::new()).unwrap_err().kind,
+        RuntimeErrorKind::HeapLimitExceeded { limit: 3 }
+    ));
+    assert!(matches!(
+        vm.call("echo", vec![BytecodeValue::String("long".into())]).unwrap_err().kind,
+        RuntimeErrorKind::HeapLimitExceeded { .. }
+    ));
+}
+
+#[test]
+fn deadline_stops_long_running_calls() {
+    let mut vm = vm_with(VmLimits {
+        timeout: Some(Duration::ZERO),
+        ..VmLimits::default()
+    });
+
+    assert!(matches!(count(&mut vm, 1_000), Err(RuntimeErrorKind::TimeLimitExceeded { .. })));
+}
+
+#[test]
+fn vms_have_no_host_functions_by_default() {
+    assert!(LumaVM::new().native_signatures().is_empty());
+}
@@ -0,0 +1,41 @@
+use luma_compiler::{CompilerOptions, LumaCompiler, bytecode::ModuleBytecode};
+use luma_core::{CodeSource, SourceManager};
+
+use crate::{LumaVM, RuntimeError, Value};
+
+pub mod capabilities;
+pub mod convert;
+pub mod embedding;
+pub mod errors;
+pub mod execution;
+pub mod gc;
+pub mod globals;
+pub mod hoisting;
+pub mod limits;
+pub mod optionals;
+pub mod references;
+pub mod results;
+pub mod strings;
+
+/// Compiles the source, also returning the sources so diagnostics can be rendered
+pub fn compile_with_sources(src: &str) -> (SourceManager, ModuleBytecode) {
+    let result = LumaCompiler::configure(CompilerOptions::default()).compile([CodeSource::from(src)]);
+
+    assert!(!result.has_errors(), "compilation failed: {:#?}", result.diagnostics);
+
+    let module = result
+        .result
+        .and_then(|modules| modules.into_iter().next())
+        .expect("expected at least one module");
+
+    (result.sources, module)
+}
+
+pub fn compile_module(src: &str) -> ModuleBytecode {
+    compile_with_sources(src).1
+}
+
+/// Compiles the source and calls the function at `index` with the given arguments
+pub fn call(src: &str, index: usize, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    LumaVM::new().call_function(&compile_module(src), index, args)
+}
@@ -0,0 +1,73 @@
+use luma_compiler::bytecode::BytecodeValue;
+use pretty_assertions::assert_eq;
+
+use crate::{LumaVM, tests::{compile_module, embedding::compile_for}};
+
+const SOURCE: &str = "
+    pub func positive(x: i32): i32? { if x > 0 { x } else { none } };
+    pub func or_zero(x: i32?): i32 { if var value = x { value } else { 0 } };
+    pub func doubled(x: i32): i32? { var value = positive(x)?; value * 2 };
+    pub func is_none(x: i32?): bool { x == none };
+    pub func is_five(x: i32?): bool { x == 5 };
+    pub func describe(x: str?): str { if var text = x { \"some {text}\" } else { \"none\" } };
+";
+
+fn vm() -> LumaVM {
+    let mut vm = LumaVM::new();
+    vm.load(compile_module(SOURCE)).unwrap();
+    vm
+}
+
+#[test]
+fn optionals_hold_a_value_or_none() {
+    let mut vm = vm();
+
+    assert_eq!(vm.call("positive", vec![BytecodeValue::Int32(3)]).unwrap(), BytecodeValue::Int32(3));
+    assert_eq!(vm.call("positive", vec![BytecodeValue::Int32(-3)]).unwrap(), BytecodeValue::None);
+}
+
+#[test]
+fn if_var_unwraps_present_values() {
+    let mut vm = vm();
+
+    assert_eq!(vm.call("or_zero", vec![BytecodeValue::Int32(7)]).unwrap(), BytecodeValue::Int32(7));
+    assert_eq!(vm.call("or_zero", vec![BytecodeValue::None]).unwrap(), BytecodeValue::Int32(0));
+    assert_eq!(vm.call("describe", vec![BytecodeValue::String("luma".into())]).unwrap(), BytecodeValue::String("some luma".into()));
+    assert_eq!(vm.call("describe", vec![BytecodeValue::None]).unwrap(), BytecodeValue::String("none".into()));
+}
+
+#[test]
+fn question_mark_returns_none_early() {
+    let mut vm = vm();
+
+    assert_eq!(vm.call("doubled", vec![BytecodeValue::Int32(4)]).unwrap(), BytecodeValue::Int32(8));
+    assert_eq!(vm.call("doubled", vec![BytecodeValue::Int32(-4)]).unwrap(), BytecodeValue::None);
+}
+
+#[test]
+fn optionals_compare_with_none_and_values() {
+    let mut vm = vm();
+
+    assert_eq!(vm.call("is_none", vec![BytecodeValue::None]).unwrap(), BytecodeValue::Bool(true));
+    assert_eq!(vm.call("is_none", vec![BytecodeValue::Int32(0)]).unwrap(), BytecodeValue::Bool(false));
+    assert_eq!(vm.call("is_five", vec![BytecodeValue::Int32(5)]).unwrap(), BytecodeValue::Bool(true));
+    assert_eq!(vm.call("is_five", vec![BytecodeValue::None]).unwrap(), BytecodeValue::Bool(false));
+}
+
+#[test]
+fn host_functions_take_and_return_options() {
+    let mut vm = LumaVM::new();
+    vm.register_fn("find", |text: String, needle: char| text.find(needle).map(|index| index as i32));
+    vm.register_fn("or_default", |value: Option<i32>, default: i32| value.unwrap_or(default));
+
+    let module = compile_for(&vm, "
+        pub func position(text: str): i32 { or_default(find(text, 'u'), -1) };
+        pub func trailing(text: str): i32? { find(text, 'a')? + 1 };
+    ");
+    vm.load(module).unwrap();
+
+    assert_eq!(vm.call("position", vec![BytecodeValue::String("luma".into())]).unwrap(), BytecodeValue::Int32(1));
+    assert_eq!(vm.call("position", vec![BytecodeValue::String("lamp".into())]).unwrap(), BytecodeValue::Int32(-1));
+    assert_eq!(vm.call("trailing", vec![BytecodeValue::String("luma".into())]).unwrap(), BytecodeValue::Int32(4));
+    assert_eq!(vm.call("trailing", vec![BytecodeValue::String("lump".into())]).unwrap(), BytecodeValue::None);
+}
@@ -0,0 +1,36 @@
+use luma_compiler::bytecode::BytecodeValue;
+use pretty_assertions::assert_eq;
+
+use crate::{LumaVM, tests::compile_module};
+
+const SOURCE: &str = "
+    func inc(p: *i32) { *p = *p + 1; };
+    func inc_twice(p: *i32) { inc(p); inc(p); };
+    func swap(a: *i32, b: *i32) { var t = *a; *a = *b; *b = t; };
+    pub func counted(x: i32): i32 { var count = x; inc(&count); inc_twice(&count); count };
+    pub func swapped(x: i32, y: i32): i32 { swap(&x, &y); x * 10 + y };
+    pub func local(x: i32): i32 { var y = x; var p = &y; *p = *p * 2; y };
+    pub func chained(x: i32): i32 { var y = 0; var p = &y; var z = *p = x; y + z };
+";
+
+fn vm() -> LumaVM {
+    let mut vm = LumaVM::new();
+    vm.load(compile_module(SOURCE)).unwrap();
+    vm
+}
+
+#[test]
+fn callees_mutate_variables_through_references() {
+    let mut vm = vm();
+
+    assert_eq!(vm.call("counted", vec![BytecodeValue::Int32(1)]).unwrap(), BytecodeValue::Int32(4));
+    assert_eq!(vm.call("swapped", vec![BytecodeValue::Int32(1), BytecodeValue::Int32(2)]).unwrap(), BytecodeValue::Int32(21));
+}
+
+#[test]
+fn references_read_and_write_locals() {
+    let mut vm = vm();
+
+    assert_eq!(vm.call("local", vec![BytecodeValue::Int32(4)]).unwrap(), BytecodeValue::Int32(8));
+    assert_eq!(vm.call("chained", vec![BytecodeValue::Int32(3)]).unwrap(), BytecodeValue::Int32(6));
+}
@@ -0,0 +1,111 @@
+use luma_compiler::bytecode::BytecodeValue;
+use pretty_assertions::assert_eq;
+
+use crate::{LumaResult, LumaVM, RuntimeErrorKind, tests::{compile_module, embedding::compile_for}};
+
+const SOURCE: &str = "
+    pub func checked(x: i32): i32!str { if x > 0 { ok(x) } else { err(\"not positive\") } };
+    pub func doubled(x: i32): i32!str { var value = checked(x)?; ok(value * 2) };
+    pub func or_zero(x: i32): i32 { checked(x) catch e { 0 } };
+    func named(x: i32): str!str { if x > 0 { ok(\"fine\") } else { err(\"not positive\") } };
+    pub func reason(x: i32): str { named(x) catch e { e } };
+    pub func divide(a: i32, b: i32): i32!str { try a / b };
+    pub func safe_divide(a: i32, b: i32): i32 { try a / b catch e { -1 } };
+    pub func is_ok(x: i32!str): bool { x == ok(1) };
+";
+
+fn vm() -> LumaVM {
+    let mut vm = LumaVM::new();
+    vm.load(compile_module(SOURCE)).unwrap();
+    vm
+}
+
+fn err(message: &str) -> BytecodeValue {
+    BytecodeValue::Err(Box::new(BytecodeValue::String(message.into())))
+}
+
+#[test]
+fn results_hold_a_value_or_an_error() {
+    let mut vm = vm();
+
+    assert_eq!(vm.call("checked", vec![BytecodeValue::Int32(3)]).unwrap(), BytecodeValue::Int32(3));
+    assert_eq!(vm.call("checked", vec![BytecodeValue::Int32(-3)]).unwrap(), err("not positive"));
+}
+
+#[test]
+fn question_mark_returns_errors_early() {
+    let mut vm = vm();
+
+    assert_eq!(vm.call("doubled", vec![BytecodeValue::Int32(4)]).unwrap(), BytecodeValue::Int32(8));
+    assert_eq!(vm.call("doubled", vec![BytecodeValue::Int32(-4)]).unwrap(), err("not positive"));
+}
+
+#[test]
+fn catch_handles_errors() {
+    let mut vm = vm();
+
+    assert_eq!(vm.call("or_zero", vec![BytecodeValue::Int32(5)]).unwrap(), BytecodeValue::Int32(5));
+    assert_eq!(vm.call("or_zero", vec![BytecodeValue::Int32(-5)]).unwrap(), BytecodeValue::Int32(0));
+    assert_eq!(vm.call("reason", vec![BytecodeValue::Int32(5)]).unwrap(), BytecodeValue::String("fine".into()));
+    assert_eq!(vm.call("reason", vec![BytecodeValue::Int32(-5)]).unwrap(), BytecodeValue::String("not positive".into()));
+}
+
+#[test]
+fn try_turns_runtime_errors_into_results() {
+    let mut vm = vm();
+
+    assert_eq!(vm.call("divide", vec![BytecodeValue::Int32(6), BytecodeValue::Int32(3)]).unwrap(), BytecodeValue::Int32(2));
+    assert_eq!(vm.call("divide", vec![BytecodeValue::Int32(6), BytecodeValue::Int32(0)]).unwrap(), err("attempted to divide by zero"));
+    assert_eq!(vm.call("safe_divide", vec![BytecodeValue::Int32(1), BytecodeValue::Int32(0)]).unwrap(), BytecodeValue::Int32(-1));
+}
+
+#[test]
+fn try_unwinds_calls_and_keeps_the_stack_intact() {
+    let mut vm = LumaVM::new();
+    vm.load(compile_module("
+        func inner(a: i32): i32 { var b = 10; b / a };
+        func middle(a: i32): i32 { 1 + inner(a) };
+        pub func outer(a: i32): i32 { var kept = 100; kept + (try middle(a) catch e { 0 }) + kept };
+    "))
+    .unwrap();
+
+    assert_eq!(vm.call("outer", vec![BytecodeValue::Int32(5)]).unwrap(), BytecodeValue::Int32(203));
+    assert_eq!(vm.call("outer", vec![BytecodeValue::Int32(0)]).unwrap(), BytecodeValue::Int32(200));
+}
+
+#[test]
+fn errors_outside_of_try_still_abort() {
+    let mut vm = LumaVM::new();
+    vm.load(compile_module("pub func divide(a: i32, b: i32): i32 { a / b };")).unwrap();
+
+    let error = vm.call("divide", vec![BytecodeValue::Int32(1), BytecodeValue::Int32(0)]).unwrap_err();
+    assert!(matches!(error.kind, RuntimeErrorKind::DivisionByZero));
+}
+
+#[test]
+fn results_compare_by_their_contents() {
+    let mut vm = vm();
+
+    assert_eq!(vm.call("is_ok", vec![BytecodeValue::Int32(1)]).unwrap(), BytecodeValue::Bool(true));
+    assert_eq!(vm.call("is_ok", vec![BytecodeValue::Int32(2)]).unwrap(), BytecodeValue::Bool(false));
+    assert_eq!(vm.call("is_ok", vec![err("1")]).unwrap(), BytecodeValue::Bool(false));
+}
+
+#[test]
+fn host_functions_hand_errors_to_scripts() {
+    let mut vm = LumaVM::new();
+    vm.register_fn("parse", |text: String| -> LumaResult<i32, String> {
+        text.parse::<i32>().map_err(|err| err.to_string()).into()
+    });
+    vm.register_fn("fail", |text: String| -> Result<i32, String> { Err(text) });
+
+    let module = compile_for(&vm, "
+        pub func parsed(text: str): i32!str { ok(parse(text)? + 1) };
+        pub func guarded(text: str): i32 { try fail(text) catch e { len(e) } };
+    ");
+    vm.load(module).unwrap();
+
+    assert_eq!(vm.call("parsed", vec![BytecodeValue::String("41".into())]).unwrap(), BytecodeValue::Int32(42));
+    assert_eq!(vm.call("parsed", vec![BytecodeValue::String("x".into())]).unwrap(), err("invalid digit found in string"));
+    assert_eq!(vm.call("guarded", vec![BytecodeValue::String("boom".into())]).unwrap(), BytecodeValue::Int32(4));
+}
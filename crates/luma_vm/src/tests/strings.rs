@@ -0,0 +1,99 @@
+use luma_compiler::bytecode::BytecodeValue;
+use pretty_assertions::assert_eq;
+
+use crate::{LumaVM, RuntimeErrorKind, tests::compile_module};
+
+const SOURCE: &str = "
+    pub func greet(name: str): str { \"hello \" + name };
+    pub func less(a: str, b: str): bool { a < b };
+    pub func same(a: str, b: str): bool { a == b };
+    pub func length(text: str): i32 { len(text) };
+    pub func middle(text: str): str { slice(text, 1, len(text) - 1) };
+    pub func first(text: str): char { char_at(text, 0) };
+    pub func letters(text: str): i32 { len(to_string(chars(text))) };
+    pub func describe(n: i32, f: f64, c: char): str { to_string(n) + \" \" + to_string(f) + \" \" + to_string(c) };
+    pub func increment(text: str): i64 { parse_int(text) + 1 };
+    pub func half(text: str): f64 { parse_float(text) / 2.0 };
+    pub func introduce(name: str, age: i32): str { \"hello {name}, you are {age} years old\" };
+    pub func pair(a: i32, b: bool): str { \"{b} {a + 1}{'!'}\" };
+";
+
+fn string(text: &str) -> BytecodeValue {
+    BytecodeValue::String(text.into())
+}
+
+fn vm() -> LumaVM {
+    let mut vm = LumaVM::new();
+    vm.load(compile_module(SOURCE)).unwrap();
+    vm
+}
+
+#[test]
+fn strings_concatenate_and_compare() {
+    let mut vm = vm();
+
+    assert_eq!(vm.call("greet", vec![string("luma")]).unwrap(), string("hello luma"));
+    assert_eq!(vm.call("less", vec![string("abc"), string("abd")]).unwrap(), BytecodeValue::Bool(true));
+    assert_eq!(vm.call("same", vec![string("ab"), string("ab")]).unwrap(), BytecodeValue::Bool(true));
+    assert_eq!(vm.call("same", vec![string("ab"), string("ba")]).unwrap(), BytecodeValue::Bool(false));
+}
+
+#[test]
+fn strings_are_indexed_by_char() {
+    let mut vm = vm();
+
+    assert_eq!(vm.call("length", vec![string("héllo")]).unwrap(), BytecodeValue::Int32(5));
+    assert_eq!(vm.call("middle", vec![string("«ab»")]).unwrap(), string("ab"));
+    assert_eq!(vm.call("first", vec![string("éa")]).unwrap(), BytecodeValue::Char('é'));
+    assert_eq!(vm.call("letters", vec![string("ab")]).unwrap(), BytecodeValue::Int32(6));
+
+    assert!(matches!(
+        vm.call("first", vec![string("")]).unwrap_err().kind,
+        RuntimeErrorKind::IndexOutOfBounds { index: 0, len: 0 }
+    ));
+    assert!(matches!(
+        vm.call("middle", vec![string("a")]).unwrap_err().kind,
+        RuntimeErrorKind::InvalidRange { start: 1, end: 0 }
+    ));
+}
+
+#[test]
+fn strings_convert_to_and_from_numbers() {
+    let mut vm = vm();
+
+    assert_eq!(
+        vm.call("describe", vec![BytecodeValue::Int32(-3), BytecodeValue::Float64(0.5), BytecodeValue::Char('x')])
+            .unwrap(),
+        string("-3 0.5 x")
+    );
+    assert_eq!(vm.call("increment", vec![string(" 41 ")]).unwrap(), BytecodeValue::Int64(42));
+    assert_eq!(vm.call("half", vec![string("3")]).unwrap(), BytecodeValue::Float64(1.5));
+
+    assert!(matches!(
+        vm.call("increment", vec![string("four")]).unwrap_err().kind,
+        RuntimeErrorKind::InvalidNumber { text } if text == "four"
+    ));
+}
+
+#[test]
+fn interpolation_joins_displayed_values() {
+    let mut vm = vm();
+
+    assert_eq!(
+        vm.call("introduce", vec![string("luma"), BytecodeValue::Int32(3)]).unwrap(),
+        string("hello luma, you are 3 years old")
+    );
+    assert_eq!(
+        vm.call("pair", vec![BytecodeValue::Int32(1), BytecodeValue::Bool(true)]).unwrap(),
+        string("true 2!")
+    );
+}
+
+#[test]
+fn equal_strings_share_one_interned_object() {
+    let mut vm = vm();
+
+    vm.call("same", vec![string("twice"), string("twice")]).unwrap();
+
+    assert_eq!(vm.gc_stats().allocated_objects, 1);
+}
@@ -0,0 +1,194 @@
+use std::{cmp::Ordering, fmt::Display};
+
+use crate::{ObjectRef, RuntimeErrorKind};
+
+/// A value living on the VM's stack
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Value {
+    UInt8(u8),
+    UInt16(u16),
+    UInt32(u32),
+    UInt64(u64),
+    Int8(i8),
+    Int16(i16),
+    Int32(i32),
+    Int64(i64),
+    Float32(f32),
+    Float64(f64),
+    Bool(bool),
+    Char(char),
+    Unit,
+    /// the absent value of an optional, present values are stored as themselves
+    None,
+    /// strings and composite values, which live on the VM's [`Heap`](crate::Heap)
+    Object(ObjectRef),
+    /// a reference to a variable, the index of its slot on the VM's stack
+    Ref(usize),
+    /// a reference to a global variable, the index of its slot in the module's globals
+    GlobalRef(usize),
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::UInt8(v) => write!(f, "{v}"),
+            Value::UInt16(v) => write!(f, "{v}"),
+            Value::UInt32(v) => write!(f, "{v}"),
+            Value::UInt64(v) => write!(f, "{v}"),
+            Value::Int8(v) => write!(f, "{v}"),
+            Value::Int16(v) => write!(f, "{v}"),
+            Value::Int32(v) => write!(f, "{v}"),
+            Value::Int64(v) => write!(f, "{v}"),
+            Value::Float32(v) => write!(f, "{v}"),
+            Value::Float64(v) => write!(f, "{v}"),
+            Value::Bool(v) => write!(f, "{v}"),
+            Value::Char(v) => write!(f, "{v}"),
+            Value::Unit => write!(f, "()"),
+            Value::None => write!(f, "none"),
+            Value::Object(object) => write!(f, "<object #{}>", object.index()),
+            Value::Ref(index) => write!(f, "<reference #{index}>"),
+            Value::GlobalRef(index) => write!(f, "<global reference #{index}>"),
+        }
+    }
+}
+
+/// Applies an operation to two values of the same integer type, `$float` handles the float types if given
+macro_rules! numeric_op {
+    ($name:literal, $left:expr, $right:expr, |$l:ident, $r:ident| $int:expr $(, float |$fl:ident, $fr:ident| $float:expr)?) => {
+        match ($left, $right) {
+            (Value::UInt8($l), Value::UInt8($r)) => $int.map(Value::UInt8),
+            (Value::UInt16($l), Value::UInt16($r)) => $int.map(Value::UInt16),
+            (Value::UInt32($l), Value::UInt32($r)) => $int.map(Value::UInt32),
+            (Value::UInt64($l), Value::UInt64($r)) => $int.map(Value::UInt64),
+            (Value::Int8($l), Value::Int8($r)) => $int.map(Value::Int8),
+            (Value::Int16($l), Value::Int16($r)) => $int.map(Value::Int16),
+            (Value::Int32($l), Value::Int32($r)) => $int.map(Value::Int32),
+            (Value::Int64($l), Value::Int64($r)) => $int.map(Value::Int64),
+            $(
+                (Value::Float32($fl), Value::Float32($fr)) => Ok(Value::Float32($float)),
+                (Value::Float64($fl), Value::Float64($fr)) => Ok(Value::Float64($float)),
+            )?
+            _ => Err(RuntimeErrorKind::TypeMismatch { operation: $name }),
+        }
+    };
+}
+
+/// Turns the result of a checked integer operation into a runtime error
+#[inline]
+fn checked<T>(result: Option<T>) -> Result<T, RuntimeErrorKind> {
+    result.ok_or(RuntimeErrorKind::IntegerOverflow)
+}
+
+/// Like [`checked`] but reports a zero divisor as such rather than as an overflow
+#[inline]
+fn checked_div<T: Default + PartialEq>(divisor: T, result: Option<T>) -> Result<T, RuntimeErrorKind> {
+    if divisor == T::default() {
+        return Err(RuntimeErrorKind::DivisionByZero);
+    }
+
+    checked(result)
+}
+
+impl Value {
+    pub fn checked_add(self, other: Value) -> Result<Value, RuntimeErrorKind> {
+        numeric_op!("addition", self, other, |l, r| checked(l.checked_add(r)), float |l, r| l + r)
+    }
+
+    pub fn checked_sub(self, other: Value) -> Result<Value, RuntimeErrorKind> {
+        numeric_op!("subtraction", self, other, |l, r| checked(l.checked_sub(r)), float |l, r| l - r)
+    }
+
+    pub fn checked_mul(self, other: Value) -> Result<Value, RuntimeErrorKind> {
+        numeric_op!("multiplication", self, other, |l, r| checked(l.checked_mul(r)), float |l, r| l * r)
+    }
+
+    pub fn checked_div(self, other: Value) -> Result<Value, RuntimeErrorKind> {
+        numeric_op!("division", self, other, |l, r| checked_div(r, l.checked_div(r)), float |l, r| l / r)
+    }
+
+    pub fn checked_rem(self, other: Value) -> Result<Value, RuntimeErrorKind> {
+        numeric_op!("modulo", self, other, |l, r| checked_div(r, l.checked_rem(r)), float |l, r| l % r)
+    }
+
+    pub fn bit_and(self, other: Value) -> Result<Value, RuntimeErrorKind> {
+        numeric_op!("bitwise and", self, other, |l, r| Ok::<_, RuntimeErrorKind>(l & r))
+    }
+
+    pub fn bit_or(self, other: Value) -> Result<Value, RuntimeErrorKind> {
+        numeric_op!("bitwise or", self, other, |l, r| Ok::<_, RuntimeErrorKind>(l | r))
+    }
+
+    pub fn bit_xor(self, other: Value) -> Result<Value, RuntimeErrorKind> {
+        numeric_op!("bitwise xor", self, other, |l, r| Ok::<_, RuntimeErrorKind>(l ^ r))
+    }
+
+    pub fn shift_left(self, other: Value) -> Result<Value, RuntimeErrorKind> {
+        numeric_op!("shift left", self, other, |l, r| {
+            checked(u32::try_from(r as i128).ok().and_then(|r| l.checked_shl(r)))
+        })
+    }
+
+    pub fn shift_right(self, other: Value) -> Result<Value, RuntimeErrorKind> {
+        numeric_op!("shift right", self, other, |l, r| {
+            checked(u32::try_from(r as i128).ok().and_then(|r| l.checked_shr(r)))
+        })
+    }
+
+    pub fn negate(self) -> Result<Value, RuntimeErrorKind> {
+        match self {
+            Value::Int8(v) => checked(v.checked_neg()).map(Value::Int8),
+            Value::Int16(v) => checked(v.checked_neg()).map(Value::Int16),
+            Value::Int32(v) => checked(v.checked_neg()).map(Value::Int32),
+            Value::Int64(v) => checked(v.checked_neg()).map(Value::Int64),
+            Value::Float32(v) => Ok(Value::Float32(-v)),
+            Value::Float64(v) => Ok(Value::Float64(-v)),
+            _ => Err(RuntimeErrorKind::TypeMismatch { operation: "negation" }),
+        }
+    }
+
+    pub fn as_bool(&self, operation: &'static str) -> Result<bool, RuntimeErrorKind> {
+        match self {
+            Value::Bool(value) => Ok(*value),
+            _ => Err(RuntimeErrorKind::TypeMismatch { operation }),
+        }
+    }
+
+    /// Whether two values of the same type or a value and `none` are equal, `None` for mismatched values.
+    ///
+    /// Unordered floats are unequal rather than mismatched, so `NaN != NaN` holds
+    pub fn equal(&self, other: &Value) -> Option<bool> {
+        match (self, other) {
+            (Value::Float32(l), Value::Float32(r)) => Some(l == r),
+            (Value::Float64(l), Value::Float64(r)) => Some(l == r),
+            _ => self.compare(other).map(Ordering::is_eq),
+        }
+    }
+
+    /// Orders two values of the same type or a value and `none`, `None` for mismatched or unordered values.
+    ///
+    /// Objects are compared by [`Heap::compare`](crate::Heap::compare)
+    pub fn compare(&self, other: &Value) -> Option<Ordering> {
+        match (self, other) {
+            (Value::UInt8(l), Value::UInt8(r)) => l.partial_cmp(r),
+            (Value::UInt16(l), Value::UInt16(r)) => l.partial_cmp(r),
+            (Value::UInt32(l), Value::UInt32(r)) => l.partial_cmp(r),
+            (Value::UInt64(l), Value::UInt64(r)) => l.partial_cmp(r),
+            (Value::Int8(l), Value::Int8(r)) => l.partial_cmp(r),
+            (Value::Int16(l), Value::Int16(r)) => l.partial_cmp(r),
+            (Value::Int32(l), Value::Int32(r)) => l.partial_cmp(r),
+            (Value::Int64(l), Value::Int64(r)) => l.partial_cmp(r),
+            (Value::Float32(l), Value::Float32(r)) => l.partial_cmp(r),
+            (Value::Float64(l), Value::Float64(r)) => l.partial_cmp(r),
+            (Value::Bool(l), Value::Bool(r)) => l.partial_cmp(r),
+            (Value::Char(l), Value::Char(r)) => l.partial_cmp(r),
+            (Value::Unit, Value::Unit) => Some(Ordering::Equal),
+            // none is only equal to itself and ordered before any present value
+            (Value::None, Value::None) => Some(Ordering::Equal),
+            (Value::None, _) => Some(Ordering::Less),
+            (_, Value::None) => Some(Ordering::Greater),
+            // references are equal if they refer to the same variable
+            (Value::Ref(l), Value::Ref(r)) | (Value::GlobalRef(l), Value::GlobalRef(r)) => l.partial_cmp(r),
+            _ => None,
+        }
+    }
+}
@@ -1,11 +1,805 @@
-/// Virtual Machine handle for Luma
+use std::{cmp::Ordering, rc::Rc};
+
+use luma_compiler::{
+    Intrinsic, NativeSignature,
+    bytecode::{BytecodeValue, ModuleBytecode, op},
+};
+
+use crate::{
+    Capabilities, GcStats, Heap, HostFunction, IntoHostFunction, LumaStruct, RuntimeError, RuntimeErrorKind, StackFrame, Value, VmLimits,
+    capabilities,
+    intrinsics::call_intrinsic,
+    limits::Budget,
+};
+
+/// Virtual Machine handle for Luma.
+///
+/// A VM starts without host functions or [`Capabilities`], scripts can only call the natives registered with it
 pub struct LumaVM {
-    
+    stack: Vec<Value>,
+
+    /// module-level variables of the module being run, sized by its init chunk
+    globals: Vec<Value>,
+    frames: Vec<CallFrame>,
+    handlers: Vec<Handler>,
+    natives: Vec<HostFunction>,
+    capabilities: Capabilities,
+    heap: Heap,
+    limits: VmLimits,
+    budget: Budget,
+
+    /// the module loaded with [`Self::load`] and the registered natives its imports resolved to
+    module: Option<(Rc<ModuleBytecode>, Rc<[usize]>)>,
+}
+
+/// A function being executed
+struct CallFrame {
+    /// index in the module's function table
+    function: usize,
+
+    /// offset of the instruction being executed, the call instruction while a callee runs
+    offset: usize,
+
+    /// stack index of the function's first local
+    base: usize,
+
+    /// offset the caller continues at once the function returns
+    return_offset: usize,
+}
+
+/// A `try` catching the recoverable errors raised while its value is evaluated
+struct Handler {
+    /// amount of active frames when it was installed, its function's frame being the last one
+    frames: usize,
+
+    /// stack height to restore before pushing the error
+    stack: usize,
+
+    /// offset of the instruction following the `try`
+    target: usize,
 }
 
 impl LumaVM {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        LumaVM {}
+        Self::configure(VmLimits::default())
+    }
+
+    pub fn configure(limits: VmLimits) -> Self {
+        LumaVM {
+            stack: Vec::new(),
+            globals: Vec::new(),
+            frames: Vec::new(),
+            handlers: Vec::new(),
+            natives: Vec::new(),
+            capabilities: Capabilities::NONE,
+            heap: Heap::new(),
+            limits,
+            budget: Budget::default(),
+            module: None,
+        }
+    }
+
+    pub fn limits(&self) -> &VmLimits {
+        &self.limits
+    }
+
+    /// Objects referenced by the values the VM returned, until the next call into the VM
+    pub fn heap(&self) -> &Heap {
+        &self.heap
+    }
+
+    pub fn gc_stats(&self) -> GcStats {
+        self.heap.stats()
+    }
+
+    /// Frees every object, values returned earlier by [`Self::call_function`] and [`Self::run`] dangle afterwards
+    pub fn collect_garbage(&mut self) {
+        self.heap.collect(self.stack.iter().chain(&self.globals));
+    }
+
+    /// Collects at every safepoint instead of once the heap outgrows its threshold, for tests looking for values the collector doesn't see
+    pub fn set_gc_stress(&mut self, stress: bool) {
+        self.heap.set_stress(stress);
+    }
+
+    /// Instructions executed by the last call into the VM
+    pub fn executed_instructions(&self) -> u64 {
+        self.budget.executed
+    }
+
+    /// Registers a host function, replacing a previously registered one with the same name.
+    ///
+    /// Modules calling it have to be compiled with its signature, see [`Self::native_signatures`]
+    pub fn register_function(&mut self, function: HostFunction) {
+        match self
+            .natives
+            .iter_mut()
+            .find(|native| native.signature.name == function.signature.name)
+        {
+            Some(native) => *native = function,
+            None => self.natives.push(function),
+        }
+    }
+
+    /// Registers a Rust closure as a host function, its signature is derived from the argument and return types
+    pub fn register_fn<Args>(&mut self, name: impl Into<String>, func: impl IntoHostFunction<Args>) {
+        self.register_function(HostFunction::from_fn(name, func));
+    }
+
+    pub fn capabilities(&self) -> Capabilities {
+        self.capabilities
+    }
+
+    /// Registers the natives of the capabilities that weren't granted yet, granted capabilities can't be revoked
+    pub fn grant(&mut self, capabilities: Capabilities) {
+        let granted = Capabilities {
+            fs: capabilities.fs && !self.capabilities.fs,
+            env: capabilities.env && !self.capabilities.env,
+            time: capabilities.time && !self.capabilities.time,
+        };
+
+        capabilities::register(self, granted);
+
+        self.capabilities = Capabilities {
+            fs: self.capabilities.fs || capabilities.fs,
+            env: self.capabilities.env || capabilities.env,
+            time: self.capabilities.time || capabilities.time,
+        };
+    }
+
+    /// Signatures of the registered host functions, to be declared to the compiler
+    pub fn native_signatures(&self) -> Vec<NativeSignature> {
+        self.natives.iter().map(|native| native.signature.clone()).collect()
+    }
+
+    /// Loads the module and runs its init chunk, its exported functions can be called with [`Self::call`] afterwards
+    pub fn load(&mut self, module: ModuleBytecode) -> Result<(), RuntimeError> {
+        let imports = self.resolve_imports(&module)?;
+        let (module, imports) = (Rc::new(module), Rc::<[usize]>::from(imports));
+
+        self.module = Some((module.clone(), imports.clone()));
+        self.globals.clear();
+        self.invoke(&module, &imports, 0, Vec::new())?;
+
+        Ok(())
+    }
+
+    /// Calls a function exported by the loaded module
+    pub fn call(&mut self, name: &str, args: Vec<BytecodeValue>) -> Result<BytecodeValue, RuntimeError> {
+        let Some((module, imports)) = self.module.clone() else {
+            return Err(self.error(None, RuntimeErrorKind::NoModuleLoaded));
+        };
+
+        let Some((index, _)) = module.get_export(name) else {
+            let kind = RuntimeErrorKind::ExportNotFound { name: name.to_string() };
+            return Err(self.error(Some(&module), kind));
+        };
+
+        // allocating never collects, so the imported arguments stay alive until they are on the stack
+        let args = args.iter().map(|arg| self.heap.import(arg)).collect();
+        let result = self.invoke(&module, &imports, index, args)?;
+
+        Ok(self.heap.export(&result))
+    }
+
+    /// Reads a global exported by the loaded module
+    pub fn global(&self, name: &str) -> Result<BytecodeValue, RuntimeError> {
+        let (module, index) = self.global_export(name)?;
+        let value = self.globals.get(index).ok_or_else(|| self.error(Some(module), RuntimeErrorKind::InvalidGlobal { slot: index }))?;
+
+        Ok(self.heap.export(value))
+    }
+
+    /// Writes a global exported by the loaded module, the value has to be of the global's type
+    pub fn set_global(&mut self, name: &str, value: BytecodeValue) -> Result<(), RuntimeError> {
+        let (module, index) = self.global_export(name)?;
+        let ty = &module.globals[index].ty;
+
+        if !value.matches_type(ty) {
+            let kind = RuntimeErrorKind::ConversionFailed {
+                expected: ty.to_string(),
+                found: value.type_kind().to_string(),
+            };
+            return Err(self.error(Some(module), kind));
+        }
+
+        if index >= self.globals.len() {
+            return Err(self.error(Some(module), RuntimeErrorKind::InvalidGlobal { slot: index }));
+        }
+
+        let value = self.heap.import(&value);
+        self.globals[index] = value;
+
+        Ok(())
+    }
+
+    /// Looks up an exported global of the loaded module
+    fn global_export(&self, name: &str) -> Result<(&ModuleBytecode, usize), RuntimeError> {
+        let Some((module, _)) = &self.module else {
+            return Err(self.error(None, RuntimeErrorKind::NoModuleLoaded));
+        };
+
+        match module.get_global_export(name) {
+            Some((index, _)) => Ok((module, index)),
+            None => Err(self.error(Some(module), RuntimeErrorKind::GlobalNotFound { name: name.to_string() })),
+        }
+    }
+
+    /// Checks the host struct against the layout of the loaded module's struct with the same name
+    pub fn check_struct<T: LumaStruct>(&self) -> Result<(), RuntimeError> {
+        let Some((module, _)) = &self.module else {
+            return Err(self.error(None, RuntimeErrorKind::NoModuleLoaded));
+        };
+
+        let Some(layout) = module.get_struct(T::NAME) else {
+            let kind = RuntimeErrorKind::StructNotFound { name: T::NAME.to_string() };
+            return Err(self.error(Some(module), kind));
+        };
+
+        T::check_layout(layout).map_err(|kind| self.error(Some(module), kind))
+    }
+
+    /// Runs the module's init chunk, starting over with fresh globals
+    pub fn run(&mut self, module: &ModuleBytecode) -> Result<Value, RuntimeError> {
+        self.globals.clear();
+        self.call_function(module, 0, Vec::new())
+    }
+
+    /// Calls the function at `index` in the module's function table, `0` being the init chunk
+    pub fn call_function(&mut self, module: &ModuleBytecode, index: usize, args: Vec<Value>) -> Result<Value, RuntimeError> {
+        let imports = self.resolve_imports(module)?;
+        self.invoke(module, &imports, index, args)
+    }
+
+    /// Maps the module's native imports to the index of the registered host function
+    fn resolve_imports(&self, module: &ModuleBytecode) -> Result<Vec<usize>, RuntimeError> {
+        module
+            .natives
+            .iter()
+            .map(|name| {
+                self.natives
+                    .iter()
+                    .position(|native| &native.signature.name == name)
+                    .ok_or_else(|| self.error(Some(module), RuntimeErrorKind::UnknownNative { name: name.clone() }))
+            })
+            .collect()
+    }
+
+    fn invoke(&mut self, module: &ModuleBytecode, imports: &[usize], index: usize, args: Vec<Value>) -> Result<Value, RuntimeError> {
+        let Some(func) = module.functions.get(index) else {
+            return Err(self.error(Some(module), RuntimeErrorKind::FunctionNotFound { index }));
+        };
+
+        if args.len() != func.arity {
+            let kind = RuntimeErrorKind::ArityMismatch {
+                expected: func.arity,
+                found: args.len(),
+            };
+
+            return Err(self.error(Some(module), kind));
+        }
+
+        let base = self.stack.len();
+        let depth = self.frames.len();
+        let handlers = self.handlers.len();
+
+        self.budget = Budget::new(&self.limits);
+        self.stack.extend(args);
+
+        // globals are unit until their declaration runs, the analyzer rejects reading them before that
+        if self.globals.len() < module.globals.len() {
+            self.globals.resize(module.globals.len(), Value::Unit);
+        }
+
+        if let Err(kind) = self.safepoint() {
+            self.stack.truncate(base);
+            return Err(self.error(Some(module), kind));
+        }
+
+        let result = self
+            .enter(module, index, base, 0)
+            .and_then(|()| self.execute(module, imports, depth))
+            .map_err(|kind| self.error(Some(module), kind));
+
+        self.frames.truncate(depth);
+        self.handlers.truncate(handlers);
+        self.stack.truncate(base);
+
+        result
+    }
+
+    /// Collects garbage once it is due and enforces the heap limit, every value in use has to be on the stack.
+    ///
+    /// The stack holds the locals of every frame, the globals are the only other roots
+    fn safepoint(&mut self) -> Result<(), RuntimeErrorKind> {
+        if self.heap.should_collect() {
+            self.heap.collect(self.stack.iter().chain(&self.globals));
+        }
+
+        let Some(limit) = self.limits.max_heap_size else {
+            return Ok(());
+        };
+
+        // the garbage may be all that is over the limit
+        if self.heap.bytes() > limit {
+            self.heap.collect(self.stack.iter().chain(&self.globals));
+        }
+
+        if self.heap.bytes() > limit {
+            return Err(RuntimeErrorKind::HeapLimitExceeded { limit });
+        }
+
+        Ok(())
+    }
+
+    /// Pushes the frame of a function whose arguments start at `base` on the stack
+    fn enter(&mut self, module: &ModuleBytecode, function: usize, base: usize, return_offset: usize) -> Result<(), RuntimeErrorKind> {
+        let func = &module.functions[function];
+        let locals = func.code.max_locals.max(func.arity);
+
+        if base + locals > self.limits.max_stack_size {
+            return Err(RuntimeErrorKind::StackOverflow { limit: self.limits.max_stack_size });
+        }
+
+        if self.frames.len() >= self.limits.max_call_depth {
+            return Err(RuntimeErrorKind::CallDepthExceeded { limit: self.limits.max_call_depth });
+        }
+
+        self.stack.resize(base + locals, Value::Unit);
+        self.frames.push(CallFrame {
+            function,
+            offset: 0,
+            base,
+            return_offset,
+        });
+
+        Ok(())
+    }
+
+    /// Attaches the stack trace of the active functions to the error
+    fn error(&self, module: Option<&ModuleBytecode>, kind: RuntimeErrorKind) -> RuntimeError {
+        let frames = module.into_iter().flat_map(|module| {
+            self.frames.iter().rev().map(|frame| {
+                let func = &module.functions[frame.function];
+
+                StackFrame {
+                    function: func.name.clone(),
+                    span: func.code.debug.lines.span_at(frame.offset as u32),
+                    repeated: 0,
+                }
+            })
+        });
+
+        RuntimeError::new(kind, frames)
+    }
+
+    /// Runs the innermost frame until the frames above `depth` returned, with the offset of a failing
+    /// instruction recorded in its frame.
+    ///
+    /// Recoverable errors unwind to the innermost handler installed above `depth`, which continues with the error
+    fn execute(&mut self, module: &ModuleBytecode, imports: &[usize], depth: usize) -> Result<Value, RuntimeErrorKind> {
+        let mut ip = 0;
+
+        loop {
+            let mut offset = ip;
+            let result = self.execute_frames(module, imports, depth, ip, &mut offset);
+
+            if let Err(kind) = &result
+                && kind.is_recoverable()
+                && self.handlers.last().is_some_and(|handler| handler.frames > depth)
+            {
+                let handler = self.handlers.pop().expect("a handler was found");
+                self.frames.truncate(handler.frames);
+                self.stack.truncate(handler.stack);
+
+                let message = self.heap.allocate_string(kind.message());
+                let error = self.heap.allocate_err(message);
+                self.stack.push(error);
+                ip = handler.target;
+
+                self.safepoint()?;
+                continue;
+            }
+
+            if result.is_err()
+                && let Some(frame) = self.frames.last_mut()
+            {
+                frame.offset = offset;
+            }
+
+            return result;
+        }
+    }
+
+    /// Runs the innermost frame, starting at `ip`, and the functions it calls until the frames above `depth` returned,
+    /// `offset` is kept at the instruction being executed
+    fn execute_frames(
+        &mut self,
+        module: &ModuleBytecode,
+        imports: &[usize],
+        depth: usize,
+        mut ip: usize,
+        offset: &mut usize,
+    ) -> Result<Value, RuntimeErrorKind> {
+
+        'frames: loop {
+            let frame = self.frames.last().expect("a frame is active while executing");
+            let func = &module.functions[frame.function];
+            let base = frame.base;
+
+            let code = func.code.bytes();
+            let locals = base..base + func.code.max_locals.max(func.arity);
+
+            loop {
+                *offset = ip;
+                let offset = ip;
+                self.budget.tick(&self.limits)?;
+
+                let Some(&byte) = code.get(ip) else {
+                    return Err(RuntimeErrorKind::InvalidInstruction { offset });
+                };
+
+                ip += 1;
+
+                // the wide form of an instruction is its narrow byte with the high bit set
+                let wide = byte & 0x80 != 0;
+
+                macro_rules! operand {
+                    () => {
+                        read_operand(code, ip, wide).ok_or(RuntimeErrorKind::InvalidInstruction { offset })?
+                    };
+                }
+
+                macro_rules! pop {
+                    () => {
+                        if self.stack.len() > locals.end {
+                            self.stack.pop().expect("stack is longer than the locals")
+                        } else {
+                            return Err(RuntimeErrorKind::StackUnderflow { offset });
+                        }
+                    };
+                }
+
+                /// Checks that the top `$count` values are temporaries rather than locals of the frame
+                macro_rules! operands {
+                    ($count:expr) => {
+                        if self.stack.len() < locals.end + $count {
+                            return Err(RuntimeErrorKind::StackUnderflow { offset });
+                        }
+                    };
+                }
+
+                macro_rules! binary {
+                    ($method:ident) => {{
+                        let right = pop!();
+                        let left = pop!();
+                        self.stack.push(left.$method(right)?);
+                    }};
+                }
+
+                macro_rules! compare {
+                    ($operation:literal, $ordering:pat) => {{
+                        let right = pop!();
+                        let left = pop!();
+                        let ordering = self.heap.compare(&left, &right).ok_or(RuntimeErrorKind::TypeMismatch { operation: $operation })?;
+                        self.stack.push(Value::Bool(matches!(ordering, $ordering)));
+                    }};
+                }
+
+                macro_rules! equal {
+                    ($operation:literal, $expected:literal) => {{
+                        let right = pop!();
+                        let left = pop!();
+                        let equal = self.heap.equal(&left, &right).ok_or(RuntimeErrorKind::TypeMismatch { operation: $operation })?;
+                        self.stack.push(Value::Bool(equal == $expected));
+                    }};
+                }
+
+                match byte {
+                    op::GET_LOCAL | op::GET_LOCAL_WIDE => {
+                        let (slot, len) = operand!();
+                        ip += len;
+
+                        let value = *self.local(&locals, slot)?;
+                        self.stack.push(value);
+                    }
+                    op::SET_LOCAL | op::SET_LOCAL_WIDE => {
+                        let (slot, len) = operand!();
+                        ip += len;
+
+                        let value = pop!();
+                        *self.local(&locals, slot)? = value;
+                    }
+                    op::REF_LOCAL | op::REF_LOCAL_WIDE => {
+                        let (slot, len) = operand!();
+                        ip += len;
+
+                        self.local(&locals, slot)?;
+                        self.stack.push(Value::Ref(locals.start + slot));
+                    }
+                    op::GET_GLOBAL | op::GET_GLOBAL_WIDE => {
+                        let (slot, len) = operand!();
+                        ip += len;
+
+                        let value = *self.global_slot(slot)?;
+                        self.stack.push(value);
+                    }
+                    op::SET_GLOBAL | op::SET_GLOBAL_WIDE => {
+                        let (slot, len) = operand!();
+                        ip += len;
+
+                        let value = pop!();
+                        *self.global_slot(slot)? = value;
+                    }
+                    op::REF_GLOBAL | op::REF_GLOBAL_WIDE => {
+                        let (slot, len) = operand!();
+                        ip += len;
+
+                        self.global_slot(slot)?;
+                        self.stack.push(Value::GlobalRef(slot));
+                    }
+                    op::LOAD_REF => {
+                        let reference = pop!();
+                        let value = *self.referenced(reference)?;
+                        self.stack.push(value);
+                    }
+                    op::STORE_REF => {
+                        let reference = pop!();
+                        let value = pop!();
+                        *self.referenced(reference)? = value;
+                    }
+                    op::POP => {
+                        pop!();
+                    }
+                    op::DUP => {
+                        let value = pop!();
+                        self.stack.push(value);
+                        self.stack.push(value);
+                    }
+                    op::RETURN => {
+                        let value = pop!();
+                        let frame = self.frames.pop().expect("the returning frame is active");
+
+                        // a `?` inside of a `try` returns before the handler is removed
+                        while self.handlers.last().is_some_and(|handler| handler.frames > self.frames.len()) {
+                            self.handlers.pop();
+                        }
+
+                        if self.frames.len() == depth {
+                            return Ok(value);
+                        }
+
+                        // the arguments and locals of the callee are replaced by its result
+                        self.stack.truncate(frame.base);
+                        self.stack.push(value);
+                        ip = frame.return_offset;
+
+                        continue 'frames;
+                    }
+                    op::LOAD_CONST | op::LOAD_CONST_WIDE => {
+                        let (slot, len) = operand!();
+                        ip += len;
+
+                        let value = module.constants.get(slot).ok_or(RuntimeErrorKind::InvalidConstant { slot })?;
+                        let value = self.heap.import(value);
+                        self.stack.push(value);
+
+                        if let Value::Object(_) = value {
+                            self.safepoint()?;
+                        }
+                    }
+                    op::PUSH_UNIT => self.stack.push(Value::Unit),
+                    op::PUSH_NONE => self.stack.push(Value::None),
+                    op::JUMP | op::JUMP_WIDE => {
+                        ip = operand!().0;
+                    }
+                    op::JUMP_IF_TRUE | op::JUMP_IF_TRUE_WIDE | op::JUMP_IF_FALSE | op::JUMP_IF_FALSE_WIDE => {
+                        let (target, len) = operand!();
+                        let jumps_on = matches!(byte, op::JUMP_IF_TRUE | op::JUMP_IF_TRUE_WIDE);
+
+                        if pop!().as_bool("conditional jump")? == jumps_on {
+                            ip = target;
+                        } else {
+                            ip += len;
+                        }
+                    }
+                    op::CALL | op::CALL_WIDE => {
+                        let (index, len) = operand!();
+                        let callee = module.functions.get(index).ok_or(RuntimeErrorKind::FunctionNotFound { index })?;
+                        operands!(callee.arity);
+
+                        self.frames.last_mut().expect("the calling frame is active").offset = offset;
+
+                        // the arguments on top of the stack become the callee's first locals
+                        let callee_base = self.stack.len() - callee.arity;
+                        self.enter(module, index, callee_base, ip + len)?;
+                        ip = 0;
+
+                        continue 'frames;
+                    }
+                    op::CALL_NATIVE | op::CALL_NATIVE_WIDE => {
+                        let (index, len) = operand!();
+                        ip += len;
+
+                        let native = imports
+                            .get(index)
+                            .map(|&native| &self.natives[native])
+                            .ok_or(RuntimeErrorKind::InvalidInstruction { offset })?;
+
+                        let arity = native.signature.params.len();
+                        operands!(arity);
+
+                        let args = self
+                            .stack
+                            .drain(self.stack.len() - arity..)
+                            .map(|arg| self.heap.export(&arg))
+                            .collect::<Vec<_>>();
+
+                        let result = (native.func)(&args).map_err(|message| RuntimeErrorKind::HostError {
+                            function: native.signature.name.clone(),
+                            message,
+                        })?;
+
+                        // the compiler trusts the declared return type
+                        if !result.matches_type(&native.signature.return_type) {
+                            return Err(RuntimeErrorKind::HostReturnMismatch {
+                                function: native.signature.name.clone(),
+                                expected: native.signature.return_type.to_string(),
+                                found: result.type_kind().to_string(),
+                            });
+                        }
+
+                        let result = self.heap.import(&result);
+                        self.stack.push(result);
+
+                        if let Value::Object(_) = result {
+                            self.safepoint()?;
+                        }
+                    }
+                    op::ADD => {
+                        let right = pop!();
+                        let left = pop!();
+
+                        match (self.heap.string(&left), self.heap.string(&right)) {
+                            (Some(left), Some(right)) => {
+                                let result = self.heap.allocate_string([left, right].concat());
+                                self.stack.push(result);
+                                self.safepoint()?;
+                            }
+                            _ => self.stack.push(left.checked_add(right)?),
+                        }
+                    }
+                    op::CALL_INTRINSIC => {
+                        let (id, len) = operand!();
+                        ip += len;
+
+                        let intrinsic = u16::try_from(id)
+                            .ok()
+                            .and_then(Intrinsic::from_id)
+                            .ok_or(RuntimeErrorKind::InvalidInstruction { offset })?;
+                        operands!(intrinsic.arity());
+
+                        let args = self.stack.split_off(self.stack.len() - intrinsic.arity());
+                        let result = call_intrinsic(&mut self.heap, intrinsic, &args)?;
+                        self.stack.push(result);
+
+                        if let Value::Object(_) = result {
+                            self.safepoint()?;
+                        }
+                    }
+                    op::CONCAT => {
+                        let (count, len) = operand!();
+                        ip += len;
+                        operands!(count);
+
+                        let mut result = String::new();
+                        for value in &self.stack[self.stack.len() - count..] {
+                            result.push_str(&self.heap.display(value));
+                        }
+
+                        self.stack.truncate(self.stack.len() - count);
+                        let result = self.heap.allocate_string(result);
+                        self.stack.push(result);
+                        self.safepoint()?;
+                    }
+                    op::SUB => binary!(checked_sub),
+                    op::MUL => binary!(checked_mul),
+                    op::DIV => binary!(checked_div),
+                    op::MOD => binary!(checked_rem),
+                    op::BIT_AND => binary!(bit_and),
+                    op::BIT_OR => binary!(bit_or),
+                    op::BIT_XOR => binary!(bit_xor),
+                    op::SHIFT_LEFT => binary!(shift_left),
+                    op::SHIFT_RIGHT => binary!(shift_right),
+                    op::EQUAL => equal!("equality", true),
+                    op::NOT_EQUAL => equal!("inequality", false),
+                    op::GREATER_THAN => compare!("comparison", Ordering::Greater),
+                    op::LESSER_THAN => compare!("comparison", Ordering::Less),
+                    op::GREATER_THAN_EQUAL => compare!("comparison", Ordering::Greater | Ordering::Equal),
+                    op::LESSER_THAN_EQUAL => compare!("comparison", Ordering::Less | Ordering::Equal),
+                    op::AND | op::OR => {
+                        let right = pop!().as_bool("logical operation")?;
+                        let left = pop!().as_bool("logical operation")?;
+                        let result = if byte == op::AND { left && right } else { left || right };
+                        self.stack.push(Value::Bool(result));
+                    }
+                    op::NEGATE => {
+                        let value = pop!();
+                        self.stack.push(value.negate()?);
+                    }
+                    op::NOT => {
+                        let value = pop!().as_bool("not")?;
+                        self.stack.push(Value::Bool(!value));
+                    }
+                    op::TRY | op::TRY_WIDE => {
+                        let (target, len) = operand!();
+                        ip += len;
+
+                        self.handlers.push(Handler {
+                            frames: self.frames.len(),
+                            stack: self.stack.len(),
+                            target,
+                        });
+                    }
+                    op::END_TRY => {
+                        self.handlers.pop();
+                    }
+                    op::WRAP_ERR => {
+                        let value = pop!();
+                        let error = self.heap.allocate_err(value);
+                        self.stack.push(error);
+                        self.safepoint()?;
+                    }
+                    op::IS_ERR => {
+                        let value = pop!();
+                        let is_err = self.heap.err(&value).is_some();
+                        self.stack.push(Value::Bool(is_err));
+                    }
+                    op::UNWRAP_ERR => {
+                        let value = pop!();
+                        let error = *self.heap.err(&value).ok_or(RuntimeErrorKind::TypeMismatch { operation: "unwrapping an error" })?;
+                        self.stack.push(error);
+                    }
+                    _ => return Err(RuntimeErrorKind::InvalidInstruction { offset }),
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn local(&mut self, locals: &std::ops::Range<usize>, slot: usize) -> Result<&mut Value, RuntimeErrorKind> {
+        if slot >= locals.len() {
+            return Err(RuntimeErrorKind::InvalidLocal { slot });
+        }
+
+        Ok(&mut self.stack[locals.start + slot])
+    }
+
+    #[inline]
+    fn global_slot(&mut self, slot: usize) -> Result<&mut Value, RuntimeErrorKind> {
+        self.globals.get_mut(slot).ok_or(RuntimeErrorKind::InvalidGlobal { slot })
+    }
+
+    /// The variable a reference refers to, a global or a local of the running function or one of its callers
+    #[inline]
+    fn referenced(&mut self, reference: Value) -> Result<&mut Value, RuntimeErrorKind> {
+        match reference {
+            Value::Ref(index) => self.stack.get_mut(index).ok_or(RuntimeErrorKind::InvalidReference { index }),
+            Value::GlobalRef(slot) => self.global_slot(slot),
+            _ => Err(RuntimeErrorKind::TypeMismatch { operation: "dereferencing" }),
+        }
+    }
+}
+
+/// Reads the operand starting at `at` along with its byte length, wide operands are u32 and narrow ones u16
+#[inline]
+fn read_operand(code: &[u8], at: usize, wide: bool) -> Option<(usize, usize)> {
+    if wide {
+        let bytes = code.get(at..at + 4)?;
+        Some((u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize, 4))
+    } else {
+        let bytes = code.get(at..at + 2)?;
+        Some((u16::from_le_bytes([bytes[0], bytes[1]]) as usize, 2))
     }
 }